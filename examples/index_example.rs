@@ -118,12 +118,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Try to drop index");
     client.release_collection(COLLECTION_NAME).await?;
 
-    match client.drop_index(COLLECTION_NAME, "my_trie").await {
+    match client
+        .drop_index(COLLECTION_NAME, "title", Some("my_trie"))
+        .await
+    {
         Ok(_) => println!("Successfully dropped index for title field"),
         Err(e) => println!("Caught error when dropping index: {}", e),
     }
 
-    match client.drop_index(COLLECTION_NAME, "my_trie").await {
+    match client
+        .drop_index(COLLECTION_NAME, "title", Some("my_trie"))
+        .await
+    {
         Ok(_) => println!("Successfully dropped index for title field"),
         Err(e) => println!("Caught error when dropping index: {}", e),
     }
@@ -232,28 +232,11 @@ fn print_search_results(res: &Vec<SearchResult<'_>>) {
     }
 }
 
-fn print_get_results(res: &Vec<FieldColumn>) {
-    let id_column = res.iter().find(|col| col.name == USER_ID).unwrap();
-    let age_column = res.iter().find(|col| col.name == AGE).unwrap();
-    let deposit_column = res.iter().find(|col| col.name == DEPOSIT).unwrap();
-    let picture_column = res.iter().find(|col| col.name == PICTURE).unwrap();
-
-    let ids: Vec<i64> = id_column.value.clone().try_into().unwrap();
-    let ages: Vec<i64> = age_column.value.clone().try_into().unwrap();
-    let deposits: Vec<f64> = deposit_column.value.clone().try_into().unwrap();
-    let pictures: Vec<f32> = picture_column.value.clone().try_into().unwrap();
-    for (id, age, deposit, picture) in ids
-        .iter()
-        .zip(ages.iter())
-        .zip(deposits.iter())
-        .zip(pictures.chunks(DIM as usize))
-        .map(|(((id, age), deposit), picture)| {
-            (id.clone(), age.clone(), deposit.clone(), picture.to_vec())
-        })
-    {
+fn print_get_results(res: &Vec<Option<HashMap<String, Value<'static>>>>) {
+    for row in res.iter().flatten() {
         println!(
-            "id: {} age: {} deposit: {} picture: {:?}",
-            id, age, deposit, picture
+            "id: {:?} age: {:?} deposit: {:?} picture: {:?}",
+            row[USER_ID], row[AGE], row[DEPOSIT], row[PICTURE]
         );
     }
 }
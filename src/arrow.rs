@@ -0,0 +1,270 @@
+// Licensed to the LF AI & Data foundation under one
+// or more contributor license agreements. See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership. The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversion of query results to Apache Arrow, enabled by the `arrow` feature.
+//!
+//! Each [`FieldColumn`] becomes one Arrow column with the same name, nullable
+//! when the column carries `valid_data`:
+//!
+//! | Milvus type | Arrow type |
+//! |---|---|
+//! | `Bool` | `Boolean` |
+//! | `Int8` / `Int16` / `Int32` / `Int64` | `Int8` / `Int16` / `Int32` / `Int64` |
+//! | `Float` / `Double` | `Float32` / `Float64` |
+//! | `VarChar` / `String` | `Utf8` |
+//! | `Json` | `Binary`, holding the raw JSON bytes |
+//! | `FloatVector` | `FixedSizeList<Float32>` of `dim` items |
+//! | `BinaryVector` | `FixedSizeBinary` of `dim / 8` bytes |
+//! | `Float16Vector` / `BFloat16Vector` | `FixedSizeBinary` of `dim * 2` bytes |
+//! | `SparseFloatVector` | `List<Struct<index: UInt32, value: Float32>>` |
+//!
+//! Primary keys are not treated specially, an `Int64` or `VarChar` primary key
+//! maps like any other column of that type. `Array` columns are not supported.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use milvus::arrow::ToRecordBatch;
+//!
+//! let results = client.query("my_collection", "id > 0", &options).await?;
+//! let batch = results.to_record_batch()?;
+//! ```
+
+use std::sync::Arc;
+
+use arrow_array::builder::{
+    FixedSizeBinaryBuilder, FixedSizeListBuilder, Float32Builder, ListBuilder, NullBufferBuilder,
+    StructBuilder, UInt32Builder,
+};
+use arrow_array::{
+    Array, ArrayRef, BinaryArray, BooleanArray, Float32Array, Float64Array, Int8Array, Int16Array,
+    Int32Array, Int64Array, NullArray, RecordBatch, StringArray, make_array,
+};
+use arrow_schema::{DataType as ArrowDataType, Field, Schema};
+
+use crate::data::FieldColumn;
+use crate::error::{Error, Result};
+use crate::proto::schema::DataType;
+use crate::value::ValueVec;
+
+/// Converts a set of columns into an Arrow [`RecordBatch`].
+pub trait ToRecordBatch {
+    /// Builds a record batch with one column per field, see the [module docs](self)
+    /// for the type mapping.
+    fn to_record_batch(&self) -> Result<RecordBatch>;
+}
+
+impl ToRecordBatch for [FieldColumn] {
+    fn to_record_batch(&self) -> Result<RecordBatch> {
+        let num_rows = self.iter().map(FieldColumn::len).max().unwrap_or(0);
+        let arrays = self
+            .iter()
+            .map(|column| column_to_array(column, num_rows).and_then(|a| with_nulls(column, a)))
+            .collect::<Result<Vec<_>>>()?;
+        let fields = self
+            .iter()
+            .zip(&arrays)
+            .map(|(column, array)| {
+                let nullable = !column.valid_data.is_empty();
+                Field::new(&column.name, array.data_type().clone(), nullable)
+            })
+            .collect::<Vec<_>>();
+
+        Ok(RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)?)
+    }
+}
+
+fn column_to_array(column: &FieldColumn, num_rows: usize) -> Result<ArrayRef> {
+    let unsupported = || {
+        Error::Unexpected(format!(
+            "field {} of type {:?} can not be converted to arrow",
+            column.name, column.dtype
+        ))
+    };
+
+    let array: ArrayRef = match (&column.value, column.dtype) {
+        (ValueVec::None, _) => Arc::new(NullArray::new(num_rows)),
+        (ValueVec::Bool(v), _) => Arc::new(BooleanArray::from(v.clone())),
+        (ValueVec::Int(v), DataType::Int8) => {
+            Arc::new(v.iter().map(|&x| x as i8).collect::<Int8Array>())
+        }
+        (ValueVec::Int(v), DataType::Int16) => {
+            Arc::new(v.iter().map(|&x| x as i16).collect::<Int16Array>())
+        }
+        (ValueVec::Int(v), _) => Arc::new(Int32Array::from(v.clone())),
+        (ValueVec::Long(v), _) => Arc::new(Int64Array::from(v.clone())),
+        (ValueVec::Float(v), DataType::FloatVector) => {
+            let dim = column.dim as usize;
+            let mut builder = FixedSizeListBuilder::new(Float32Builder::new(), column.dim as i32);
+            for row in v.chunks_exact(dim.max(1)) {
+                builder.values().append_slice(row);
+                builder.append(true);
+            }
+            Arc::new(builder.finish())
+        }
+        (ValueVec::Float(v), _) => Arc::new(Float32Array::from(v.clone())),
+        (ValueVec::Double(v), _) => Arc::new(Float64Array::from(v.clone())),
+        (ValueVec::String(v), _) => Arc::new(StringArray::from(v.clone())),
+        (ValueVec::Json(v), _) => Arc::new(BinaryArray::from_iter_values(v)),
+        (ValueVec::Binary(v), dtype) => {
+            let width = match dtype {
+                DataType::BinaryVector => column.dim / 8,
+                DataType::Float16Vector | DataType::BFloat16Vector => column.dim * 2,
+                _ => return Err(unsupported()),
+            };
+            let mut builder = FixedSizeBinaryBuilder::new(width as i32);
+            for row in v.chunks_exact((width as usize).max(1)) {
+                builder.append_value(row)?;
+            }
+            Arc::new(builder.finish())
+        }
+        (ValueVec::SparseFloatVector(v), _) => {
+            let entry_fields = vec![
+                Field::new("index", ArrowDataType::UInt32, false),
+                Field::new("value", ArrowDataType::Float32, false),
+            ];
+            let entries = StructBuilder::new(
+                entry_fields,
+                vec![
+                    Box::new(UInt32Builder::new()),
+                    Box::new(Float32Builder::new()),
+                ],
+            );
+            let mut builder = ListBuilder::new(entries);
+            for row in v {
+                let entries = builder.values();
+                for &(index, value) in row {
+                    entries
+                        .field_builder::<UInt32Builder>(0)
+                        .ok_or_else(unsupported)?
+                        .append_value(index);
+                    entries
+                        .field_builder::<Float32Builder>(1)
+                        .ok_or_else(unsupported)?
+                        .append_value(value);
+                    entries.append(true);
+                }
+                builder.append(true);
+            }
+            Arc::new(builder.finish())
+        }
+        (ValueVec::Array(_), _) => return Err(unsupported()),
+    };
+
+    Ok(array)
+}
+
+/// Marks the rows of a nullable column that `valid_data` flags as invalid null.
+fn with_nulls(column: &FieldColumn, array: ArrayRef) -> Result<ArrayRef> {
+    if column.valid_data.is_empty() || matches!(column.value, ValueVec::None) {
+        return Ok(array);
+    }
+
+    let mut nulls = NullBufferBuilder::new(column.valid_data.len());
+    for &valid in &column.valid_data {
+        nulls.append(valid);
+    }
+    let data = array
+        .to_data()
+        .into_builder()
+        .nulls(nulls.finish())
+        .build()?;
+    Ok(make_array(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::FieldSchema;
+
+    #[test]
+    fn test_to_record_batch() {
+        let columns = [
+            FieldColumn::new(
+                &FieldSchema::new_primary_int64("id", "", false),
+                vec![1i64, 2],
+            ),
+            FieldColumn::new(
+                &FieldSchema::new_varchar("title", "", 64),
+                vec!["a".to_string(), "b".to_string()],
+            ),
+            FieldColumn::new(
+                &FieldSchema::new_float_vector("dense", "", 2),
+                vec![0.1f32, 0.2, 0.3, 0.4],
+            ),
+            FieldColumn::new(
+                &FieldSchema::new_sparse_float_vector("sparse", ""),
+                vec![vec![(3u32, 0.5f32)], vec![(1, 0.25), (7, 1.0)]],
+            ),
+        ];
+
+        let batch = columns.to_record_batch().unwrap();
+
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.num_columns(), 4);
+        let schema = batch.schema();
+        let names: Vec<&str> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+        assert_eq!(names, vec!["id", "title", "dense", "sparse"]);
+
+        assert_eq!(schema.field(0).data_type(), &ArrowDataType::Int64);
+        assert_eq!(schema.field(1).data_type(), &ArrowDataType::Utf8);
+        assert!(matches!(
+            schema.field(2).data_type(),
+            ArrowDataType::FixedSizeList(item, 2) if item.data_type() == &ArrowDataType::Float32
+        ));
+        assert!(matches!(
+            schema.field(3).data_type(),
+            ArrowDataType::List(item) if matches!(item.data_type(), ArrowDataType::Struct(f) if f.len() == 2)
+        ));
+    }
+
+    #[test]
+    fn test_to_record_batch_nullable() {
+        let mut price = FieldColumn::new(
+            &FieldSchema::new_int64("price", "").with_nullable(),
+            vec![10i64, 0, 30],
+        );
+        price.valid_data = vec![true, false, true];
+        let columns = [
+            FieldColumn::new(
+                &FieldSchema::new_primary_int64("id", "", false),
+                vec![1i64, 2, 3],
+            ),
+            price,
+        ];
+
+        let batch = columns.to_record_batch().unwrap();
+
+        let schema = batch.schema();
+        assert!(!schema.field(0).is_nullable());
+        assert!(schema.field(1).is_nullable());
+        assert_eq!(batch.column(0).null_count(), 0);
+        let price = batch.column(1);
+        assert_eq!(price.null_count(), 1);
+        assert!(price.is_valid(0));
+        assert!(price.is_null(1));
+        assert!(price.is_valid(2));
+    }
+
+    #[test]
+    fn test_to_record_batch_unsupported() {
+        let columns = [FieldColumn::new(
+            &FieldSchema::new_int64("array", ""),
+            ValueVec::Array(Vec::new()),
+        )];
+        assert!(columns.to_record_batch().is_err());
+    }
+}
@@ -15,23 +15,30 @@
 // limitations under the License.
 
 use crate::collection::CollectionCache;
-use crate::config::RPC_TIMEOUT;
+use crate::config::{RPC_TIMEOUT, WAIT_FLUSH_DURATION_MS};
 use crate::error::{Error, Result};
 pub use crate::proto::common::ConsistencyLevel;
+pub use tonic::codec::CompressionEncoding;
 use crate::proto::common::{MsgBase, MsgType};
 use crate::proto::milvus::milvus_service_client::MilvusServiceClient;
-use crate::proto::milvus::FlushRequest;
+use crate::proto::milvus::{
+    CheckHealthRequest, FlushAllRequest, FlushRequest, GetFlushAllStateRequest, GetMetricsRequest,
+    GetVersionRequest,
+};
 use crate::utils::status_to_result;
 use base64::engine::general_purpose;
 use base64::Engine;
 use std::collections::HashMap;
 use std::convert::TryInto;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tonic::codegen::{InterceptedService, StdError};
 use tonic::service::Interceptor;
 use tonic::transport::Channel;
 use tonic::Request;
 
+/// Request for [`Client::get_metrics`] returning the system info of every node.
+pub const SYSTEM_INFO_METRICS: &str = r#"{"metric_type":"system_info"}"#;
+
 #[derive(Debug, Clone)]
 pub struct AuthInterceptor {
     token: Option<String>,
@@ -53,16 +60,31 @@ impl Interceptor for AuthInterceptor {
         &mut self,
         mut req: Request<()>,
     ) -> std::result::Result<tonic::Request<()>, tonic::Status> {
-        if let Some(ref token) = self.token {
-            let header_value = format!("{}", token);
+        // Without credentials no header is sent at all, some unauthenticated
+        // servers reject an empty authorization header.
+        if let Some(token) = self.token.as_ref().filter(|token| !token.is_empty()) {
             req.metadata_mut()
-                .insert("authorization", header_value.parse().unwrap());
+                .insert("authorization", token.parse().unwrap());
         }
 
         Ok(req)
     }
 }
 
+/// Builds the authorization token for a username and password, `None` when no
+/// credentials are configured or both are empty, as for a local server without
+/// authentication.
+fn auth_token(username: Option<&str>, password: Option<&str>) -> Option<String> {
+    match (username, password) {
+        (Some(""), Some("")) => None,
+        (Some(username), Some(password)) => {
+            let auth_token = format!("{}:{}", username, password);
+            Some(general_purpose::STANDARD.encode(auth_token))
+        }
+        _ => None,
+    }
+}
+
 impl Interceptor for DbInterceptor {
     fn call(
         &mut self,
@@ -90,12 +112,159 @@ impl Interceptor for CombinedInterceptor {
     }
 }
 
+/// Maximum size of a gRPC message sent or received by the client
+const DEFAULT_MAX_MESSAGE_SIZE: usize = 256 * 1024 * 1024; // 256MB
+
+/// Connection options for [`Client::with_config`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use milvus::client::{Client, ClientConfig};
+/// use std::time::Duration;
+///
+/// let config = ClientConfig::new("http://localhost:19530")
+///     .username("root")
+///     .password("Milvus")
+///     .timeout(Duration::from_secs(30));
+/// let client = Client::with_config(config).await?;
+/// ```
+#[derive(Clone)]
+pub struct ClientConfig {
+    /// Address of the Milvus server, e.g. `http://localhost:19530`.
+    pub address: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Timeout of each RPC.
+    pub timeout: Duration,
+    /// Timeout for establishing the connection, no timeout if `None`.
+    pub connect_timeout: Option<Duration>,
+    /// Maximum size in bytes of a message sent to or received from the server.
+    pub max_message_size: usize,
+    /// Number of attempts to connect, with more than one the server must also pass a
+    /// health check before the client is returned.
+    pub connect_attempts: u32,
+    /// Delay between two connection attempts.
+    pub connect_retry_delay: Duration,
+    /// Database every call is made in, the server default database if `None`.
+    pub db_name: Option<String>,
+    /// Whether search and query first check the collection exists and is loaded.
+    pub precheck_loaded: bool,
+    /// Compression of the messages sent to the server, which is also asked to
+    /// compress its responses. No compression if `None`.
+    pub compression: Option<CompressionEncoding>,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            address: "http://localhost:19530".to_owned(),
+            username: None,
+            password: None,
+            timeout: RPC_TIMEOUT,
+            connect_timeout: None,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            connect_attempts: 1,
+            connect_retry_delay: Duration::ZERO,
+            db_name: None,
+            precheck_loaded: false,
+            compression: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for ClientConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientConfig")
+            .field("address", &self.address)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "***"))
+            .field("timeout", &self.timeout)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("max_message_size", &self.max_message_size)
+            .field("connect_attempts", &self.connect_attempts)
+            .field("connect_retry_delay", &self.connect_retry_delay)
+            .field("db_name", &self.db_name)
+            .field("precheck_loaded", &self.precheck_loaded)
+            .field("compression", &self.compression)
+            .finish()
+    }
+}
+
+impl ClientConfig {
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn username(mut self, username: &str) -> Self {
+        self.username = Some(username.to_owned());
+        self
+    }
+
+    pub fn password(mut self, password: &str) -> Self {
+        self.password = Some(password.to_owned());
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    pub fn max_message_size(mut self, max_message_size: usize) -> Self {
+        self.max_message_size = max_message_size;
+        self
+    }
+
+    pub fn connect_retry(mut self, max_attempts: u32, delay: Duration) -> Self {
+        self.connect_attempts = max_attempts.max(1);
+        self.connect_retry_delay = delay;
+        self
+    }
+
+    /// Makes every call in `db_name` instead of the default database, see
+    /// [`Client::with_database`] to use another one for some calls.
+    pub fn database(mut self, db_name: &str) -> Self {
+        self.db_name = Some(db_name.to_owned());
+        self
+    }
+
+    /// Makes search and query fail early with `CollectionNotFound` or `NotLoaded`
+    /// when the collection does not exist or is not loaded, instead of with the
+    /// server's error. It costs two round trips the first time a collection is
+    /// searched or queried.
+    pub fn precheck_loaded(mut self, precheck_loaded: bool) -> Self {
+        self.precheck_loaded = precheck_loaded;
+        self
+    }
+
+    /// Compresses requests with `encoding` and accepts responses compressed
+    /// with it, e.g. `CompressionEncoding::Gzip` for large inserts over a slow
+    /// link. It trades CPU on both ends for bandwidth, so it rarely pays off on
+    /// a local network or for small messages.
+    pub fn compression(mut self, encoding: CompressionEncoding) -> Self {
+        self.compression = Some(encoding);
+        self
+    }
+}
+
 #[derive(Clone)]
 pub struct ClientBuilder<D> {
     dst: D,
     username: Option<String>,
     password: Option<String>,
     timeout: Option<Duration>,
+    connect_retry: Option<(u32, Duration)>,
+    db_name: Option<String>,
+    compression: Option<CompressionEncoding>,
 }
 
 impl<D> ClientBuilder<D>
@@ -110,6 +279,9 @@ where
             username: None,
             password: None,
             timeout: None,
+            connect_retry: None,
+            db_name: None,
+            compression: None,
         }
     }
 
@@ -128,14 +300,94 @@ where
         self
     }
 
+    /// Retries the initial connection and health check until the server responds,
+    /// for servers that may still be starting up.
+    pub fn connect_retry(mut self, max_attempts: u32, delay: Duration) -> Self {
+        self.connect_retry = Some((max_attempts, delay));
+        self
+    }
+
+    /// Makes every call in `db_name` instead of the default database.
+    pub fn database(mut self, db_name: &str) -> Self {
+        self.db_name = Some(db_name.to_owned());
+        self
+    }
+
+    /// Compresses the messages exchanged with the server, see
+    /// [`ClientConfig::compression`] for the tradeoff.
+    pub fn compression(mut self, encoding: CompressionEncoding) -> Self {
+        self.compression = Some(encoding);
+        self
+    }
+
     pub async fn build(self) -> Result<Client> {
-        Client::with_timeout(
-            self.dst,
-            self.timeout.unwrap_or(RPC_TIMEOUT),
-            self.username,
-            self.password,
-        )
-        .await
+        let dst: tonic::transport::Endpoint = self.dst.try_into().map_err(|err| {
+            Error::InvalidParameter("url".to_owned(), format!("to parse {:?}", err))
+        })?;
+
+        let mut config = ClientConfig {
+            address: dst.uri().to_string(),
+            username: self.username,
+            password: self.password,
+            timeout: self.timeout.unwrap_or(RPC_TIMEOUT),
+            db_name: self.db_name,
+            compression: self.compression,
+            ..Default::default()
+        };
+        if let Some((max_attempts, delay)) = self.connect_retry {
+            config = config.connect_retry(max_attempts, delay);
+        }
+        Client::connect(dst, config).await
+    }
+}
+
+/// Creates the gRPC client over `channel`, authenticating with `auth` and
+/// making its calls in `db_name`, with the message size and compression of
+/// `config`.
+pub(crate) fn service_client(
+    channel: Channel,
+    auth: AuthInterceptor,
+    db_name: Option<String>,
+    config: &ClientConfig,
+) -> MilvusServiceClient<InterceptedService<Channel, CombinedInterceptor>> {
+    let interceptor = CombinedInterceptor {
+        auth,
+        db: DbInterceptor { db_name },
+    };
+    let client = MilvusServiceClient::with_interceptor(channel, interceptor)
+        .max_decoding_message_size(config.max_message_size)
+        .max_encoding_message_size(config.max_message_size);
+    match config.compression {
+        Some(encoding) => client.send_compressed(encoding).accept_compressed(encoding),
+        None => client,
+    }
+}
+
+/// Runs `f` up to `max_attempts` times, sleeping `delay` after each failure.
+///
+/// # Errors
+///
+/// Returns `Error::Timeout` with the last error once every attempt has failed
+async fn retry<T, F, Fut>(max_attempts: u32, delay: Duration, mut f: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        match f().await {
+            Ok(value) => return Ok(value),
+            Err(err) if attempt >= max_attempts => {
+                return Err(Error::Timeout(format!(
+                    "no healthy server after {} attempts, last error: {}",
+                    attempt, err
+                )));
+            }
+            Err(_) => {
+                attempt += 1;
+                tokio::time::sleep(delay).await;
+            }
+        }
     }
 }
 
@@ -146,6 +398,7 @@ pub struct Client {
     pub(crate) db_name: Option<String>,
     pub(crate) channel: Channel,
     pub(crate) auth_interceptor: AuthInterceptor,
+    pub(crate) config: ClientConfig,
 }
 
 impl Client {
@@ -168,41 +421,175 @@ impl Client {
         D::Error: Into<StdError>,
         D::Error: std::fmt::Debug,
     {
-        let mut dst: tonic::transport::Endpoint = dst.try_into().map_err(|err| {
+        let dst: tonic::transport::Endpoint = dst.try_into().map_err(|err| {
             Error::InvalidParameter("url".to_owned(), format!("to parse {:?}", err))
         })?;
 
-        dst = dst.timeout(timeout);
-
-        let token = match (username, password) {
-            (Some(username), Some(password)) => {
-                let auth_token = format!("{}:{}", username, password);
-                let auth_token = general_purpose::STANDARD.encode(auth_token);
-                Some(auth_token)
-            }
-            _ => None,
+        let config = ClientConfig {
+            address: dst.uri().to_string(),
+            username,
+            password,
+            timeout,
+            ..Default::default()
         };
+        Self::connect(dst, config).await
+    }
 
-        let auth_interceptor = AuthInterceptor { token };
-        let db_interceptor = DbInterceptor { db_name: None };
-        let combined_interceptor = CombinedInterceptor {
-            auth: auth_interceptor.clone(),
-            db: db_interceptor,
-        };
+    /// Creates a client from a [`ClientConfig`].
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The connection options.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the connected client, or an error if the address is invalid or the connection fails.
+    pub async fn with_config(config: ClientConfig) -> Result<Self> {
+        let dst =
+            tonic::transport::Endpoint::from_shared(config.address.clone()).map_err(|err| {
+                Error::InvalidParameter("url".to_owned(), format!("to parse {:?}", err))
+            })?;
+        Self::connect(dst, config).await
+    }
+
+    /// Returns the connection options the client was created with.
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    async fn connect(dst: tonic::transport::Endpoint, config: ClientConfig) -> Result<Self> {
+        if config.connect_attempts <= 1 {
+            return Self::connect_once(dst, config).await;
+        }
+
+        let (max_attempts, delay) = (config.connect_attempts, config.connect_retry_delay);
+        retry(max_attempts, delay, || async {
+            let client = Self::connect_once(dst.clone(), config.clone()).await?;
+            client.ensure_healthy().await?;
+            Ok(client)
+        })
+        .await
+    }
+
+    async fn connect_once(
+        mut dst: tonic::transport::Endpoint,
+        config: ClientConfig,
+    ) -> Result<Self> {
+        dst = dst.timeout(config.timeout);
+        if let Some(connect_timeout) = config.connect_timeout {
+            dst = dst.connect_timeout(connect_timeout);
+        }
 
         let channel = tonic::transport::Endpoint::new(dst)?.connect().await?;
+        Ok(Self::from_channel(channel, config))
+    }
 
-        let client = MilvusServiceClient::with_interceptor(channel.clone(), combined_interceptor)
-            .max_decoding_message_size(256 * 1024 * 1024) // 256MB
-            .max_encoding_message_size(256 * 1024 * 1024); // 256MB
+    fn from_channel(channel: Channel, config: ClientConfig) -> Self {
+        let token = auth_token(config.username.as_deref(), config.password.as_deref());
+        let auth_interceptor = AuthInterceptor { token };
+        let client = service_client(
+            channel.clone(),
+            auth_interceptor.clone(),
+            config.db_name.clone(),
+            &config,
+        );
 
-        Ok(Self {
+        Self {
             client: client.clone(),
             collection_cache: CollectionCache::new(client),
-            db_name: None,
+            db_name: config.db_name.clone(),
             channel,
             auth_interceptor,
-        })
+            config,
+        }
+    }
+
+    /// Returns a client making its calls in `db_name`, overriding the database
+    /// this client uses for the calls made through it.
+    ///
+    /// The connection is shared, so this is cheap enough to do per call.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let archived = client.with_database("archive").list_collections().await?;
+    /// ```
+    pub fn with_database(&self, db_name: impl Into<String>) -> Client {
+        let db_name = Some(db_name.into());
+        let client = service_client(
+            self.channel.clone(),
+            self.auth_interceptor.clone(),
+            db_name.clone(),
+            &self.config,
+        );
+
+        Self {
+            client: client.clone(),
+            collection_cache: CollectionCache::new(client),
+            db_name,
+            channel: self.channel.clone(),
+            auth_interceptor: self.auth_interceptor.clone(),
+            config: self.config.clone(),
+        }
+    }
+
+    /// Measures the round-trip time of a `GetVersion` call, a cheap RPC that
+    /// touches no collection, to diagnose the connection.
+    ///
+    /// Like every call it is bounded by the client's timeout, after which the
+    /// error is returned instead of a latency.
+    pub async fn ping(&self) -> Result<Duration> {
+        let started = Instant::now();
+        let res = self
+            .client
+            .clone()
+            .get_version(GetVersionRequest {})
+            .await?
+            .into_inner();
+        let elapsed = started.elapsed();
+        status_to_result(&res.status)?;
+
+        Ok(elapsed)
+    }
+
+    /// Fetches the server's internal metrics through `GetMetrics`.
+    ///
+    /// `request` is the JSON request understood by the server, usually
+    /// [`SYSTEM_INFO_METRICS`]. The JSON response is returned parsed, an error
+    /// is returned if it is not valid JSON.
+    pub async fn get_metrics(&self, request: &str) -> Result<serde_json::Value> {
+        let res = self
+            .client
+            .clone()
+            .get_metrics(GetMetricsRequest {
+                base: Some(MsgBase::new(MsgType::SystemInfo)),
+                request: request.to_owned(),
+            })
+            .await?
+            .into_inner();
+        status_to_result(&res.status)?;
+
+        parse_metrics(&res.response)
+    }
+
+    /// Fails unless the server reports itself healthy
+    async fn ensure_healthy(&self) -> Result<()> {
+        let res = self
+            .client
+            .clone()
+            .check_health(CheckHealthRequest {})
+            .await?
+            .into_inner();
+        status_to_result(&res.status)?;
+
+        if res.is_healthy {
+            Ok(())
+        } else {
+            Err(Error::Unexpected(format!(
+                "server is not healthy: {}",
+                res.reasons.join(", ")
+            )))
+        }
     }
 
     pub async fn flush_collections<C>(&self, collections: C) -> Result<HashMap<String, Vec<i64>>>
@@ -230,6 +617,79 @@ impl Client {
             .collect())
     }
 
+    /// Flushes every collection, one at a time, returning the sealed segment
+    /// IDs of each.
+    ///
+    /// A collection failing to flush does not stop the others. If any fails,
+    /// [`Error::PartialFlush`] holds the segments of the flushed collections and
+    /// the error of each failed one.
+    pub async fn flush_all(&self) -> Result<HashMap<String, Vec<i64>>> {
+        let mut flushed = HashMap::new();
+        let mut failed = HashMap::new();
+        for name in self.list_collections().await? {
+            match self.flush_collections([&name]).await {
+                Ok(mut segments) => {
+                    let segments = segments.remove(&name).unwrap_or_default();
+                    flushed.insert(name, segments);
+                }
+                Err(err) => {
+                    failed.insert(name, err);
+                }
+            }
+        }
+
+        if failed.is_empty() {
+            Ok(flushed)
+        } else {
+            Err(Error::PartialFlush(flushed, failed))
+        }
+    }
+
+    /// Flushes every collection of every database with a single `FlushAll`,
+    /// then polls `GetFlushAllState` until the server reports all of it flushed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Timeout` if the flush is not done after `timeout`
+    pub async fn flush_all_and_wait(&self, timeout: Duration) -> Result<()> {
+        let res = self
+            .client
+            .clone()
+            .flush_all(FlushAllRequest {
+                base: Some(MsgBase::new(MsgType::Flush)),
+                ..Default::default()
+            })
+            .await?
+            .into_inner();
+        status_to_result(&res.status)?;
+
+        let started = Instant::now();
+        loop {
+            let state = self
+                .client
+                .clone()
+                .get_flush_all_state(GetFlushAllStateRequest {
+                    base: Some(MsgBase::new(MsgType::Flush)),
+                    flush_all_ts: res.flush_all_ts,
+                    ..Default::default()
+                })
+                .await?
+                .into_inner();
+            status_to_result(&state.status)?;
+            if state.flushed {
+                return Ok(());
+            }
+
+            if started.elapsed() >= timeout {
+                return Err(Error::Timeout(format!(
+                    "flush all not done after {:?}",
+                    timeout
+                )));
+            }
+            tokio::time::sleep(Duration::from_millis(WAIT_FLUSH_DURATION_MS)).await;
+        }
+    }
+
     // alias related:
 
     /// Creates an alias for a collection.
@@ -330,6 +790,11 @@ impl Client {
     /// # Returns
     ///
     /// Returns a `Result` containing the alias, collection name, and database name.
+    /// The collection name is the collection the alias currently points to.
+    ///
+    /// # Errors
+    ///
+    /// Returns a server error if the alias does not exist.
     pub async fn describe_alias(&self, alias: &str) -> Result<(String, String, String)> {
         let resp = self
             .client
@@ -372,3 +837,181 @@ impl Client {
         Ok((resp.db_name, resp.collection_name, resp.aliases))
     }
 }
+
+fn parse_metrics(response: &str) -> Result<serde_json::Value> {
+    serde_json::from_str(response)
+        .map_err(|e| Error::Unexpected(format!("malformed metrics response: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll};
+    use tonic::codegen::{Service, http};
+
+    #[test]
+    fn test_parse_metrics() {
+        let metrics = parse_metrics(r#"{"nodes_info":[{"identifier":1}]}"#).unwrap();
+        assert_eq!(metrics["nodes_info"][0]["identifier"], 1);
+
+        let err = parse_metrics("not json").unwrap_err();
+        assert!(
+            err.to_string().starts_with("malformed metrics response"),
+            "{err}"
+        );
+    }
+
+    fn lazy_client(config: ClientConfig) -> Client {
+        let channel =
+            tonic::transport::Endpoint::from_static("http://localhost:19530").connect_lazy();
+        Client::from_channel(channel, config)
+    }
+
+    /// A server answering every call with `Unimplemented`, recording the
+    /// dbname header each request arrived with
+    #[derive(Clone, Default)]
+    struct HeaderCapture {
+        dbnames: Arc<Mutex<Vec<Option<String>>>>,
+    }
+
+    impl tonic::server::NamedService for HeaderCapture {
+        const NAME: &'static str = "milvus.proto.milvus.MilvusService";
+    }
+
+    impl Service<http::Request<tonic::body::Body>> for HeaderCapture {
+        type Response = http::Response<tonic::body::Body>;
+        type Error = std::convert::Infallible;
+        type Future = std::future::Ready<std::result::Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(
+            &mut self,
+            _: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: http::Request<tonic::body::Body>) -> Self::Future {
+            let dbname = req
+                .headers()
+                .get("dbname")
+                .map(|v| v.to_str().unwrap().to_owned());
+            self.dbnames.lock().unwrap().push(dbname);
+            std::future::ready(Ok(tonic::Status::unimplemented("").into_http()))
+        }
+    }
+
+    /// Starts a `HeaderCapture` server, returning it with its address
+    async fn capture_server() -> (HeaderCapture, String) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = format!("http://{}", listener.local_addr().unwrap());
+        let capture = HeaderCapture::default();
+        let service = capture.clone();
+        tokio::spawn(async move {
+            tonic::transport::Server::builder()
+                .add_service(service)
+                .serve_with_incoming(tonic::transport::server::TcpIncoming::from(listener))
+                .await
+                .unwrap();
+        });
+        (capture, addr)
+    }
+
+    /// The dbname header the next call of `client` is sent with
+    async fn dbname_header(capture: &HeaderCapture, client: &Client) -> Option<String> {
+        assert!(client.list_collections().await.is_err());
+        capture.dbnames.lock().unwrap().pop().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_configured_database_header() {
+        let (capture, addr) = capture_server().await;
+        let connect = |config: ClientConfig| {
+            let channel = tonic::transport::Endpoint::from_shared(addr.clone())
+                .unwrap()
+                .connect_lazy();
+            Client::from_channel(channel, config)
+        };
+
+        let default = connect(ClientConfig::default());
+        assert_eq!(dbname_header(&capture, &default).await, None);
+
+        let client = connect(ClientConfig::default().database("analytics"));
+        assert_eq!(
+            dbname_header(&capture, &client).await.as_deref(),
+            Some("analytics")
+        );
+
+        let archive = client.with_database("archive");
+        assert_eq!(
+            dbname_header(&capture, &archive).await.as_deref(),
+            Some("archive")
+        );
+        assert_eq!(
+            dbname_header(&capture, &client).await.as_deref(),
+            Some("analytics")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gzip_compression() {
+        // the generated client only exposes its compression settings through Debug
+        let configured = |client: &Client| format!("{:?}", client.client).contains("Gzip");
+        assert!(!configured(&lazy_client(ClientConfig::default())));
+
+        let config = ClientConfig::default().compression(CompressionEncoding::Gzip);
+        let client = lazy_client(config);
+        assert_eq!(client.config().compression, Some(CompressionEncoding::Gzip));
+        assert!(configured(&client));
+        assert!(configured(&client.with_database("archive")));
+    }
+
+    #[test]
+    fn test_no_credentials_attach_no_auth_metadata() {
+        for (username, password) in [(None, None), (Some("root"), None), (Some(""), Some(""))] {
+            let mut interceptor = AuthInterceptor {
+                token: auth_token(username, password),
+            };
+            let req = interceptor.call(Request::new(())).unwrap();
+            assert!(req.metadata().get("authorization").is_none());
+        }
+
+        let mut interceptor = AuthInterceptor {
+            token: auth_token(Some("root"), Some("Milvus")),
+        };
+        let req = interceptor.call(Request::new(())).unwrap();
+        assert_eq!(
+            req.metadata().get("authorization").unwrap(),
+            general_purpose::STANDARD.encode("root:Milvus").as_str()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_succeeds_on_third_attempt() {
+        let attempts = AtomicU32::new(0);
+        let result = retry(5, Duration::from_millis(1), || async {
+            match attempts.fetch_add(1, Ordering::SeqCst) + 1 {
+                3 => Ok("connected"),
+                n => Err(Error::Unexpected(format!("attempt {} refused", n))),
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), "connected");
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_exhausted() {
+        let attempts = AtomicU32::new(0);
+        let result: Result<()> = retry(2, Duration::from_millis(1), || async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err(Error::Unexpected("refused".to_owned()))
+        })
+        .await;
+
+        assert!(matches!(result, Err(Error::Timeout(msg)) if msg.contains("after 2 attempts")));
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+}
@@ -15,22 +15,28 @@
 // limitations under the License.
 
 use crate::config;
-use crate::data::FieldColumn;
+use crate::data::{FieldColumn, ToRows};
 use crate::error::{Error as SuperError, Result};
+use crate::index::{IndexInfo, MetricType};
+use crate::mutate::InsertOptions;
+use crate::proto::common::{IndexState, LoadState, SegmentState};
 use crate::proto::milvus::{
-    AlterCollectionFieldRequest, AlterCollectionRequest, CreateCollectionRequest,
-    DropCollectionRequest, FlushRequest, GetCompactionStateRequest, GetCompactionStateResponse,
-    HasCollectionRequest, LoadCollectionRequest, ManualCompactionRequest, ManualCompactionResponse,
-    ReleaseCollectionRequest, ShowCollectionsRequest,
+    AlterCollectionFieldRequest, AlterCollectionRequest, CompactionMergeInfo,
+    CreateCollectionRequest, DropCollectionRequest, FlushRequest, GetCompactionPlansRequest,
+    GetCompactionPlansResponse, GetCompactionStateRequest, GetCompactionStateResponse,
+    GetPersistentSegmentInfoRequest, HasCollectionRequest, IndexDescription, LoadCollectionRequest,
+    ManualCompactionRequest, ManualCompactionResponse, MutationResult, PersistentSegmentInfo,
+    ReleaseCollectionRequest, ShowCollectionsRequest, ShowType,
 };
 use crate::proto::schema::DataType;
+use crate::query::{QueryOptions, SearchOptions};
 use crate::schema::{CollectionSchema, CollectionSchemaBuilder};
 use crate::types::*;
 use crate::utils::status_to_result;
-use crate::value::Value;
+use crate::value::{RowMap, Value};
 use crate::{
     client::{Client, CombinedInterceptor},
-    options::{CreateCollectionOptions, GetLoadStateOptions, LoadOptions},
+    options::{CreateCollectionOptions, GetLoadStateOptions, GetStatsOptions, LoadOptions},
     proto::{
         self,
         common::{ConsistencyLevel, MsgBase, MsgType},
@@ -41,7 +47,7 @@ use prost::bytes::BytesMut;
 use prost::Message;
 use serde_json;
 use std::collections::HashMap;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use thiserror::Error as ThisError;
 use tonic::{service::interceptor::InterceptedService, transport::Channel};
 
@@ -58,6 +64,10 @@ pub struct Collection {
     // pub enable_dynamic_field: bool,
 }
 
+/// Collection property holding the time to live of its entities in seconds,
+/// set with [`Client::alter_collection_properties`].
+pub const TTL_PROPERTY: &str = "collection.ttl.seconds";
+
 /// Return type for describe collection,containing enough messages
 pub struct DescribeCollection {
     pub collection_name: String,
@@ -68,12 +78,136 @@ pub struct DescribeCollection {
     pub properties: Vec<proto::common::KeyValuePair>,
     pub num_partitions: i64,
     pub schema: crate::proto::schema::CollectionSchema,
+    /// Time to live of the entities, decoded from the [`TTL_PROPERTY`] property.
+    /// `None` if it is absent or zero, as entities then never expire.
+    pub ttl: Option<Duration>,
+}
+
+/// Overview of a collection, see [`Client::collection_summary`].
+#[derive(Debug, Clone)]
+pub struct CollectionSummary {
+    pub collection_name: String,
+    pub exists: bool,
+    pub load_state: LoadState,
+    /// Loading progress in percent (0-100), 0 if the collection is not loaded.
+    pub loading_progress: i64,
+    pub num_entities: i64,
+    /// Indexes of the collection, empty if no index has been created yet.
+    pub indexes: Vec<IndexInfo>,
+}
+
+impl CollectionSummary {
+    /// Returns true if the collection has at least one index and all of them are built.
+    pub fn is_indexed(&self) -> bool {
+        !self.indexes.is_empty()
+            && self
+                .indexes
+                .iter()
+                .all(|index| index.state() == IndexState::Finished)
+    }
+}
+
+/// A persisted segment of a collection, see [`Client::get_persistent_segment_info`].
+#[derive(Debug, Clone)]
+pub struct SegmentInfo {
+    pub segment_id: i64,
+    pub collection_id: i64,
+    pub partition_id: i64,
+    pub num_rows: i64,
+    pub state: SegmentState,
+}
+
+impl From<PersistentSegmentInfo> for SegmentInfo {
+    fn from(info: PersistentSegmentInfo) -> Self {
+        Self {
+            segment_id: info.segment_id,
+            collection_id: info.collection_id,
+            partition_id: info.partition_id,
+            num_rows: info.num_rows,
+            state: info.state(),
+        }
+    }
+}
+
+/// A client bound to one collection, see [`Client::collection`].
+///
+/// The handle holds a clone of the client, which shares the underlying
+/// connection, so it is cheap to create and can outlive the client it came from.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let books = client.collection("books");
+/// books.load(None).await?;
+/// let rows = books.query("id > 0", &QueryOptions::new()).await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct CollectionHandle {
+    client: Client,
+    name: String,
+}
+
+impl CollectionHandle {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// See [`Client::insert`].
+    pub async fn insert(
+        &self,
+        fields_data: Vec<FieldColumn>,
+        options: Option<InsertOptions>,
+    ) -> Result<MutationResult> {
+        self.client.insert(&self.name, fields_data, options).await
+    }
+
+    /// See [`Client::search`].
+    pub async fn search(
+        &self,
+        data: Vec<Value<'_>>,
+        options: Option<SearchOptions>,
+    ) -> Result<Vec<SearchResult<'_>>> {
+        self.client.search(&self.name, data, options).await
+    }
+
+    /// See [`Client::query`].
+    pub async fn query(&self, expr: &str, options: &QueryOptions) -> Result<Vec<FieldColumn>> {
+        self.client.query(&self.name, expr, options).await
+    }
+
+    /// See [`Client::load_collection`].
+    pub async fn load(&self, options: Option<LoadOptions>) -> Result<()> {
+        self.client.load_collection(&self.name, options).await
+    }
+
+    /// See [`Client::release_collection`].
+    pub async fn release(&self) -> Result<()> {
+        self.client.release_collection(&self.name).await
+    }
+
+    /// See [`Client::flush`].
+    pub async fn flush(&self) -> Result<()> {
+        self.client.flush(&self.name).await
+    }
+
+    /// See [`Client::get_collection_stats`].
+    pub async fn stats(&self) -> Result<HashMap<String, String>> {
+        self.client.get_collection_stats(&self.name, None).await
+    }
 }
 
 #[derive(Debug, Clone)]
 pub(crate) struct CollectionCache {
     collections: dashmap::DashMap<String, Collection>,
     timestamps: dashmap::DashMap<String, Timestamp>,
+    /// Collections seen loaded by a precheck, see [`Client::precheck_collection`]
+    loaded: dashmap::DashSet<String>,
+    /// Options of the last load through this client, see [`Client::load_fields`]
+    load_options: dashmap::DashMap<String, LoadOptions>,
     client: MilvusServiceClient<InterceptedService<Channel, CombinedInterceptor>>,
 }
 
@@ -84,6 +218,8 @@ impl CollectionCache {
         Self {
             collections: dashmap::DashMap::new(),
             timestamps: dashmap::DashMap::new(),
+            loaded: dashmap::DashSet::new(),
+            load_options: dashmap::DashMap::new(),
             client: client,
         }
     }
@@ -91,6 +227,8 @@ impl CollectionCache {
     pub fn clear(&self) {
         self.collections.clear();
         self.timestamps.clear();
+        self.loaded.clear();
+        self.load_options.clear();
     }
 
     pub async fn get<'a>(&self, name: &str) -> Result<Collection> {
@@ -121,6 +259,33 @@ impl CollectionCache {
             )))
     }
 
+    /// Drops the cached description of a collection, e.g. after altering it.
+    pub fn invalidate(&self, name: &str) {
+        self.collections.remove(name);
+        self.loaded.remove(name);
+    }
+
+    pub fn set_loaded(&self, name: &str) {
+        self.loaded.insert(name.to_owned());
+    }
+
+    pub fn unset_loaded(&self, name: &str) {
+        self.loaded.remove(name);
+        self.load_options.remove(name);
+    }
+
+    pub fn set_load_options(&self, name: &str, options: LoadOptions) {
+        self.load_options.insert(name.to_owned(), options);
+    }
+
+    pub fn load_options(&self, name: &str) -> Option<LoadOptions> {
+        self.load_options.get(name).map(|v| v.value().clone())
+    }
+
+    pub fn is_loaded(&self, name: &str) -> bool {
+        self.loaded.contains(name)
+    }
+
     pub fn update_timestamp(&self, name: &str, timestamp: Timestamp) {
         self.timestamps
             .entry(name.to_owned())
@@ -200,21 +365,80 @@ impl From<GetCompactionStateResponse> for CompactionState {
     }
 }
 
+/// The merges planned by a compaction, see
+/// [`Client::get_compaction_state_with_plans`].
+#[derive(Debug)]
+pub struct CompactionPlans {
+    pub state: crate::proto::common::CompactionState,
+    /// One entry per plan, empty if the compaction found nothing to merge
+    pub merges: Vec<CompactionMerge>,
+}
+
+/// A group of segments merged into one by a compaction plan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactionMerge {
+    pub sources: Vec<i64>,
+    pub target: i64,
+}
+
+impl From<GetCompactionPlansResponse> for CompactionPlans {
+    fn from(value: GetCompactionPlansResponse) -> Self {
+        Self {
+            state: crate::proto::common::CompactionState::try_from(value.state)
+                .unwrap_or(crate::proto::common::CompactionState::UndefiedState),
+            merges: value.merge_infos.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<CompactionMergeInfo> for CompactionMerge {
+    fn from(value: CompactionMergeInfo) -> Self {
+        Self {
+            sources: value.sources,
+            target: value.target,
+        }
+    }
+}
+
 //type ConcurrentHashMap<K, V> = tokio::sync::RwLock<std::collections::HashMap<K, V>>;
 
 impl Client {
+    /// Returns a handle whose methods act on the named collection, so the name
+    /// does not have to be passed to every call.
+    ///
+    /// The collection is not checked for existence until the handle is used.
+    pub fn collection(&self, name: impl Into<String>) -> CollectionHandle {
+        CollectionHandle {
+            client: self.clone(),
+            name: name.into(),
+        }
+    }
+
     /// Creates a new collection with the specified schema and options.
     ///
     /// # Arguments
     ///
     /// * `schema` - The schema of the collection.
     /// * `options` - Optional parameters for creating the collection.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if `shard_num` is set outside 1 to 16, the
+    /// server's default maximum.
     pub async fn create_collection(
         &self,
         schema: CollectionSchema,
         options: Option<CreateCollectionOptions>,
     ) -> Result<()> {
         let options = options.unwrap_or_default();
+        check_shard_num(options.shard_num)?;
+        let mut properties = Vec::new();
+        if schema.partition_key_isolation {
+            properties.push(proto::common::KeyValuePair {
+                key: "partitionkey.isolation".to_owned(),
+                value: "true".to_owned(),
+            });
+        }
         let schema: crate::proto::schema::CollectionSchema = schema.into();
         let mut buf = BytesMut::new();
 
@@ -229,6 +453,7 @@ impl Client {
                 schema: buf.to_vec(),
                 shards_num: options.shard_num,
                 consistency_level: options.consistency_level as i32,
+                properties,
                 ..Default::default()
             })
             .await?
@@ -237,6 +462,35 @@ impl Client {
         status_to_result(&Some(status))
     }
 
+    /// Creates a collection unless it already exists.
+    ///
+    /// If a collection with the same name exists, its schema is compared with
+    /// `schema` and an error is returned if they differ.
+    ///
+    /// # Arguments
+    ///
+    /// * `schema` - The schema of the collection.
+    /// * `options` - Optional parameters for creating the collection.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing `true` if the collection was created, or `false` if it already existed with a matching schema.
+    pub async fn create_collection_if_not_exists(
+        &self,
+        schema: CollectionSchema,
+        options: Option<CreateCollectionOptions>,
+    ) -> Result<bool> {
+        if self.has_collection(schema.name()).await? {
+            let existing: CollectionSchema =
+                self.describe_collection(schema.name()).await?.schema.into();
+            schema.validate_schema(&existing)?;
+            return Ok(false);
+        }
+
+        self.create_collection(schema, options).await?;
+        Ok(true)
+    }
+
     /// Drops a collection with the given name.
     ///
     /// # Arguments
@@ -250,17 +504,21 @@ impl Client {
     where
         S: Into<String>,
     {
+        let name = name.into();
         status_to_result(&Some(
             self.client
                 .clone()
                 .drop_collection(DropCollectionRequest {
                     base: Some(MsgBase::new(MsgType::DropCollection)),
-                    collection_name: name.into(),
+                    collection_name: name.clone(),
                     ..Default::default()
                 })
                 .await?
                 .into_inner(),
-        ))
+        ))?;
+        self.collection_cache.invalidate(&name);
+        self.collection_cache.unset_loaded(&name);
+        Ok(())
     }
 
     /// Retrieves a list of collections.
@@ -283,6 +541,48 @@ impl Client {
         Ok(response.collection_names)
     }
 
+    /// Retrieves the names of all collections along with their load state.
+    ///
+    /// Two `ShowCollections` requests are made whatever the number of collections: one
+    /// listing every collection, and one listing the collections in memory. A collection
+    /// in memory that can not serve queries yet is reported as `Loading`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a vector of `(name, load state)` pairs if successful, or an error if the operation fails.
+    pub async fn list_collections_with_state(&self) -> Result<Vec<(String, LoadState)>> {
+        let all = self.list_collections().await?;
+        let in_memory = self
+            .client
+            .clone()
+            .show_collections(ShowCollectionsRequest {
+                base: Some(MsgBase::new(MsgType::ShowCollections)),
+                r#type: ShowType::InMemory as i32,
+                ..Default::default()
+            })
+            .await?
+            .into_inner();
+        status_to_result(&in_memory.status)?;
+
+        let loaded: HashMap<String, LoadState> = in_memory
+            .collection_names
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| match in_memory.query_service_available.get(i) {
+                Some(false) => (name, LoadState::Loading),
+                _ => (name, LoadState::Loaded),
+            })
+            .collect();
+
+        Ok(all
+            .into_iter()
+            .map(|name| {
+                let state = loaded.get(&name).copied().unwrap_or(LoadState::NotLoad);
+                (name, state)
+            })
+            .collect())
+    }
+
     /// Retrieves information about a collection.
     ///
     /// # Arguments
@@ -317,6 +617,7 @@ impl Client {
             shards_num: resp.shards_num,
             aliases: resp.aliases,
             consistency_level: resp.consistency_level,
+            ttl: parse_ttl(&resp.properties),
             properties: resp.properties,
             num_partitions: resp.num_partitions,
             schema: resp.schema.unwrap_or_default(),
@@ -354,6 +655,34 @@ impl Client {
         Ok(res.value)
     }
 
+    /// Returns the data type of a field, looked up from the cached collection schema.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection.
+    /// * `field_name` - The name of the field.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `DataType` of the field, or an error if the collection or the field does not exist.
+    pub async fn field_data_type(
+        &self,
+        collection_name: &str,
+        field_name: &str,
+    ) -> Result<DataType> {
+        let collection = self.collection_cache.get(collection_name).await?;
+        collection
+            .fields
+            .iter()
+            .find(|f| f.name == field_name)
+            .map(|f| f.dtype)
+            .ok_or_else(|| {
+                SuperError::from(crate::schema::Error::FieldDoesNotExists(
+                    field_name.to_owned(),
+                ))
+            })
+    }
+
     pub async fn rename_collection<S>(
         &self,
         name: S,
@@ -392,11 +721,35 @@ impl Client {
     /// # Arguments
     ///
     /// * `name` - The name of the collection.
+    /// * `options` - Optional partitions to scope the statistics to. Their
+    ///   statistics are fetched one partition at a time and the `row_count`
+    ///   values summed.
     ///
     /// # Returns
     ///
     /// A `Result` containing a `HashMap` with string keys and string values representing the collection statistics.
-    pub async fn get_collection_stats(&self, name: &str) -> Result<HashMap<String, String>> {
+    pub async fn get_collection_stats(
+        &self,
+        name: &str,
+        options: Option<GetStatsOptions>,
+    ) -> Result<HashMap<String, String>> {
+        let options = options.unwrap_or_default();
+        if !options.partition_names.is_empty() {
+            let mut row_count = 0i64;
+            for partition_name in options.partition_names {
+                row_count += self
+                    .get_partition_stats(name.to_owned(), partition_name)
+                    .await?
+                    .get("row_count")
+                    .and_then(|count| count.parse::<i64>().ok())
+                    .unwrap_or(0);
+            }
+            return Ok(HashMap::from([(
+                "row_count".to_owned(),
+                row_count.to_string(),
+            )]));
+        }
+
         let res = self
             .client
             .clone()
@@ -413,6 +766,87 @@ impl Client {
         Ok(res.stats.into_iter().map(|s| (s.key, s.value)).collect())
     }
 
+    /// Retrieves the segments of a collection that are persisted in storage.
+    ///
+    /// Growing segments only held in memory are not included, flush the collection
+    /// first to persist recent inserts.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `SegmentInfo` of every persisted segment if successful, or an error if the operation fails.
+    pub async fn get_persistent_segment_info<S>(
+        &self,
+        collection_name: S,
+    ) -> Result<Vec<SegmentInfo>>
+    where
+        S: Into<String>,
+    {
+        let res = self
+            .client
+            .clone()
+            .get_persistent_segment_info(GetPersistentSegmentInfoRequest {
+                base: Some(MsgBase::new(MsgType::SegmentInfo)),
+                db_name: "".to_string(),
+                collection_name: collection_name.into(),
+            })
+            .await?
+            .into_inner();
+        status_to_result(&res.status)?;
+
+        Ok(res.infos.into_iter().map(Into::into).collect())
+    }
+
+    /// Aggregates the existence, load state, row count and index state of a collection.
+    ///
+    /// Missing pieces are reported instead of failing the call: a collection that does
+    /// not exist yields `exists == false`, a collection without an index yields an empty
+    /// `indexes`, and a collection that is not loaded yields a `loading_progress` of 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the collection.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `CollectionSummary` of the collection.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the underlying requests fails.
+    pub async fn collection_summary(&self, name: &str) -> Result<CollectionSummary> {
+        let mut summary = CollectionSummary {
+            collection_name: name.to_owned(),
+            exists: false,
+            load_state: LoadState::NotExist,
+            loading_progress: 0,
+            num_entities: 0,
+            indexes: Vec::new(),
+        };
+        if !self.has_collection(name).await? {
+            return Ok(summary);
+        }
+        summary.exists = true;
+
+        summary.collection_name = self.describe_collection(name).await?.collection_name;
+        summary.num_entities = self
+            .get_collection_stats(name, None)
+            .await?
+            .get("row_count")
+            .and_then(|count| count.parse().ok())
+            .unwrap_or(0);
+        summary.load_state = self.get_load_state(name, None).await?;
+        if matches!(summary.load_state, LoadState::Loading | LoadState::Loaded) {
+            summary.loading_progress = self.get_loading_progress(name, None).await?;
+        }
+        summary.indexes = self.describe_indexes(name).await?;
+
+        Ok(summary)
+    }
+
     /// Loads a collection with the given name and options.
     ///
     /// # Arguments
@@ -423,6 +857,12 @@ impl Client {
     /// # Returns
     ///
     /// Returns a `Result` indicating success or failure.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VectorFieldNotIndexed` without loading if any vector field of
+    /// the collection has no index, and `Error::Timeout` if the collection is
+    /// not loaded after 10 minutes.
     pub async fn load_collection<S>(
         &self,
         collection_name: S,
@@ -433,6 +873,16 @@ impl Client {
     {
         let options = options.unwrap_or_default();
         let collection_name = collection_name.into();
+        let collection = self.collection_cache.get(&collection_name).await?;
+        if !options.load_fields.is_empty() {
+            check_load_fields(&collection.fields, &options.load_fields)?;
+        }
+        // only the indexed fields matter, whatever the index types
+        let indexes = self
+            .collection_index_descriptions(collection_name.clone())
+            .await?;
+        check_vector_indexes(&collection.fields, &indexes)?;
+
         status_to_result(&Some(
             self.client
                 .clone()
@@ -441,28 +891,237 @@ impl Client {
                     db_name: "".to_string(),
                     collection_name: collection_name.clone(),
                     replica_number: options.replica_number,
-                    resource_groups: options.resource_groups,
+                    resource_groups: options.resource_groups.clone(),
                     refresh: options.refresh,
-                    load_fields: options.load_fields,
+                    load_fields: options.load_fields.clone(),
                     skip_load_dynamic_field: options.skip_load_dynamic_field,
-                    load_params: options.load_params,
+                    load_params: options.load_params.clone(),
                 })
                 .await?
                 .into_inner(),
         ))?;
+        self.collection_cache
+            .set_load_options(&collection_name, options);
+
+        self.wait_until_loaded(&collection_name, None, config::WAIT_LOAD_TIMEOUT)
+            .await
+    }
 
+    /// Adds fields to a collection loaded with
+    /// [`LoadOptions::load_fields`](crate::options::LoadOptions::load_fields).
+    ///
+    /// Milvus can not change the fields of a loaded collection, so it is
+    /// released and loaded again with the fields of its last load through this
+    /// client plus `fields`, keeping the other load options. Searches and
+    /// queries fail until the load completes.
+    ///
+    /// Nothing is done if every field is already loaded, or if this client has
+    /// not loaded the collection with a field list, in which case every field
+    /// is assumed loaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection.
+    /// * `fields` - The fields to load in addition to the loaded ones.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` indicating success or failure.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FieldDoesNotExists` without releasing the collection if a field
+    /// is not in its schema.
+    pub async fn load_fields<S>(&self, collection_name: S, fields: Vec<String>) -> Result<()>
+    where
+        S: Into<String>,
+    {
+        let collection_name = collection_name.into();
+        let collection = self.collection_cache.get(&collection_name).await?;
+        if let Some(name) = fields
+            .iter()
+            .find(|name| !collection.fields.iter().any(|f| &f.name == *name))
+        {
+            return Err(crate::schema::Error::FieldDoesNotExists(name.clone()).into());
+        }
+
+        let Some(mut options) = self
+            .collection_cache
+            .load_options(&collection_name)
+            .filter(|options| !options.load_fields.is_empty())
+        else {
+            return Ok(());
+        };
+        let mut added = false;
+        for name in fields {
+            if !options.load_fields.contains(&name) {
+                options.load_fields.push(name);
+                added = true;
+            }
+        }
+        if !added {
+            return Ok(());
+        }
+
+        options.refresh = false;
+        self.release_collection(&collection_name).await?;
+        self.load_collection(collection_name, Some(options)).await
+    }
+
+    /// Waits until a collection, or the partitions given in `options`, is loaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection.
+    /// * `options` - Optional partitions to wait for instead of the whole collection.
+    /// * `timeout` - How long to wait for the load to finish.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` indicating success or failure.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the load fails on the server, or if the collection
+    /// does not exist or is not being loaded, and `Error::Timeout` if the
+    /// load is not done after `timeout`.
+    pub async fn wait_until_loaded<S>(
+        &self,
+        collection_name: S,
+        options: Option<GetLoadStateOptions>,
+        timeout: Duration,
+    ) -> Result<()>
+    where
+        S: Into<String>,
+    {
+        let collection_name = collection_name.into();
+        let started = Instant::now();
         loop {
-            match self.get_load_state(&collection_name, None).await? {
+            match self
+                .get_load_state(&collection_name, options.clone())
+                .await?
+            {
                 proto::common::LoadState::NotExist => {
-                    return Err(SuperError::Unexpected("collection not found".to_owned()))
+                    return Err(SuperError::Unexpected("collection not found".to_owned()));
                 }
                 proto::common::LoadState::Loading => (),
                 proto::common::LoadState::Loaded => return Ok(()),
                 proto::common::LoadState::NotLoad => {
-                    return Err(SuperError::Unexpected("collection not loaded".to_owned()))
+                    return Err(SuperError::Unexpected("collection not loaded".to_owned()));
                 }
             }
 
+            if started.elapsed() >= timeout {
+                return Err(SuperError::Timeout(format!(
+                    "load of {} not done after {:?}",
+                    collection_name, timeout
+                )));
+            }
+            tokio::time::sleep(Duration::from_millis(config::WAIT_LOAD_DURATION_MS)).await;
+        }
+    }
+
+    /// Reloads a loaded collection so that data inserted since it was loaded is
+    /// brought into memory, and waits until the refresh is done.
+    ///
+    /// Unlike a cold load, which loads a released collection from scratch, a refresh
+    /// keeps serving searches from the segments already in memory while the new
+    /// segments are loaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the loaded collection.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` indicating success or failure.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Timeout` if the refresh is not done after 10 minutes, see
+    /// [`Client::refresh_load_with_timeout`] to wait longer.
+    pub async fn refresh_load<S>(&self, collection_name: S) -> Result<()>
+    where
+        S: Into<String>,
+    {
+        self.refresh_load_with_timeout(collection_name, config::WAIT_LOAD_TIMEOUT)
+            .await
+    }
+
+    /// Reloads a loaded collection like [`Client::refresh_load`], waiting at
+    /// most `timeout` for the refresh to finish.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the loaded collection.
+    /// * `timeout` - How long to wait for the refresh to finish.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` indicating success or failure.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Timeout` if the refresh is not done after `timeout`
+    pub async fn refresh_load_with_timeout<S>(
+        &self,
+        collection_name: S,
+        timeout: Duration,
+    ) -> Result<()>
+    where
+        S: Into<String>,
+    {
+        let collection_name = collection_name.into();
+        self.load_collection(&collection_name, Some(LoadOptions::new().refresh(true)))
+            .await?;
+        self.wait_until_refreshed(&collection_name, timeout).await
+    }
+
+    /// Waits until the refresh of a collection reaches 100%, e.g. after loading it
+    /// with [`LoadOptions::refresh`].
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection.
+    /// * `timeout` - How long to wait for the refresh to finish.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` indicating success or failure.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Timeout` if the refresh is not done after `timeout`
+    pub async fn wait_until_refreshed<S>(&self, collection_name: S, timeout: Duration) -> Result<()>
+    where
+        S: Into<String>,
+    {
+        let collection_name = collection_name.into();
+        let started = Instant::now();
+        loop {
+            let resp = self
+                .client
+                .clone()
+                .get_loading_progress(crate::proto::milvus::GetLoadingProgressRequest {
+                    base: Some(MsgBase::new(MsgType::LoadCollection)),
+                    db_name: "".to_string(),
+                    collection_name: collection_name.clone(),
+                    partition_names: Vec::new(),
+                })
+                .await?
+                .into_inner();
+            status_to_result(&resp.status)?;
+
+            if resp.refresh_progress >= 100 {
+                return Ok(());
+            }
+
+            if started.elapsed() >= timeout {
+                return Err(SuperError::Timeout(format!(
+                    "refresh of {} not done after {:?}",
+                    collection_name, timeout
+                )));
+            }
             tokio::time::sleep(Duration::from_millis(config::WAIT_LOAD_DURATION_MS)).await;
         }
     }
@@ -476,11 +1135,15 @@ impl Client {
     ///
     /// # Returns
     ///
-    /// The load state of the collection.
+    /// The load state of the collection, `NotLoad` before it is loaded, `Loading`
+    /// while its segments are brought into memory and `Loaded` once it can serve
+    /// searches.
     ///
     /// # Errors
     ///
-    /// Returns an error if the load state retrieval fails.
+    /// Returns an error if the load state retrieval fails. A load that failed on
+    /// the server has no state of its own, it is reported as the server error
+    /// returned here.
     pub async fn get_load_state<S>(
         &self,
         collection_name: S,
@@ -507,12 +1170,6 @@ impl Client {
         Ok(res.state())
     }
 
-    pub async fn refresh_load<S: Into<String>>(&self, collection_name: S) -> Result<()> {
-        let options = LoadOptions::new().refresh(true);
-        self.load_collection(collection_name, Some(options)).await?;
-        Ok(())
-    }
-
     /// Releases a collection with the given name from memory.
     ///
     /// # Arguments
@@ -526,21 +1183,56 @@ impl Client {
     where
         S: Into<String>,
     {
+        let collection_name = collection_name.into();
         status_to_result(&Some(
             self.client
                 .clone()
                 .release_collection(ReleaseCollectionRequest {
                     base: Some(MsgBase::new(MsgType::ReleaseCollection)),
                     db_name: "".to_string(),
-                    collection_name: collection_name.into(),
+                    collection_name: collection_name.clone(),
                 })
                 .await?
                 .into_inner(),
-        ))
+        ))?;
+        self.collection_cache.unset_loaded(&collection_name);
+        Ok(())
+    }
+
+    /// Checks a collection exists and is loaded before searching or querying it,
+    /// when [`ClientConfig::precheck_loaded`](crate::client::ClientConfig::precheck_loaded)
+    /// is enabled.
+    ///
+    /// A collection seen loaded is remembered until it is released or dropped
+    /// through this client, so only the first call costs round trips.
+    ///
+    /// # Errors
+    ///
+    /// * `CollectionNotFound` - the collection does not exist
+    /// * `NotLoaded` - the collection exists but is not loaded
+    pub(crate) async fn precheck_collection(&self, collection_name: &str) -> Result<()> {
+        if !self.config.precheck_loaded || self.collection_cache.is_loaded(collection_name) {
+            return Ok(());
+        }
+
+        if !self.has_collection(collection_name).await? {
+            return Err(Error::CollectionNotFound(collection_name.to_owned()).into());
+        }
+        match self.get_load_state(collection_name, None).await? {
+            LoadState::Loaded => {
+                self.collection_cache.set_loaded(collection_name);
+                Ok(())
+            }
+            _ => Err(Error::NotLoaded(collection_name.to_owned()).into()),
+        }
     }
 
     /// Alters the field of a collection.
     ///
+    /// Only `mmap.enabled` (any field), `max_length` (VarChar and Array fields)
+    /// and `max_capacity` (Array fields) can be altered, other properties are
+    /// rejected before the request is sent.
+    ///
     /// # Arguments
     ///
     /// * `collection_name` - The name of the collection.
@@ -559,6 +1251,16 @@ impl Client {
     where
         S: Into<String>,
     {
+        let collection_name = collection_name.into();
+        let field_name = field_name.into();
+        let collection = self.collection_cache.get(&collection_name).await?;
+        let field = collection
+            .fields
+            .iter()
+            .find(|f| f.name == field_name)
+            .ok_or_else(|| crate::schema::Error::FieldDoesNotExists(field_name.clone()))?;
+        check_alterable_field_properties(field, &field_params)?;
+
         //collect field_params into a vec
         let properties: Vec<crate::proto::common::KeyValuePair> = field_params
             .iter()
@@ -574,14 +1276,18 @@ impl Client {
             .alter_collection_field(AlterCollectionFieldRequest {
                 base: Some(MsgBase::new(MsgType::AlterCollectionField)),
                 db_name: "".to_string(),
-                collection_name: collection_name.into(),
-                field_name: field_name.into(),
+                collection_name: collection_name.clone(),
+                field_name,
                 properties: properties,
                 delete_keys: vec![],
             })
             .await?
             .into_inner();
-        status_to_result(&Some(resp))
+        status_to_result(&Some(resp))?;
+
+        // The cached max_length is used to validate inserts
+        self.collection_cache.invalidate(&collection_name);
+        Ok(())
     }
 
     /// alter a collection
@@ -746,6 +1452,31 @@ impl Client {
         status_to_result(&resp.status)?;
         Ok(resp.into())
     }
+
+    /// Gets the state of a compaction along with the segments each of its plans
+    /// merges.
+    ///
+    /// # Arguments
+    ///
+    /// * `compaction_id` - The id returned by [`Client::manual_compaction`]
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `CompactionPlans`. A compaction that
+    /// found nothing to merge has no plans, `merges` is then empty.
+    pub async fn get_compaction_state_with_plans(
+        &self,
+        compaction_id: i64,
+    ) -> Result<CompactionPlans> {
+        let resp = self
+            .client
+            .clone()
+            .get_compaction_state_with_plans(GetCompactionPlansRequest { compaction_id })
+            .await?
+            .into_inner();
+        status_to_result(&resp.status)?;
+        Ok(resp.into())
+    }
 }
 
 pub type ParamValue = serde_json::Value;
@@ -758,6 +1489,129 @@ pub struct SearchResult<'a> {
     pub id: Vec<Value<'a>>,
     pub field: Vec<FieldColumn>,
     pub score: Vec<f32>,
+    pub(crate) stats: SearchStats,
+}
+
+impl SearchResult<'_> {
+    /// Execution statistics of the search request that produced this result.
+    pub fn stats(&self) -> &SearchStats {
+        &self.stats
+    }
+
+    /// The dimension the server reported for the sparse vectors returned in
+    /// `field_name`, one past the largest index in the whole result.
+    ///
+    /// Hits only carry the parsed vectors, the dim belongs to the returned
+    /// batch. `None` if `field_name` is not a returned sparse vector field.
+    pub fn sparse_dim(&self, field_name: &str) -> Option<i64> {
+        self.field
+            .iter()
+            .find(|column| column.name == field_name && column.dtype == DataType::SparseFloatVector)
+            .map(|column| column.dim)
+    }
+
+    /// Splits the column-oriented result into one `Hit` per matched entity.
+    pub fn hits(&self) -> Vec<Hit> {
+        self.id
+            .iter()
+            .zip(self.score.iter())
+            .enumerate()
+            .map(|(i, (id, &score))| Hit {
+                id: id.clone().into_owned(),
+                score,
+                fields: self.field.row(i),
+            })
+            .collect()
+    }
+
+    /// Keeps only the hits whose score meets `threshold`, that is `>=` for
+    /// similarity metrics like IP and COSINE and `<=` for distances like L2.
+    ///
+    /// The result still belongs to the same query vector, so the per-query
+    /// grouping returned by search is preserved.
+    pub fn filter_by_score(&self, threshold: f32, metric: MetricType) -> Self {
+        let keep = |score: f32| {
+            if metric.higher_is_better() {
+                score >= threshold
+            } else {
+                score <= threshold
+            }
+        };
+        let kept: Vec<usize> = (0..self.score.len())
+            .filter(|&i| keep(self.score[i]))
+            .collect();
+
+        let field = self
+            .field
+            .iter()
+            .map(|column| {
+                let mut filtered = column.copy_with_metadata();
+                for &i in &kept {
+                    filtered.push(column.get(i).unwrap_or(Value::None));
+                }
+                filtered
+            })
+            .collect();
+
+        Self {
+            size: kept.len() as i64,
+            id: kept.iter().map(|&i| self.id[i].clone()).collect(),
+            field,
+            score: kept.iter().map(|&i| self.score[i]).collect(),
+            stats: self.stats.clone(),
+        }
+    }
+}
+
+/// Iterates the hits of one query vector, e.g. `for hit in results.remove(0)`.
+impl IntoIterator for SearchResult<'_> {
+    type Item = Hit;
+    type IntoIter = std::vec::IntoIter<Hit>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.hits().into_iter()
+    }
+}
+
+impl IntoIterator for &SearchResult<'_> {
+    type Item = Hit;
+    type IntoIter = std::vec::IntoIter<Hit>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.hits().into_iter()
+    }
+}
+
+/// A single entity matched by a search, with its score and output fields.
+#[derive(Clone, Debug)]
+pub struct Hit {
+    pub id: Value<'static>,
+    pub score: f32,
+    pub fields: RowMap,
+}
+
+impl Hit {
+    /// The score as an `f64`, for thresholding alongside other `f64` values.
+    ///
+    /// The search response only carries single precision scores, so this is
+    /// always `score` widened without loss and adds no precision.
+    pub fn score_f64(&self) -> f64 {
+        f64::from(self.score)
+    }
+}
+
+/// Execution statistics reported alongside a search result.
+///
+/// Milvus does not report per-segment scan details in the search response, so this
+/// carries what the response does expose plus the client-side round-trip time.
+#[derive(Clone, Debug, Default)]
+pub struct SearchStats {
+    /// Number of entities the server searched for the whole request.
+    pub all_search_count: i64,
+    /// Recall reported by the server for this query, if recall evaluation was enabled.
+    pub recall: Option<f32>,
+    /// Round-trip time of the search RPC, measured on the client.
+    pub elapsed: Duration,
 }
 
 pub struct IndexProgress {
@@ -765,11 +1619,102 @@ pub struct IndexProgress {
     pub indexed_rows: i64,
 }
 
+/// Checks a requested shard count, 0 leaves the choice to the server.
+/// Decodes the [`TTL_PROPERTY`] of a collection, `None` if it is absent, zero
+/// or not a number of seconds.
+fn parse_ttl(properties: &[proto::common::KeyValuePair]) -> Option<Duration> {
+    properties
+        .iter()
+        .find(|p| p.key == TTL_PROPERTY)
+        .and_then(|p| p.value.trim().parse::<u64>().ok())
+        .filter(|&seconds| seconds > 0)
+        .map(Duration::from_secs)
+}
+
+fn check_shard_num(shard_num: i32) -> Result<()> {
+    if (0..=config::MAX_SHARD_NUM).contains(&shard_num) {
+        Ok(())
+    } else {
+        Err(SuperError::InvalidParameter(
+            "shard_num".to_owned(),
+            format!(
+                "{shard_num}, must be between 1 and {}",
+                config::MAX_SHARD_NUM
+            ),
+        ))
+    }
+}
+
+/// Checks that every vector field has an index, which the server requires to
+/// load a collection.
+fn check_vector_indexes(fields: &[Field], indexes: &[IndexDescription]) -> Result<()> {
+    let missing: Vec<String> = fields
+        .iter()
+        .filter(|f| f.is_vector() && !indexes.iter().any(|i| i.field_name == f.name))
+        .map(|f| f.name.clone())
+        .collect();
+
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::VectorFieldNotIndexed(missing).into())
+    }
+}
+
+/// Checks that a partial load keeps the primary key and every vector field,
+/// which the server needs to serve searches.
+fn check_load_fields(fields: &[Field], load_fields: &[String]) -> Result<()> {
+    let required = fields.iter().filter(|f| f.is_primary_key || f.is_vector());
+
+    for field in required {
+        if !load_fields.contains(&field.name) {
+            return Err(SuperError::InvalidParameter(
+                "load_fields".to_owned(),
+                format!("must include primary key and vector field {:?}", field.name),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that every property can be altered on a field of this type.
+fn check_alterable_field_properties(
+    field: &Field,
+    properties: &HashMap<String, String>,
+) -> Result<()> {
+    for key in properties.keys() {
+        let alterable = match key.as_str() {
+            "mmap.enabled" => true,
+            "max_length" => matches!(field.dtype, DataType::VarChar | DataType::Array),
+            "max_capacity" => field.dtype == DataType::Array,
+            _ => false,
+        };
+        if !alterable {
+            return Err(SuperError::InvalidParameter(
+                key.clone(),
+                format!(
+                    "can not be altered on {:?} field {:?}",
+                    field.dtype, field.name
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, ThisError)]
 pub enum Error {
     #[error("collection {0} not found")]
     CollectionNotFound(String),
 
+    #[error("collection {0} is not loaded, load it before searching or querying")]
+    NotLoaded(String),
+
+    #[error("partition {1:?} not found in collection {0:?}")]
+    PartitionNotFound(String, String),
+
     #[error("type mismatched in {0:?}, available types: {1:?}")]
     IllegalType(String, Vec<DataType>),
 
@@ -778,4 +1723,186 @@ pub enum Error {
 
     #[error("index build failed")]
     IndexBuildFailed,
+
+    #[error("vector fields {0:?} have no index, every vector field must be indexed before loading")]
+    VectorFieldNotIndexed(Vec<String>),
+
+    #[error("metric {1} is not supported by field {0:?}, {2:?} supports only: {3:?}")]
+    UnsupportedMetric(String, MetricType, DataType, Vec<MetricType>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::fixtures::field;
+
+    #[test]
+    fn test_parse_ttl() {
+        let properties = |pairs: &[(&str, &str)]| -> Vec<proto::common::KeyValuePair> {
+            pairs
+                .iter()
+                .map(|(key, value)| proto::common::KeyValuePair {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                })
+                .collect()
+        };
+
+        let hour = properties(&[("mmap.enabled", "true"), (TTL_PROPERTY, "3600")]);
+        assert_eq!(parse_ttl(&hour), Some(Duration::from_secs(3600)));
+        assert_eq!(parse_ttl(&properties(&[])), None);
+        assert_eq!(parse_ttl(&properties(&[(TTL_PROPERTY, "0")])), None);
+        assert_eq!(parse_ttl(&properties(&[(TTL_PROPERTY, "soon")])), None);
+    }
+
+    #[test]
+    fn test_check_load_fields() {
+        let fields = vec![
+            Field {
+                is_primary_key: true,
+                ..field("id", DataType::Int64)
+            },
+            field("dense", DataType::FloatVector),
+            field("title", DataType::VarChar),
+        ];
+        let names = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+
+        assert!(check_load_fields(&fields, &names(&["id", "dense"])).is_ok());
+        assert!(check_load_fields(&fields, &names(&["id", "dense", "title"])).is_ok());
+        assert!(matches!(
+            check_load_fields(&fields, &names(&["id", "title"])),
+            Err(SuperError::InvalidParameter(param, msg)) if param == "load_fields" && msg.contains("\"dense\"")
+        ));
+        assert!(check_load_fields(&fields, &names(&["dense"])).is_err());
+    }
+
+    #[test]
+    fn test_check_alterable_field_properties() {
+        let props = |key: &str| HashMap::from([(key.to_owned(), "true".to_owned())]);
+        let dense = field("dense", DataType::FloatVector);
+        let title = field("title", DataType::VarChar);
+
+        assert!(check_alterable_field_properties(&dense, &props("mmap.enabled")).is_ok());
+        assert!(check_alterable_field_properties(&title, &props("mmap.enabled")).is_ok());
+        assert!(check_alterable_field_properties(&title, &props("max_length")).is_ok());
+        assert!(matches!(
+            check_alterable_field_properties(&dense, &props("max_length")),
+            Err(SuperError::InvalidParameter(key, _)) if key == "max_length"
+        ));
+        assert!(check_alterable_field_properties(&title, &props("max_capacity")).is_err());
+        assert!(check_alterable_field_properties(&dense, &props("mmap_enabled")).is_err());
+    }
+
+    #[test]
+    fn test_check_vector_indexes() {
+        let fields = vec![
+            Field {
+                is_primary_key: true,
+                ..field("id", DataType::Int64)
+            },
+            field("text_dense", DataType::FloatVector),
+            field("image_dense", DataType::FloatVector),
+            field("title", DataType::VarChar),
+        ];
+        let index = |field_name: &str, index_type: &str| IndexDescription {
+            index_name: format!("{field_name}_index"),
+            field_name: field_name.to_owned(),
+            params: vec![proto::common::KeyValuePair {
+                key: "index_type".to_owned(),
+                value: index_type.to_owned(),
+            }],
+            ..Default::default()
+        };
+        let title = index("title", "INVERTED");
+
+        assert!(
+            check_vector_indexes(
+                &fields,
+                &[
+                    index("text_dense", "FLAT"),
+                    index("image_dense", "DISKANN"),
+                    title.clone()
+                ]
+            )
+            .is_ok()
+        );
+        assert!(matches!(
+            check_vector_indexes(&fields, &[index("text_dense", "AUTOINDEX"), title]),
+            Err(SuperError::Collection(Error::VectorFieldNotIndexed(missing))) if missing == ["image_dense"]
+        ));
+        assert!(matches!(
+            check_vector_indexes(&fields, &[]),
+            Err(SuperError::Collection(Error::VectorFieldNotIndexed(missing))) if missing.len() == 2
+        ));
+    }
+
+    #[test]
+    fn test_check_shard_num() {
+        assert!(check_shard_num(0).is_ok());
+        assert!(check_shard_num(2).is_ok());
+        assert!(check_shard_num(config::MAX_SHARD_NUM).is_ok());
+        assert!(matches!(
+            check_shard_num(-1),
+            Err(SuperError::InvalidParameter(param, _)) if param == "shard_num"
+        ));
+        assert!(check_shard_num(config::MAX_SHARD_NUM + 1).is_err());
+    }
+
+    fn search_result(scores: Vec<f32>) -> SearchResult<'static> {
+        let ids: Vec<i64> = (0..scores.len() as i64).collect();
+        SearchResult {
+            size: scores.len() as i64,
+            id: ids.iter().map(|&id| Value::Long(id)).collect(),
+            field: vec![FieldColumn::new(
+                &crate::schema::FieldSchema::new_int64("rank", ""),
+                ids.iter().map(|id| id * 10).collect::<Vec<_>>(),
+            )],
+            score: scores,
+            stats: SearchStats::default(),
+        }
+    }
+
+    fn ids(result: &SearchResult) -> Vec<i64> {
+        result
+            .id
+            .iter()
+            .map(|id| match id {
+                Value::Long(id) => *id,
+                other => panic!("unexpected id {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_filter_by_score() {
+        let groups = [
+            search_result(vec![0.9, 0.7, 0.5, 0.2]),
+            search_result(vec![0.4, 0.1]),
+        ];
+        let filtered: Vec<_> = groups
+            .iter()
+            .map(|group| group.filter_by_score(0.5, MetricType::IP))
+            .collect();
+        assert_eq!(filtered.len(), 2);
+        assert_eq!(ids(&filtered[0]), vec![0, 1, 2]);
+        assert_eq!(filtered[0].score, vec![0.9, 0.7, 0.5]);
+        assert_eq!(filtered[0].size, 3);
+        assert!(matches!(filtered[0].field[0].get(2), Some(Value::Long(20))));
+        assert!(ids(&filtered[1]).is_empty());
+        assert_eq!(filtered[1].field[0].len(), 0);
+
+        let distances = search_result(vec![0.1, 0.3, 0.6, 1.2]);
+        let filtered = distances.filter_by_score(0.3, MetricType::L2);
+        assert_eq!(ids(&filtered), vec![0, 1]);
+        assert_eq!(filtered.score, vec![0.1, 0.3]);
+        assert!(matches!(filtered.field[0].get(1), Some(Value::Long(10))));
+    }
+
+    #[test]
+    fn test_hit_score_f64() {
+        for hit in search_result(vec![0.9, 0.1, 1.0 / 3.0]).hits() {
+            assert_eq!(hit.score_f64(), hit.score as f64);
+            assert_eq!(hit.score_f64() as f32, hit.score);
+        }
+    }
 }
@@ -18,4 +18,9 @@ use core::time;
 
 pub const WAIT_LOAD_DURATION_MS: u64 = 500;
 pub const WAIT_CREATE_INDEX_DURATION_MS: u64 = 100;
+pub const WAIT_FLUSH_DURATION_MS: u64 = 500;
+/// How long a load or refresh is waited for when no timeout is given.
+pub const WAIT_LOAD_TIMEOUT: time::Duration = time::Duration::new(600, 0);
+/// Default of the server's `proxy.maxShardNum`, the most shards a collection can have.
+pub const MAX_SHARD_NUM: i32 = 16;
 pub const RPC_TIMEOUT: time::Duration = time::Duration::new(10, 0);
@@ -1,12 +1,14 @@
 use std::borrow::Cow;
 
 use crate::{
+    error::Result,
     proto::schema::{
         self, DataType, ScalarField, VectorField, field_data::Field,
         scalar_field::Data as ScalarData, vector_field::Data as VectorData,
     },
-    schema::FieldSchema,
-    value::{Value, ValueVec},
+    schema::{DYNAMIC_FIELD_NAME, Error as SchemaError, FieldSchema},
+    sparse::SparseVector,
+    value::{RowMap, Value, ValueVec},
 };
 
 pub trait HasDataType {
@@ -59,6 +61,8 @@ pub struct FieldColumn {
     pub dim: i64,
     pub max_length: i32,
     pub is_dynamic: bool,
+    /// Validity of each row of a nullable column, empty when every row is valid.
+    pub valid_data: Vec<bool>,
 }
 
 impl From<schema::FieldData> for FieldColumn {
@@ -69,7 +73,22 @@ impl From<schema::FieldData> for FieldColumn {
             .map(get_dim_max_length)
             .unwrap_or((Some(1), None));
 
-        let value: ValueVec = fd.field.map(Into::into).unwrap_or(ValueVec::None);
+        let value: ValueVec = match fd.field.map(Into::into).unwrap_or(ValueVec::None) {
+            // nullable vectors only carry the valid rows, put the nulls back in place
+            ValueVec::SparseFloatVector(rows) if rows.len() < fd.valid_data.len() => {
+                let mut rows = rows.into_iter();
+                ValueVec::SparseFloatVector(
+                    fd.valid_data
+                        .iter()
+                        .map(|&valid| match valid {
+                            true => rows.next().unwrap_or_default(),
+                            false => Vec::new(),
+                        })
+                        .collect(),
+                )
+            }
+            value => value,
+        };
         let dtype = DataType::from_i32(fd.r#type).unwrap_or(DataType::None);
 
         FieldColumn {
@@ -79,6 +98,7 @@ impl From<schema::FieldData> for FieldColumn {
             max_length: max_length.unwrap_or(0),
             value,
             is_dynamic: fd.is_dynamic,
+            valid_data: fd.valid_data,
         }
     }
 }
@@ -92,10 +112,78 @@ impl FieldColumn {
             dim: schm.dim,
             max_length: schm.max_length,
             is_dynamic: false,
+            valid_data: Vec::new(),
+        }
+    }
+
+    /// Builds a `Double` column, the values are sent as 64-bit floats without
+    /// passing through `f32`.
+    pub fn double(schm: &FieldSchema, values: Vec<f64>) -> FieldColumn {
+        FieldColumn::new(schm, values)
+    }
+
+    /// Builds a nullable sparse vector column, `None` rows are inserted as null.
+    pub fn new_nullable_sparse(schm: &FieldSchema, rows: Vec<Option<SparseVector>>) -> FieldColumn {
+        let valid_data = rows.iter().map(Option::is_some).collect();
+        let mut column = FieldColumn::new(
+            schm,
+            rows.into_iter()
+                .map(Option::unwrap_or_default)
+                .collect::<Vec<_>>(),
+        );
+        column.valid_data = valid_data;
+        column
+    }
+
+    /// Whether the row at `idx` holds a value, rows of non-nullable columns always do.
+    pub fn is_valid(&self, idx: usize) -> bool {
+        self.valid_data.get(idx).copied().unwrap_or(true)
+    }
+
+    /// Builds a column like [`FieldColumn::new`], rejecting VarChar values longer than
+    /// the field's `max_length`.
+    pub fn try_new<V: Into<ValueVec>>(schm: &FieldSchema, v: V) -> Result<FieldColumn> {
+        let column = FieldColumn::new(schm, v);
+        column.check_max_length(schm.max_length)?;
+        Ok(column)
+    }
+
+    /// Checks the UTF-8 byte length of every VarChar value against `max_length`.
+    ///
+    /// Non-VarChar columns and a `max_length` of 0 (unknown) always pass.
+    ///
+    /// # Errors
+    ///
+    /// * `MaxLengthExceeded` - naming the first row whose value is too long
+    pub fn check_max_length(&self, max_length: i32) -> Result<()> {
+        if self.dtype != DataType::VarChar || max_length <= 0 {
+            return Ok(());
+        }
+
+        let ValueVec::String(v) = &self.value else {
+            return Ok(());
+        };
+
+        match v
+            .iter()
+            .enumerate()
+            .find(|(_, s)| s.len() > max_length as usize)
+        {
+            Some((row, s)) => {
+                Err(
+                    SchemaError::MaxLengthExceeded(self.name.clone(), row, s.len(), max_length)
+                        .into(),
+                )
+            }
+            None => Ok(()),
         }
     }
 
     pub fn get(&self, idx: usize) -> Option<Value<'_>> {
+        if !self.is_valid(idx) {
+            return Some(Value::None);
+        }
+
         Some(match &self.value {
             ValueVec::None => Value::None,
             ValueVec::Bool(v) => Value::Bool(*v.get(idx)?),
@@ -116,8 +204,12 @@ impl FieldColumn {
             },
             ValueVec::Double(v) => Value::Double(*v.get(idx)?),
             ValueVec::Binary(v) => {
-                let dim = (self.dim / 8) as usize;
-                Value::Binary(Cow::Borrowed(&v[idx * dim..idx * dim + dim]))
+                let width = self.binary_row_width();
+                let row = Cow::Borrowed(v.get(idx * width..idx * width + width)?);
+                match self.dtype {
+                    DataType::Float16Vector => Value::Float16Array(row),
+                    _ => Value::Binary(row),
+                }
             }
             ValueVec::String(v) => Value::String(Cow::Borrowed(v.get(idx)?.as_ref())),
             ValueVec::Json(v) => Value::Json(Cow::Borrowed(v.get(idx)?.as_ref())),
@@ -128,9 +220,27 @@ impl FieldColumn {
         })
     }
 
+    /// Borrows the rows of a FloatVector column as `dim`-long slices of the
+    /// column's buffer, without copying or allocating per row. `None` for a
+    /// column of another type.
+    ///
+    /// The slices borrow the column, so it can not be modified or dropped while
+    /// they are in use. Copy a row with `to_vec` to keep it longer.
+    pub fn float_vectors(&self) -> Option<std::slice::ChunksExact<'_, f32>> {
+        match (&self.value, self.dtype) {
+            (ValueVec::Float(v), DataType::FloatVector) if self.dim > 0 => {
+                Some(v.chunks_exact(self.dim as usize))
+            }
+            _ => None,
+        }
+    }
+
     pub fn push(&mut self, val: Value) {
+        if let Value::None = val {
+            return self.push_null();
+        }
+
         match (&mut self.value, val) {
-            (ValueVec::None, Value::None) => (),
             (ValueVec::Bool(vec), Value::Bool(i)) => vec.push(i),
             (ValueVec::Int(vec), Value::Int8(i)) => vec.push(i as _),
             (ValueVec::Int(vec), Value::Int16(i)) => vec.push(i as _),
@@ -139,20 +249,67 @@ impl FieldColumn {
             (ValueVec::Float(vec), Value::Float(i)) => vec.push(i),
             (ValueVec::Double(vec), Value::Double(i)) => vec.push(i),
             (ValueVec::String(vec), Value::String(i)) => vec.push(i.to_string()),
+            (ValueVec::Json(vec), Value::Json(i)) => vec.push(i.into_owned()),
+            (ValueVec::Array(vec), Value::Array(i)) => vec.push(i.into_owned()),
             (ValueVec::Binary(vec), Value::Binary(i)) => vec.extend_from_slice(i.as_ref()),
+            (ValueVec::Binary(vec), Value::Float16Array(i)) => vec.extend_from_slice(i.as_ref()),
             (ValueVec::Float(vec), Value::FloatArray(i)) => vec.extend_from_slice(i.as_ref()),
             (ValueVec::SparseFloatVector(vec), Value::SparseFloatVector(i)) => vec.push(i.to_vec()),
             _ => panic!("column type mismatch"),
         }
+
+        if !self.valid_data.is_empty() {
+            let len = self.len();
+            self.valid_data.resize(len, true);
+        }
+    }
+
+    /// Appends a null row: a placeholder of the column type, flagged invalid in
+    /// `valid_data` so `get` reads it back as `Value::None`.
+    fn push_null(&mut self) {
+        let len = self.len();
+        let dim = self.dim.max(1) as usize;
+        let binary_width = self.binary_row_width();
+        match &mut self.value {
+            ValueVec::None => return,
+            ValueVec::Bool(vec) => vec.push(false),
+            ValueVec::Int(vec) => vec.push(0),
+            ValueVec::Long(vec) => vec.push(0),
+            ValueVec::Float(vec) => vec.resize(vec.len() + dim, 0.0),
+            ValueVec::Double(vec) => vec.push(0.0),
+            ValueVec::String(vec) => vec.push(String::new()),
+            ValueVec::Json(vec) => vec.push(Vec::new()),
+            ValueVec::Array(vec) => vec.push(Default::default()),
+            ValueVec::Binary(vec) => vec.resize(vec.len() + binary_width, 0),
+            ValueVec::SparseFloatVector(vec) => vec.push(Vec::new()),
+        }
+
+        self.valid_data.resize(len, true);
+        self.valid_data.push(false);
     }
 
     #[inline]
     pub fn len(&self) -> usize {
-        self.value.len() / self.dim as usize
+        match &self.value {
+            // sparse rows are stored one per element, their dim is not a row width
+            ValueVec::SparseFloatVector(v) => v.len(),
+            ValueVec::Binary(v) => v.len() / self.binary_row_width().max(1),
+            v => v.len() / self.dim as usize,
+        }
+    }
+
+    /// Bytes per row of a vector column stored as `ValueVec::Binary`, one bit
+    /// per dimension for binary vectors and two bytes for 16-bit floats.
+    fn binary_row_width(&self) -> usize {
+        match self.dtype {
+            DataType::Float16Vector | DataType::BFloat16Vector => self.dim as usize * 2,
+            _ => self.dim as usize / 8,
+        }
     }
 
     pub fn copy_with_metadata(&self) -> Self {
         Self {
+            valid_data: Vec::new(),
             dim: self.dim,
             dtype: self.dtype,
             max_length: self.max_length,
@@ -175,12 +332,49 @@ impl FieldColumn {
     }
 }
 
+/// Row-oriented access to a set of result columns, such as the output of a query.
+pub trait ToRows {
+    /// Builds the row at `idx`, columns without a value at `idx` are left out.
+    fn row(&self, idx: usize) -> RowMap;
+
+    /// Transposes the columns into one map per row.
+    fn rows(&self) -> Vec<RowMap>;
+
+    /// Reads the dynamic field `key` of the row at `idx` from the `$meta` column,
+    /// `None` if the row does not have it or the dynamic field was not returned.
+    fn get_dynamic(&self, idx: usize, key: &str) -> Option<serde_json::Value>;
+}
+
+impl ToRows for [FieldColumn] {
+    fn row(&self, idx: usize) -> RowMap {
+        self.iter()
+            .filter_map(|c| Some((c.name.clone(), c.get(idx)?.into_owned())))
+            .collect()
+    }
+
+    fn rows(&self) -> Vec<RowMap> {
+        let num_rows = self.iter().map(FieldColumn::len).max().unwrap_or(0);
+        (0..num_rows).map(|i| self.row(i)).collect()
+    }
+
+    fn get_dynamic(&self, idx: usize, key: &str) -> Option<serde_json::Value> {
+        let column = self
+            .iter()
+            .find(|c| c.is_dynamic || c.name == DYNAMIC_FIELD_NAME)?;
+        let Some(Value::Json(json)) = column.get(idx) else {
+            return None;
+        };
+        let mut fields: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_slice(&json).ok()?;
+        fields.remove(key)
+    }
+}
+
 impl From<FieldColumn> for schema::FieldData {
     fn from(this: FieldColumn) -> schema::FieldData {
         schema::FieldData {
             field_name: this.name.to_string(),
             field_id: 0,
-            valid_data: vec![],
             r#type: this.dtype as _,
             field: Some(match this.value {
                 ValueVec::None => Field::Scalars(ScalarField { data: None }),
@@ -219,18 +413,28 @@ impl From<FieldColumn> for schema::FieldData {
                     })),
                 }),
                 ValueVec::Binary(v) => Field::Vectors(VectorField {
-                    data: Some(VectorData::BinaryVector(v)),
+                    data: Some(match this.dtype {
+                        DataType::Float16Vector => VectorData::Float16Vector(v),
+                        DataType::BFloat16Vector => VectorData::Bfloat16Vector(v),
+                        _ => VectorData::BinaryVector(v),
+                    }),
                     dim: this.dim,
                 }),
-                ValueVec::SparseFloatVector(v) => {
+                ValueVec::SparseFloatVector(mut v) => {
                     use crate::sparse::sparse_vectors_to_proto;
+                    // null rows are left out, the server places them by `valid_data`
+                    if !this.valid_data.is_empty() {
+                        let mut valid = this.valid_data.iter();
+                        v.retain(|_| valid.next().copied().unwrap_or(true));
+                    }
                     Field::Vectors(VectorField {
                         data: Some(VectorData::SparseFloatVector(sparse_vectors_to_proto(v))),
                         dim: this.dim,
                     })
                 }
             }),
-            is_dynamic: false,
+            valid_data: this.valid_data,
+            is_dynamic: this.is_dynamic,
         }
     }
 }
@@ -284,9 +488,188 @@ impl FromField for Vec<f32> {
 fn get_dim_max_length(field: &Field) -> (Option<i64>, Option<i32>) {
     let dim = match field {
         Field::Scalars(ScalarField { data: Some(_) }) => 1i64,
+        // the dim the server reports for the batch of sparse rows it returned
+        Field::Vectors(VectorField {
+            data: Some(VectorData::SparseFloatVector(sparse)),
+            ..
+        }) => sparse.dim,
         Field::Vectors(VectorField { dim, .. }) => *dim,
         _ => 0i64,
     };
 
     (Some(dim), None) // no idea how to get max_length
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+
+    #[test]
+    fn test_float_vectors_borrow_column_buffer() {
+        let schm = FieldSchema::new_float_vector("dense", "", 3);
+        let rows = [[0.1f32, 0.2, 0.3], [1.0, 2.0, 3.0]];
+        let column = FieldColumn::new(&schm, rows.concat());
+
+        let ValueVec::Float(buffer) = &column.value else {
+            panic!("expected float data");
+        };
+        let slices: Vec<&[f32]> = column.float_vectors().unwrap().collect();
+        assert_eq!(slices.len(), 2);
+        for (i, slice) in slices.iter().enumerate() {
+            assert_eq!(*slice, rows[i]);
+            // Each row points into the column's own buffer, nothing was copied
+            assert_eq!(slice.as_ptr(), buffer[i * 3..].as_ptr());
+        }
+
+        let scalar = FieldColumn::new(&FieldSchema::new_float("score", ""), vec![0.5f32]);
+        assert!(scalar.float_vectors().is_none());
+    }
+
+    #[test]
+    fn test_double_roundtrip() {
+        let schm = FieldSchema::new_double("price", "");
+        let values = vec![f64::MAX, f64::MIN_POSITIVE, 0.1 + 0.2, 1.0 + f64::EPSILON];
+        let column = FieldColumn::double(&schm, values.clone());
+
+        let decoded = FieldColumn::from(schema::FieldData::from(column));
+        assert_eq!(decoded.dtype, DataType::Double);
+        let ValueVec::Double(decoded_values) = &decoded.value else {
+            panic!("expected double data");
+        };
+        assert_eq!(*decoded_values, values);
+        // Narrowing to f32 would lose these
+        assert_ne!(decoded_values[2] as f32 as f64, values[2]);
+        assert!(matches!(decoded.get(3), Some(Value::Double(v)) if v == 1.0 + f64::EPSILON));
+    }
+
+    #[test]
+    fn test_float16_roundtrip() {
+        let schm = FieldSchema {
+            name: "half".to_owned(),
+            dtype: DataType::Float16Vector,
+            dim: 2,
+            ..Default::default()
+        };
+        let mut column = FieldColumn::new(&schm, ValueVec::Binary(Vec::new()));
+        // [1.0, 2.0] and [-2.0, 0.0]
+        for row in [[0x00, 0x3c, 0x00, 0x40], [0x00, 0xc0, 0x00, 0x00]] {
+            column.push(Value::Float16Array(Cow::Owned(row.to_vec())));
+        }
+
+        let fd = schema::FieldData::from(column);
+        assert!(matches!(
+            &fd.field,
+            Some(Field::Vectors(VectorField {
+                data: Some(VectorData::Float16Vector(v)),
+                dim: 2,
+            })) if v.len() == 8
+        ));
+
+        let decoded = FieldColumn::from(fd);
+        assert_eq!(decoded.len(), 2);
+        assert!(matches!(
+            decoded.get(1),
+            Some(Value::Float16Array(v)) if v.as_ref() == [0x00, 0xc0, 0x00, 0x00]
+        ));
+    }
+
+    #[test]
+    fn test_nullable_scalar_push() {
+        let mut price = FieldColumn::new(
+            &FieldSchema::new_int64("price", "").with_nullable(),
+            vec![10i64, 0, 30],
+        );
+        price.valid_data = vec![true, false, true];
+        let mut title = FieldColumn::new(
+            &FieldSchema::new_varchar("title", "", 16).with_nullable(),
+            vec!["a".to_string(), String::new(), "c".to_string()],
+        );
+        title.valid_data = vec![true, false, true];
+
+        for column in [price, title] {
+            let mut copy = column.copy_with_metadata();
+            for idx in 0..column.len() {
+                copy.push(column.get(idx).unwrap());
+            }
+
+            assert_eq!(copy.len(), 3);
+            assert_eq!(copy.valid_data, vec![true, false, true]);
+            assert!(matches!(copy.get(1), Some(Value::None)));
+        }
+    }
+
+    #[test]
+    fn test_nullable_sparse_roundtrip() {
+        let schm = FieldSchema::new_sparse_float_vector("sparse", "").with_nullable();
+        let column = FieldColumn::new_nullable_sparse(
+            &schm,
+            vec![Some(vec![(1, 0.5)]), None, Some(vec![(2, 0.25), (9, 1.0)])],
+        );
+        assert_eq!(column.len(), 3);
+
+        let fd: schema::FieldData = column.into();
+        assert_eq!(fd.valid_data, vec![true, false, true]);
+        let Some(Field::Vectors(VectorField {
+            data: Some(VectorData::SparseFloatVector(proto)),
+            ..
+        })) = &fd.field
+        else {
+            panic!("expected sparse vector data");
+        };
+        assert_eq!(proto.contents.len(), 2);
+
+        let column = FieldColumn::from(fd);
+        assert_eq!(column.len(), 3);
+        assert_eq!(column.dim, 10);
+        assert!(
+            matches!(column.get(0), Some(Value::SparseFloatVector(v)) if v.as_ref() == [(1, 0.5)])
+        );
+        assert!(matches!(column.get(1), Some(Value::None)));
+        assert!(
+            matches!(column.get(2), Some(Value::SparseFloatVector(v)) if v.as_ref() == [(2, 0.25), (9, 1.0)])
+        );
+    }
+
+    #[test]
+    fn test_rows() {
+        let columns = [
+            FieldColumn::new(
+                &FieldSchema::new_primary_int64("id", "", false),
+                vec![1i64, 2, 3],
+            ),
+            FieldColumn::new(
+                &FieldSchema::new_varchar("title", "", 16),
+                vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            ),
+        ];
+
+        let rows = columns.rows();
+
+        assert_eq!(rows.len(), 3);
+        for (row, (id, title)) in rows.iter().zip([(1, "a"), (2, "b"), (3, "c")]) {
+            assert_eq!(row.len(), 2);
+            assert!(matches!(row["id"], Value::Long(v) if v == id));
+            assert!(matches!(&row["title"], Value::String(v) if v == title));
+        }
+    }
+
+    #[test]
+    fn test_try_new_max_length() {
+        let schm = FieldSchema::new_varchar("title", "", 4);
+
+        let err = FieldColumn::try_new(&schm, vec!["ok".to_string(), "too long".to_string()])
+            .unwrap_err();
+        match err {
+            Error::Schema(err @ SchemaError::MaxLengthExceeded(..)) => assert_eq!(
+                err.to_string(),
+                "value of field \"title\" at row 1 is 8 bytes long, exceeding max_length 4"
+            ),
+            err => panic!("unexpected error {err:?}"),
+        }
+
+        // the limit is in bytes, "éé" is 4 bytes long
+        let column = FieldColumn::try_new(&schm, vec!["abcd".to_string(), "éé".to_string()]);
+        assert!(column.is_ok());
+    }
+}
@@ -33,10 +33,9 @@
 //! }
 //! ```
 
-use crate::client::{Client, CombinedInterceptor, DbInterceptor};
+use crate::client::{Client, service_client};
 use crate::collection::CollectionCache;
 use crate::proto::common::{KeyValuePair, MsgBase, MsgType};
-use crate::proto::milvus::milvus_service_client::MilvusServiceClient;
 use crate::utils::status_to_result;
 use crate::{error::*, proto};
 
@@ -518,18 +517,12 @@ impl Client {
         self.db_name = Some(db_name.clone());
 
         // Create new client with database interceptor
-        let db_interceptor = DbInterceptor {
-            db_name: Some(db_name),
-        };
-        let combined_interceptor = CombinedInterceptor {
-            auth: self.auth_interceptor.clone(),
-            db: db_interceptor,
-        };
-
-        let new_client =
-            MilvusServiceClient::with_interceptor(self.channel.clone(), combined_interceptor)
-                .max_decoding_message_size(256 * 1024 * 1024) // 256MB
-                .max_encoding_message_size(256 * 1024 * 1024); // 256MB
+        let new_client = service_client(
+            self.channel.clone(),
+            self.auth_interceptor.clone(),
+            Some(db_name),
+            &self.config,
+        );
         self.client = new_client.clone();
         self.collection_cache = CollectionCache::new(new_client);
 
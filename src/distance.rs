@@ -0,0 +1,305 @@
+// Licensed to the LF AI & Data foundation under one
+// or more contributor license agreements. See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership. The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Client-side distances between dense and sparse vectors, computed the way
+//! the server computes the matching metric, to reproduce or check a ranking
+//! locally, or to rerank hits with [`rerank_hits`].
+//!
+//! # Example
+//!
+//! ```
+//! use milvus::distance::{dense_cosine, dense_ip, dense_l2};
+//!
+//! assert_eq!(dense_l2(&[1.0, 2.0], &[3.0, 4.0]).unwrap(), 8.0);
+//! assert_eq!(dense_ip(&[1.0, 2.0], &[3.0, 4.0]).unwrap(), 11.0);
+//! assert_eq!(dense_cosine(&[1.0, 0.0], &[0.0, 0.0]).unwrap(), 0.0);
+//! ```
+
+use std::collections::HashMap;
+
+use crate::collection::Hit;
+use crate::error::{Error, Result};
+use crate::index::MetricType;
+use crate::query::QueryVector;
+use crate::value::Value;
+
+/// Squared Euclidean distance, which is what the server reports for
+/// [`MetricType::L2`](crate::index::MetricType::L2). Smaller is closer.
+///
+/// # Errors
+/// Returns an error if the vectors differ in length
+pub fn dense_l2(a: &[f32], b: &[f32]) -> Result<f32> {
+    check_dim(a, b)?;
+    Ok(a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum())
+}
+
+/// Inner product, as for [`MetricType::IP`](crate::index::MetricType::IP).
+/// Larger is closer.
+///
+/// # Errors
+/// Returns an error if the vectors differ in length
+pub fn dense_ip(a: &[f32], b: &[f32]) -> Result<f32> {
+    check_dim(a, b)?;
+    Ok(dot(a, b))
+}
+
+/// Cosine similarity, as for
+/// [`MetricType::COSINE`](crate::index::MetricType::COSINE). Larger is closer.
+///
+/// A zero vector has no direction, its similarity to any vector is 0.
+///
+/// # Errors
+/// Returns an error if the vectors differ in length
+pub fn dense_cosine(a: &[f32], b: &[f32]) -> Result<f32> {
+    check_dim(a, b)?;
+    let norms = (dot(a, a) * dot(b, b)).sqrt();
+    if norms == 0.0 {
+        return Ok(0.0);
+    }
+    Ok(dot(a, b) / norms)
+}
+
+/// Inner product of two sparse vectors, as for
+/// [`MetricType::IP`](crate::index::MetricType::IP). Larger is closer.
+///
+/// Entries need not be sorted, an index missing from either side contributes
+/// nothing.
+pub fn sparse_ip(a: &[(u32, f32)], b: &[(u32, f32)]) -> f32 {
+    let (small, large) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    let mut values: HashMap<u32, f32> = HashMap::with_capacity(small.len());
+    for &(index, value) in small {
+        *values.entry(index).or_default() += value;
+    }
+    large
+        .iter()
+        .filter_map(|(index, value)| values.get(index).map(|v| v * value))
+        .sum()
+}
+
+/// Cosine similarity of two sparse vectors. Larger is closer.
+///
+/// A vector without non-zero entries has no direction, its similarity to any
+/// vector is 0.
+pub fn sparse_cosine(a: &[(u32, f32)], b: &[(u32, f32)]) -> f32 {
+    let norms = (sparse_ip(a, a) * sparse_ip(b, b)).sqrt();
+    if norms == 0.0 {
+        return 0.0;
+    }
+    sparse_ip(a, b) / norms
+}
+
+/// Recomputes the score of every hit against `query` with `metric` and sorts
+/// the hits best first, closest first for L2 and largest first otherwise.
+///
+/// The hits need the vector of `field`, request it with the output fields of
+/// the search. Dense queries support L2, IP and COSINE, sparse queries IP and
+/// COSINE.
+///
+/// # Arguments
+/// * `hits` - Hits of one query vector, e.g. from [`SearchResult::hits`](crate::collection::SearchResult::hits)
+/// * `field` - Name of the vector field to score
+/// * `query` - Vector to score the hits against
+/// * `metric` - Metric of the new scores
+///
+/// # Errors
+/// Returns an error if the metric is not supported for the query, or if a hit
+/// lacks the vector field or holds a vector of another type or dimension. The
+/// hits are left unchanged in that case.
+pub fn rerank_hits(
+    hits: &mut [Hit],
+    field: &str,
+    query: &QueryVector,
+    metric: MetricType,
+) -> Result<()> {
+    let scores = hits
+        .iter()
+        .map(|hit| match (query, hit.fields.get(field)) {
+            (QueryVector::FloatVector(q), Some(Value::FloatArray(v))) => match metric {
+                MetricType::L2 => dense_l2(q, v),
+                MetricType::IP => dense_ip(q, v),
+                MetricType::COSINE => dense_cosine(q, v),
+                _ => Err(unsupported_metric(metric, "dense")),
+            },
+            (QueryVector::SparseVector(q), Some(Value::SparseFloatVector(v))) => match metric {
+                MetricType::IP => Ok(sparse_ip(q, v)),
+                MetricType::COSINE => Ok(sparse_cosine(q, v)),
+                _ => Err(unsupported_metric(metric, "sparse")),
+            },
+            (_, None) => Err(Error::InvalidParameter(
+                "hits".to_owned(),
+                format!("hit {:?} has no field {field}", hit.id),
+            )),
+            (_, Some(_)) => Err(Error::InvalidParameter(
+                "query".to_owned(),
+                format!("query vector does not match the type of field {field}"),
+            )),
+        })
+        .collect::<Result<Vec<f32>>>()?;
+
+    for (hit, score) in hits.iter_mut().zip(scores) {
+        hit.score = score;
+    }
+    if metric.higher_is_better() {
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+    } else {
+        hits.sort_by(|a, b| a.score.total_cmp(&b.score));
+    }
+    Ok(())
+}
+
+fn unsupported_metric(metric: MetricType, kind: &str) -> Error {
+    Error::InvalidParameter(
+        "metric".to_owned(),
+        format!("{metric} can not rerank {kind} vectors"),
+    )
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn check_dim(a: &[f32], b: &[f32]) -> Result<()> {
+    if a.len() != b.len() {
+        return Err(Error::InvalidParameter(
+            "b".to_owned(),
+            format!("dimension {} does not match {}", b.len(), a.len()),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dense_distances() {
+        let a = [1.0, 2.0, 3.0];
+        let b = [4.0, 6.0, 3.0];
+
+        // (1 - 4)^2 + (2 - 6)^2 + 0
+        assert_eq!(dense_l2(&a, &b).unwrap(), 25.0);
+        assert_eq!(dense_l2(&a, &a).unwrap(), 0.0);
+        // 4 + 12 + 9
+        assert_eq!(dense_ip(&a, &b).unwrap(), 25.0);
+        // 25 / (sqrt(14) * sqrt(61))
+        let cosine = dense_cosine(&a, &b).unwrap();
+        assert!((cosine - 25.0 / (14f32.sqrt() * 61f32.sqrt())).abs() < 1e-6);
+        assert!((dense_cosine(&a, &[2.0, 4.0, 6.0]).unwrap() - 1.0).abs() < 1e-6);
+        assert_eq!(dense_cosine(&[1.0, 0.0], &[0.0, 1.0]).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_sparse_distances() {
+        let a = [(1, 1.0), (5, 2.0), (9, 3.0)];
+        let b = [(9, 1.0), (2, 4.0), (1, 2.0)];
+
+        // 1 * 2 + 3 * 1
+        assert_eq!(sparse_ip(&a, &b), 5.0);
+        assert_eq!(sparse_ip(&b, &a), 5.0);
+        // 5 / (sqrt(14) * sqrt(21))
+        assert!((sparse_cosine(&a, &b) - 5.0 / (14f32.sqrt() * 21f32.sqrt())).abs() < 1e-6);
+        assert!((sparse_cosine(&a, &a) - 1.0).abs() < 1e-6);
+        assert_eq!(sparse_cosine(&a, &[]), 0.0);
+        assert_eq!(sparse_ip(&[(3, 1.0)], &[(4, 1.0)]), 0.0);
+    }
+
+    fn ids(hits: &[Hit]) -> Vec<i64> {
+        hits.iter()
+            .map(|h| match h.id {
+                Value::Long(id) => id,
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    fn hit(id: i64, score: f32, vector: Value<'static>) -> Hit {
+        Hit {
+            id: Value::Long(id),
+            score,
+            fields: HashMap::from([("vec".to_owned(), vector)]),
+        }
+    }
+
+    #[test]
+    fn test_rerank_hits_cosine() {
+        // Ranked by IP, the long vector 1 comes first but points away from the query
+        let mut hits = vec![
+            hit(1, 10.0, Value::FloatArray(vec![10.0, 10.0].into())),
+            hit(2, 1.0, Value::FloatArray(vec![1.0, 0.0].into())),
+            hit(3, 0.7, Value::FloatArray(vec![0.7, 0.7].into())),
+        ];
+        let query = QueryVector::FloatVector(vec![1.0, 0.0]);
+
+        rerank_hits(&mut hits, "vec", &query, MetricType::COSINE).unwrap();
+
+        assert_eq!(ids(&hits), vec![2, 1, 3]);
+        assert!((hits[0].score - 1.0).abs() < 1e-6);
+        assert!((hits[1].score - 0.5f32.sqrt()).abs() < 1e-6);
+
+        rerank_hits(&mut hits, "vec", &query, MetricType::L2).unwrap();
+        assert_eq!(ids(&hits), vec![2, 3, 1]);
+    }
+
+    #[test]
+    fn test_rerank_hits_sparse() {
+        let mut hits = vec![
+            hit(1, 0.0, Value::SparseFloatVector(vec![(4, 1.0)].into())),
+            hit(
+                2,
+                0.0,
+                Value::SparseFloatVector(vec![(1, 1.0), (4, 0.5)].into()),
+            ),
+        ];
+        let query = QueryVector::SparseVector(vec![(1, 2.0)]);
+
+        rerank_hits(&mut hits, "vec", &query, MetricType::IP).unwrap();
+        assert_eq!(ids(&hits), vec![2, 1]);
+        assert_eq!(hits[0].score, 2.0);
+        assert_eq!(hits[1].score, 0.0);
+
+        assert!(rerank_hits(&mut hits, "vec", &query, MetricType::L2).is_err());
+    }
+
+    #[test]
+    fn test_rerank_hits_errors() {
+        let mut hits = vec![hit(1, 0.5, Value::FloatArray(vec![1.0, 0.0].into()))];
+        let query = QueryVector::FloatVector(vec![1.0, 0.0]);
+
+        assert!(rerank_hits(&mut hits, "other", &query, MetricType::IP).is_err());
+        assert!(rerank_hits(&mut hits, "vec", &query, MetricType::HAMMING).is_err());
+        let sparse = QueryVector::SparseVector(vec![(1, 1.0)]);
+        assert!(rerank_hits(&mut hits, "vec", &sparse, MetricType::IP).is_err());
+        let short = QueryVector::FloatVector(vec![1.0]);
+        assert!(rerank_hits(&mut hits, "vec", &short, MetricType::IP).is_err());
+        assert_eq!(hits[0].score, 0.5);
+    }
+
+    #[test]
+    fn test_dense_distances_edge_cases() {
+        assert_eq!(dense_cosine(&[0.0, 0.0], &[1.0, 2.0]).unwrap(), 0.0);
+        assert_eq!(dense_cosine(&[], &[]).unwrap(), 0.0);
+        assert_eq!(dense_l2(&[], &[]).unwrap(), 0.0);
+
+        for result in [
+            dense_l2(&[1.0], &[1.0, 2.0]),
+            dense_ip(&[1.0], &[1.0, 2.0]),
+            dense_cosine(&[1.0], &[1.0, 2.0]),
+        ] {
+            assert!(matches!(result, Err(Error::InvalidParameter(name, _)) if name == "b"));
+        }
+    }
+}
@@ -19,6 +19,7 @@
 use crate::collection::Error as CollectionError;
 use crate::proto::common::{ErrorCode, Status};
 use crate::schema::Error as SchemaError;
+use std::collections::HashMap;
 use std::result;
 use thiserror::Error;
 use tonic::Status as GrpcError;
@@ -72,6 +73,23 @@ pub enum Error {
 
     #[error("Sparse vector error: {0}")]
     SparseVectorError(String),
+
+    #[error("timed out: {0}")]
+    Timeout(String),
+
+    #[error("failed to flush collections {:?}", .1.keys().collect::<Vec<_>>())]
+    PartialFlush(HashMap<String, Vec<i64>>, HashMap<String, Error>),
+
+    #[error("line {0}: {1}")]
+    Import(usize, Box<Error>),
+
+    #[cfg(feature = "arrow")]
+    #[error("{0:?}")]
+    Arrow(#[from] arrow_schema::ArrowError),
+
+    #[cfg(feature = "polars")]
+    #[error("{0:?}")]
+    Polars(#[from] polars::error::PolarsError),
 }
 
 impl From<Status> for Error {
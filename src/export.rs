@@ -0,0 +1,576 @@
+// Licensed to the LF AI & Data foundation under one
+// or more contributor license agreements. See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership. The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Export and import of collection data as JSON Lines, one JSON object per
+//! entity.
+//!
+//! Fields map to JSON as follows, [`Client::import_jsonl`] reads the same forms:
+//!
+//! | Milvus type | JSON |
+//! |---|---|
+//! | null values of nullable fields | `null` |
+//! | scalars | numbers, strings and booleans |
+//! | `Json` | the stored JSON value |
+//! | `Array` | an array of its elements |
+//! | `FloatVector` | an array of numbers |
+//! | `BinaryVector` / `Float16Vector` / `BFloat16Vector` | an array of the raw bytes |
+//! | `SparseFloatVector` | an object mapping indices to values, see [`sparse_to_rest_json`] |
+//!
+//! Dynamic fields are written as top level keys of the entity, like the fields
+//! of the schema. Auto-id primary keys are exported too, importing them back
+//! needs [`ImportOptions::drop_auto_id`] so the server assigns new ones.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! let file = std::io::BufWriter::new(std::fs::File::create("backup.jsonl")?);
+//! let rows = client.export_jsonl("my_collection", file, 1000).await?;
+//!
+//! let file = std::io::BufReader::new(std::fs::File::open("backup.jsonl")?);
+//! let options = ImportOptions::new().drop_auto_id(true);
+//! let rows = client.import_jsonl("my_collection", file, 1000, Some(options)).await?;
+//! ```
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+
+use serde_json::{Map, Value as JsonValue};
+
+use crate::client::Client;
+use crate::data::ToRows;
+use crate::error::{Error, Result};
+use crate::iterator::QueryIteratorOptions;
+use crate::mutate::rows_to_columns;
+use crate::proto::schema::{
+    self as proto_schema, DataType, ScalarField, scalar_field::Data as ScalarData,
+};
+use crate::schema::{CollectionSchema, DYNAMIC_FIELD_NAME, Error as SchemaError, FieldSchema};
+use crate::sparse::{sparse_from_rest_json, sparse_to_rest_json};
+use crate::value::{RowMap, Value};
+
+/// Options of [`Client::import_jsonl`].
+#[derive(Debug, Clone, Default)]
+pub struct ImportOptions {
+    pub(crate) drop_auto_id: bool,
+}
+
+impl ImportOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drops the values of auto-id primary keys, such as the ones written by
+    /// [`Client::export_jsonl`], so the server assigns new ones. Lines holding
+    /// them are rejected otherwise.
+    pub fn drop_auto_id(mut self, drop_auto_id: bool) -> Self {
+        self.drop_auto_id = drop_auto_id;
+        self
+    }
+}
+
+/// The collection lines are imported into.
+struct ImportTarget {
+    schema: CollectionSchema,
+    /// Element types of the `Array` fields, which [`FieldSchema`] does not keep.
+    element_types: HashMap<String, DataType>,
+    drop_auto_id: bool,
+}
+
+impl Client {
+    /// Writes every entity of a collection to `writer` as JSON Lines.
+    ///
+    /// The collection is read with a query iterator, `batch_size` entities at a
+    /// time, so it does not need to fit in memory. Every field is exported,
+    /// see the [module docs](self) for the JSON form of each type.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection to export.
+    /// * `writer` - Destination of the lines, wrap files in a `BufWriter`.
+    /// * `batch_size` - Number of entities fetched per query.
+    ///
+    /// # Returns
+    ///
+    /// The number of entities written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if querying or writing fails, or if the collection has a
+    /// field of a type with no JSON form, such as an array of structs.
+    pub async fn export_jsonl<S, W>(
+        &self,
+        collection_name: S,
+        mut writer: W,
+        batch_size: usize,
+    ) -> Result<usize>
+    where
+        S: Into<String>,
+        W: Write,
+    {
+        let options =
+            QueryIteratorOptions::with_batch_size(batch_size).output_fields(vec!["*".to_owned()]);
+        let mut iterator = self.query_iterator(collection_name, options).await?;
+
+        let mut count = 0;
+        while let Some(columns) = iterator.next().await? {
+            for row in columns.rows() {
+                serde_json::to_writer(&mut writer, &row_to_json(&row)?)?;
+                writer.write_all(b"\n")?;
+                count += 1;
+            }
+        }
+        writer.flush()?;
+
+        Ok(count)
+    }
+
+    /// Inserts the entities of JSON Lines read from `reader`, as written by
+    /// [`Client::export_jsonl`].
+    ///
+    /// Every line is checked against the collection schema, described once up
+    /// front, before it is added to the current batch. Blank lines are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection to insert into.
+    /// * `reader` - Source of the lines.
+    /// * `batch_size` - Number of entities sent per insert.
+    /// * `options` - Optional import options, see [`ImportOptions`].
+    ///
+    /// # Returns
+    ///
+    /// The number of entities inserted.
+    ///
+    /// # Errors
+    ///
+    /// Invalid lines fail with [`Error::Import`] holding the 1-based line
+    /// number. Batches inserted before the failing line are not rolled back.
+    pub async fn import_jsonl<S, R>(
+        &self,
+        collection_name: S,
+        reader: R,
+        batch_size: usize,
+        options: Option<ImportOptions>,
+    ) -> Result<usize>
+    where
+        S: Into<String>,
+        R: BufRead,
+    {
+        let collection_name = collection_name.into();
+        let schema = self
+            .describe_collection(collection_name.clone())
+            .await?
+            .schema;
+        let target = ImportTarget::new(schema, options.unwrap_or_default());
+        let batch_size = batch_size.max(1);
+
+        let mut batch = Vec::with_capacity(batch_size);
+        let mut count = 0;
+        for (idx, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let row =
+                json_to_row(&target, &line).map_err(|e| Error::Import(idx + 1, Box::new(e)))?;
+            batch.push(row);
+
+            if batch.len() == batch_size {
+                count += batch.len();
+                let columns = rows_to_columns(&target.schema, std::mem::take(&mut batch))?;
+                self.insert(collection_name.clone(), columns, None).await?;
+            }
+        }
+        if !batch.is_empty() {
+            count += batch.len();
+            let columns = rows_to_columns(&target.schema, batch)?;
+            self.insert(collection_name, columns, None).await?;
+        }
+
+        Ok(count)
+    }
+}
+
+impl ImportTarget {
+    fn new(schema: proto_schema::CollectionSchema, options: ImportOptions) -> Self {
+        let element_types = schema
+            .fields
+            .iter()
+            .filter(|f| f.data_type == DataType::Array as i32)
+            .map(|f| (f.name.clone(), f.element_type()))
+            .collect();
+        Self {
+            schema: schema.into(),
+            element_types,
+            drop_auto_id: options.drop_auto_id,
+        }
+    }
+}
+
+/// Parses one line into a row holding every field that takes user data, a
+/// nullable field may be left out or `null`.
+///
+/// With dynamic fields enabled, keys the schema lacks are kept as JSON values,
+/// otherwise they are rejected. So are auto-id primary keys, unless
+/// [`ImportOptions::drop_auto_id`] is set.
+fn json_to_row(target: &ImportTarget, line: &str) -> Result<RowMap> {
+    let schema = &target.schema;
+    let mut object = match serde_json::from_str(line)? {
+        JsonValue::Object(object) => object,
+        json => {
+            return Err(Error::Unexpected(format!(
+                "expected a JSON object, got {json}"
+            )));
+        }
+    };
+
+    for field in schema.fields.iter().filter(|f| f.auto_id) {
+        if object.remove(&field.name).is_some() && !target.drop_auto_id {
+            return Err(SchemaError::AutoIdProvided(field.name.clone()).into());
+        }
+    }
+
+    let mut row = RowMap::new();
+    for field in schema
+        .fields
+        .iter()
+        .filter(|f| !f.auto_id && !f.is_function_output && f.name != DYNAMIC_FIELD_NAME)
+    {
+        let json = match object.remove(&field.name) {
+            Some(json) => json,
+            None if field.nullable => continue,
+            None => return Err(SchemaError::FieldDoesNotExists(field.name.clone()).into()),
+        };
+        let element_type = target.element_types.get(&field.name).copied();
+        row.insert(
+            field.name.clone(),
+            json_to_value(field, element_type, json)?,
+        );
+    }
+
+    if !schema.enable_dynamic_field {
+        if let Some(name) = object.into_iter().next().map(|(name, _)| name) {
+            return Err(SchemaError::FieldDoesNotExists(name).into());
+        }
+        return Ok(row);
+    }
+    for (name, json) in object {
+        row.insert(name, Value::Json(Cow::Owned(serde_json::to_vec(&json)?)));
+    }
+    Ok(row)
+}
+
+fn json_to_value(
+    field: &FieldSchema,
+    element_type: Option<DataType>,
+    json: JsonValue,
+) -> Result<Value<'static>> {
+    let invalid = |json: &JsonValue| Error::InvalidParameter(field.name.clone(), json.to_string());
+    if json.is_null() && field.nullable {
+        return Ok(Value::None);
+    }
+
+    let value = match field.dtype {
+        DataType::Bool => json.as_bool().map(Value::Bool),
+        DataType::Int8 => json
+            .as_i64()
+            .and_then(|v| v.try_into().ok())
+            .map(Value::Int8),
+        DataType::Int16 => json
+            .as_i64()
+            .and_then(|v| v.try_into().ok())
+            .map(Value::Int16),
+        DataType::Int32 => json
+            .as_i64()
+            .and_then(|v| v.try_into().ok())
+            .map(Value::Int32),
+        DataType::Int64 => json.as_i64().map(Value::Long),
+        DataType::Float => json.as_f64().map(|v| Value::Float(v as f32)),
+        DataType::Double => json.as_f64().map(Value::Double),
+        DataType::String | DataType::VarChar => json
+            .as_str()
+            .map(|v| Value::String(Cow::Owned(v.to_owned()))),
+        DataType::Json => Some(Value::Json(Cow::Owned(serde_json::to_vec(&json)?))),
+        DataType::FloatVector => json
+            .as_array()
+            .and_then(|a| a.iter().map(|v| v.as_f64().map(|v| v as f32)).collect())
+            .map(|v: Vec<f32>| Value::FloatArray(Cow::Owned(v))),
+        // `Value` has no bfloat16 variant, its raw bytes are carried as binary
+        DataType::BinaryVector | DataType::Float16Vector | DataType::BFloat16Vector => json
+            .as_array()
+            .and_then(|a| a.iter().map(|v| v.as_u64()?.try_into().ok()).collect())
+            .map(|v: Vec<u8>| match field.dtype {
+                DataType::Float16Vector => Value::Float16Array(Cow::Owned(v)),
+                _ => Value::Binary(Cow::Owned(v)),
+            }),
+        DataType::SparseFloatVector => Some(Value::SparseFloatVector(Cow::Owned(
+            sparse_from_rest_json(&json)?,
+        ))),
+        DataType::Array => json
+            .as_array()
+            .and_then(|a| json_to_scalar_field(element_type?, a))
+            .map(|v| Value::Array(Cow::Owned(v))),
+        dtype => {
+            return Err(Error::Unexpected(format!(
+                "field {} of type {:?} can not be imported from JSON",
+                field.name, dtype
+            )));
+        }
+    };
+    value.ok_or_else(|| invalid(&json))
+}
+
+/// Converts a row to a JSON object, flattening the dynamic fields into it.
+fn row_to_json(row: &RowMap) -> Result<Map<String, JsonValue>> {
+    let mut object = Map::new();
+    for (name, value) in row {
+        match (name.as_str(), value_to_json(name, value)?) {
+            (DYNAMIC_FIELD_NAME, JsonValue::Object(dynamic)) => object.extend(dynamic),
+            (_, json) => {
+                object.insert(name.clone(), json);
+            }
+        }
+    }
+    Ok(object)
+}
+
+fn value_to_json(name: &str, value: &Value) -> Result<JsonValue> {
+    if let Some(json) = value.to_json() {
+        return Ok(json);
+    }
+
+    let json = match value {
+        Value::SparseFloatVector(v) => Some(sparse_to_rest_json(&v.to_vec())),
+        Value::Binary(v) | Value::Float16Array(v) => Some(v.iter().copied().collect()),
+        Value::Array(field) => scalar_field_to_json(field),
+        _ => None,
+    };
+    json.ok_or_else(|| {
+        Error::Unexpected(format!(
+            "field {} of type {:?} can not be exported to JSON",
+            name,
+            value.data_type()
+        ))
+    })
+}
+
+/// Reads the elements of an `Array` field, `None` if one is not of `element_type`.
+fn json_to_scalar_field(element_type: DataType, elements: &[JsonValue]) -> Option<ScalarField> {
+    fn collect<T>(elements: &[JsonValue], f: impl Fn(&JsonValue) -> Option<T>) -> Option<Vec<T>> {
+        elements.iter().map(f).collect()
+    }
+
+    let data = match element_type {
+        DataType::Bool => ScalarData::BoolData(proto_schema::BoolArray {
+            data: collect(elements, JsonValue::as_bool)?,
+        }),
+        DataType::Int8 | DataType::Int16 | DataType::Int32 => {
+            ScalarData::IntData(proto_schema::IntArray {
+                data: collect(elements, |v| v.as_i64()?.try_into().ok())?,
+            })
+        }
+        DataType::Int64 => ScalarData::LongData(proto_schema::LongArray {
+            data: collect(elements, JsonValue::as_i64)?,
+        }),
+        DataType::Float => ScalarData::FloatData(proto_schema::FloatArray {
+            data: collect(elements, |v| v.as_f64().map(|v| v as f32))?,
+        }),
+        DataType::Double => ScalarData::DoubleData(proto_schema::DoubleArray {
+            data: collect(elements, JsonValue::as_f64)?,
+        }),
+        DataType::String | DataType::VarChar => ScalarData::StringData(proto_schema::StringArray {
+            data: collect(elements, |v| v.as_str().map(str::to_owned))?,
+        }),
+        _ => return None,
+    };
+    Some(ScalarField { data: Some(data) })
+}
+
+fn scalar_field_to_json(field: &ScalarField) -> Option<JsonValue> {
+    Some(match field.data.as_ref()? {
+        ScalarData::BoolData(a) => a.data.iter().copied().collect(),
+        ScalarData::IntData(a) => a.data.iter().copied().collect(),
+        ScalarData::LongData(a) => a.data.iter().copied().collect(),
+        ScalarData::FloatData(a) => a.data.iter().copied().collect(),
+        ScalarData::DoubleData(a) => a.data.iter().copied().collect(),
+        ScalarData::StringData(a) => a.data.iter().map(String::as_str).collect(),
+        ScalarData::JsonData(a) => a
+            .data
+            .iter()
+            .map(|v| serde_json::from_slice(v).ok())
+            .collect::<Option<Vec<JsonValue>>>()?
+            .into(),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::FieldColumn;
+    use crate::proto::schema::LongArray;
+    use crate::schema::CollectionSchemaBuilder;
+    use crate::value::ValueVec;
+    use serde_json::json;
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_row_to_json() {
+        let row = RowMap::from_iter([
+            ("id".to_owned(), Value::Long(7)),
+            ("title".to_owned(), Value::from("vector db".to_owned())),
+            ("dense".to_owned(), Value::from(vec![0.5f32, 0.25])),
+            ("sparse".to_owned(), Value::from(vec![(3u32, 0.5f32)])),
+            (
+                "metadata".to_owned(),
+                Value::Json(Cow::Owned(br#"{"tags":["a"]}"#.to_vec())),
+            ),
+            (
+                "years".to_owned(),
+                Value::Array(Cow::Owned(ScalarField {
+                    data: Some(ScalarData::LongData(LongArray {
+                        data: vec![2020, 2024],
+                    })),
+                })),
+            ),
+            (
+                DYNAMIC_FIELD_NAME.to_owned(),
+                Value::Json(Cow::Owned(br#"{"color":"red"}"#.to_vec())),
+            ),
+        ]);
+
+        let object = row_to_json(&row).unwrap();
+        assert_eq!(
+            JsonValue::Object(object),
+            json!({
+                "id": 7,
+                "title": "vector db",
+                "dense": [0.5, 0.25],
+                "sparse": {"3": 0.5},
+                "metadata": {"tags": ["a"]},
+                "years": [2020, 2024],
+                "color": "red",
+            })
+        );
+    }
+
+    #[test]
+    fn test_json_to_row() {
+        let schema = CollectionSchemaBuilder::new("docs", "")
+            .add_field(FieldSchema::new_primary_int64("id", "", false))
+            .add_field(FieldSchema::new_float_vector("dense", "", 2))
+            .add_field(FieldSchema::new_sparse_float_vector("sparse", ""))
+            .enable_dynamic_field()
+            .build()
+            .unwrap();
+        let target = ImportTarget::new(schema.into(), ImportOptions::new());
+
+        let row = json_to_row(
+            &target,
+            r#"{"id": 7, "dense": [0.5, 0.25], "sparse": {"3": 0.5}, "color": "red"}"#,
+        )
+        .unwrap();
+        assert!(matches!(row["id"], Value::Long(7)));
+        assert!(matches!(&row["dense"], Value::FloatArray(v) if v[..] == [0.5, 0.25]));
+        assert!(matches!(&row["sparse"], Value::SparseFloatVector(v) if v[..] == [(3, 0.5)]));
+        assert!(matches!(&row["color"], Value::Json(v) if v[..] == *br#""red""#));
+
+        let err = json_to_row(
+            &target,
+            r#"{"id": "7", "dense": [0.5, 0.25], "sparse": {}}"#,
+        );
+        assert!(matches!(err, Err(Error::InvalidParameter(name, _)) if name == "id"));
+
+        let err = json_to_row(&target, r#"{"id": 7, "sparse": {}}"#);
+        assert!(matches!(
+            err,
+            Err(Error::Schema(SchemaError::FieldDoesNotExists(name))) if name == "dense"
+        ));
+    }
+
+    #[test]
+    fn test_export_import_roundtrip_auto_id() {
+        let field = |name: &str, dtype, dim| FieldSchema {
+            name: name.to_owned(),
+            dtype,
+            dim,
+            ..Default::default()
+        };
+        let schema = CollectionSchemaBuilder::new("docs", "")
+            .add_field(FieldSchema::new_primary_int64("id", "", true))
+            .add_field(FieldSchema::new_float_vector("dense", "", 2))
+            .add_field(field("half", DataType::BFloat16Vector, 2))
+            .add_field(field("years", DataType::Array, 1))
+            .add_field(FieldSchema::new_int64("rank", "").with_nullable())
+            .build()
+            .unwrap();
+        let years = |data: Vec<i64>| ScalarField {
+            data: Some(ScalarData::LongData(LongArray { data })),
+        };
+        let mut rank = FieldColumn::new(schema.get_field("rank").unwrap(), vec![3i64, 0]);
+        rank.valid_data = vec![true, false];
+        let exported = [
+            FieldColumn::new(schema.get_field("id").unwrap(), vec![1i64, 2]),
+            FieldColumn::new(
+                schema.get_field("dense").unwrap(),
+                vec![0.5f32, 0.25, 1.0, 2.0],
+            ),
+            FieldColumn::new(
+                schema.get_field("half").unwrap(),
+                ValueVec::Binary(vec![1, 2, 3, 4, 5, 6, 7, 8]),
+            ),
+            FieldColumn::new(
+                schema.get_field("years").unwrap(),
+                ValueVec::Array(vec![years(vec![2020]), years(vec![2021, 2024])]),
+            ),
+            rank,
+        ];
+        let lines: Vec<String> = exported
+            .rows()
+            .iter()
+            .map(|row| serde_json::to_string(&row_to_json(row).unwrap()).unwrap())
+            .collect();
+
+        let mut target = ImportTarget::new(schema.clone().into(), ImportOptions::new());
+        target
+            .element_types
+            .insert("years".to_owned(), DataType::Int64);
+        assert!(matches!(
+            json_to_row(&target, &lines[0]),
+            Err(Error::Schema(SchemaError::AutoIdProvided(name))) if name == "id"
+        ));
+
+        target.drop_auto_id = true;
+        let rows = lines
+            .iter()
+            .map(|line| json_to_row(&target, line))
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        let imported = rows_to_columns(&schema, rows).unwrap();
+
+        let names: Vec<&str> = imported.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["dense", "half", "years", "rank"]);
+        assert!(matches!(&imported[0].value, ValueVec::Float(v) if v[..] == [0.5, 0.25, 1.0, 2.0]));
+        assert!(
+            matches!(&imported[1].value, ValueVec::Binary(v) if v[..] == [1, 2, 3, 4, 5, 6, 7, 8])
+        );
+        let expected = [years(vec![2020]), years(vec![2021, 2024])];
+        assert!(matches!(&imported[2].value, ValueVec::Array(v) if v[..] == expected));
+        assert_eq!(imported[3].valid_data, vec![true, false]);
+        assert!(matches!(imported[3].get(0), Some(Value::Long(3))));
+    }
+}
@@ -0,0 +1,243 @@
+// Licensed to the LF AI & Data foundation under one
+// or more contributor license agreements. See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership. The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Builders for boolean filter expressions.
+//!
+//! Expressions render to the same syntax `query`, `search` and `delete` accept
+//! as strings, with string literals and JSON keys quoted and escaped.
+//!
+//! # Example
+//!
+//! ```
+//! use milvus::expr::{Expr, JsonKey};
+//!
+//! let expr = Expr::json_path("metadata", &[JsonKey::from("tags"), JsonKey::from(0)])
+//!     .eq("x")
+//!     .and(Expr::field("year").ge(2020));
+//! assert_eq!(expr.to_string(), r#"(metadata["tags"][0] == "x") and (year >= 2020)"#);
+//! ```
+
+use std::fmt;
+
+use crate::utils::append_quoted_string;
+
+/// One step of a JSON path, an object key or an array index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonKey {
+    Key(String),
+    Index(usize),
+}
+
+impl From<&str> for JsonKey {
+    fn from(key: &str) -> Self {
+        JsonKey::Key(key.to_owned())
+    }
+}
+
+impl From<String> for JsonKey {
+    fn from(key: String) -> Self {
+        JsonKey::Key(key)
+    }
+}
+
+impl From<usize> for JsonKey {
+    fn from(index: usize) -> Self {
+        JsonKey::Index(index)
+    }
+}
+
+/// A value that can appear on the right hand side of a comparison.
+pub trait Literal {
+    /// Appends the value to `expr` in expression syntax.
+    fn write_literal(&self, expr: &mut String);
+}
+
+impl Literal for &str {
+    fn write_literal(&self, expr: &mut String) {
+        append_quoted_string(expr, self);
+    }
+}
+
+impl Literal for String {
+    fn write_literal(&self, expr: &mut String) {
+        append_quoted_string(expr, self);
+    }
+}
+
+macro_rules! impl_display_literal {
+    ($($t:ty),*) => {
+        $(impl Literal for $t {
+            fn write_literal(&self, expr: &mut String) {
+                expr.push_str(&self.to_string());
+            }
+        })*
+    };
+}
+
+impl_display_literal!(bool, i8, i16, i32, i64, f32, f64);
+
+/// A filter expression, or an operand of one such as a field or JSON path.
+///
+/// Use `to_string` or [`Expr::as_str`] to pass it where an expression string
+/// is expected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expr(String);
+
+impl Expr {
+    /// A field of the collection, referenced by name.
+    pub fn field(name: &str) -> Self {
+        Expr(name.to_owned())
+    }
+
+    /// Access to a nested value of a JSON field, e.g. `metadata["tags"][0]`.
+    ///
+    /// Object keys are quoted and escaped like string literals, so they may
+    /// contain any character.
+    pub fn json_path(field: &str, path: &[JsonKey]) -> Self {
+        let mut expr = field.to_owned();
+        for key in path {
+            expr.push('[');
+            match key {
+                JsonKey::Key(key) => append_quoted_string(&mut expr, key),
+                JsonKey::Index(index) => expr.push_str(&index.to_string()),
+            }
+            expr.push(']');
+        }
+        Expr(expr)
+    }
+
+    /// Matches rows whose VarChar `field` contains any of the terms of `text`
+    /// after analysis, e.g. `TEXT_MATCH(title, "vector database")`.
+    ///
+    /// The field needs match enabled, see
+    /// [`FieldSchema::with_match`](crate::schema::FieldSchema::with_match).
+    pub fn text_match(field: &str, text: &str) -> Self {
+        let mut expr = format!("TEXT_MATCH({field}, ");
+        append_quoted_string(&mut expr, text);
+        expr.push(')');
+        Expr(expr)
+    }
+
+    pub fn eq(self, value: impl Literal) -> Self {
+        self.compare("==", value)
+    }
+
+    pub fn ne(self, value: impl Literal) -> Self {
+        self.compare("!=", value)
+    }
+
+    pub fn gt(self, value: impl Literal) -> Self {
+        self.compare(">", value)
+    }
+
+    pub fn ge(self, value: impl Literal) -> Self {
+        self.compare(">=", value)
+    }
+
+    pub fn lt(self, value: impl Literal) -> Self {
+        self.compare("<", value)
+    }
+
+    pub fn le(self, value: impl Literal) -> Self {
+        self.compare("<=", value)
+    }
+
+    /// Both expressions hold, each side is parenthesized.
+    pub fn and(self, other: Expr) -> Self {
+        Expr(format!("({}) and ({})", self.0, other.0))
+    }
+
+    /// Either expression holds, each side is parenthesized.
+    pub fn or(self, other: Expr) -> Self {
+        Expr(format!("({}) or ({})", self.0, other.0))
+    }
+
+    /// The expression does not hold.
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Self {
+        Expr(format!("not ({})", self.0))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    fn compare(mut self, op: &str, value: impl Literal) -> Self {
+        self.0.push(' ');
+        self.0.push_str(op);
+        self.0.push(' ');
+        value.write_literal(&mut self.0);
+        self
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<Expr> for String {
+    fn from(expr: Expr) -> Self {
+        expr.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_path() {
+        let tags = Expr::json_path("metadata", &["tags".into(), 0.into()]);
+        assert_eq!(tags.as_str(), r#"metadata["tags"][0]"#);
+
+        let nested = Expr::json_path("metadata", &["author".into(), "name".into()]);
+        assert_eq!(nested.as_str(), r#"metadata["author"]["name"]"#);
+
+        let quoted = Expr::json_path("metadata", &[r#"say "hi""#.into()]);
+        assert_eq!(quoted.as_str(), r#"metadata["say \"hi\""]"#);
+
+        assert_eq!(Expr::json_path("metadata", &[]).as_str(), "metadata");
+    }
+
+    #[test]
+    fn test_json_path_comparison() {
+        let expr = Expr::json_path("metadata", &["tags".into(), 0.into()]).eq("x");
+        assert_eq!(expr.to_string(), r#"metadata["tags"][0] == "x""#);
+
+        let expr = Expr::json_path("metadata", &["price".into()])
+            .lt(9.5)
+            .or(Expr::json_path("metadata", &["stock".into()]).ge(100))
+            .not();
+        assert_eq!(
+            expr.to_string(),
+            r#"not ((metadata["price"] < 9.5) or (metadata["stock"] >= 100))"#
+        );
+    }
+
+    #[test]
+    fn test_text_match() {
+        let expr = Expr::text_match("title", "vector database");
+        assert_eq!(expr.as_str(), r#"TEXT_MATCH(title, "vector database")"#);
+
+        let expr = Expr::text_match("title", r#"say "hi""#).and(Expr::field("year").ge(2020));
+        assert_eq!(
+            expr.to_string(),
+            r#"(TEXT_MATCH(title, "say \"hi\"")) and (year >= 2020)"#
+        );
+    }
+}
@@ -2,10 +2,14 @@ pub mod utils;
 
 use strum_macros::{Display, EnumString};
 
+use crate::collection;
+use crate::error::{Error, Result};
 use crate::proto::{
     common::{IndexState, KeyValuePair},
     milvus::IndexDescription,
+    schema::DataType,
 };
+use crate::schema::FieldSchema;
 use std::{collections::HashMap, str::FromStr};
 
 #[derive(Debug, Clone, Copy, EnumString, Display)]
@@ -50,10 +54,11 @@ pub enum IndexType {
     SparseInvertedIndex,
 }
 
-#[derive(Debug, Clone, Copy, EnumString, Display)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
 pub enum MetricType {
     L2,
     IP,
+    COSINE,
     HAMMING,
     JACCARD,
     TANIMOTO,
@@ -62,11 +67,55 @@ pub enum MetricType {
     BM25,
 }
 
+impl MetricType {
+    /// Metrics that can index and search a field of the given type, empty for
+    /// scalar types.
+    pub fn supported_for(data_type: DataType) -> &'static [MetricType] {
+        match data_type {
+            DataType::FloatVector | DataType::Float16Vector | DataType::BFloat16Vector => {
+                &[MetricType::L2, MetricType::IP, MetricType::COSINE]
+            }
+            DataType::BinaryVector => &[
+                MetricType::HAMMING,
+                MetricType::JACCARD,
+                MetricType::TANIMOTO,
+                MetricType::SUBSTRUCTURE,
+                MetricType::SUPERSTRUCTURE,
+            ],
+            DataType::SparseFloatVector => &[MetricType::IP, MetricType::BM25],
+            _ => &[],
+        }
+    }
+
+    /// Whether a larger score means a closer match, true for similarity metrics
+    /// such as IP and false for distance metrics such as L2.
+    pub fn higher_is_better(self) -> bool {
+        matches!(self, MetricType::IP | MetricType::COSINE | MetricType::BM25)
+    }
+
+    /// Checks that this metric can be used on a vector field of the given type,
+    /// scalar types accept any metric as they ignore it.
+    pub(crate) fn check_supported(self, field_name: &str, data_type: DataType) -> Result<()> {
+        let supported = Self::supported_for(data_type);
+        if supported.is_empty() || supported.contains(&self) {
+            Ok(())
+        } else {
+            Err(collection::Error::UnsupportedMetric(
+                field_name.to_owned(),
+                self,
+                data_type,
+                supported.to_vec(),
+            )
+            .into())
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct IndexParams {
     name: String,
     index_type: IndexType,
-    metric_type: MetricType,
+    metric_type: Option<MetricType>,
     params: HashMap<String, String>,
 }
 
@@ -80,7 +129,7 @@ impl IndexParams {
         Self {
             name,
             index_type,
-            metric_type,
+            metric_type: Some(metric_type),
             params,
         }
     }
@@ -93,7 +142,19 @@ impl IndexParams {
         self.index_type
     }
 
+    /// The metric of a vector index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index has no metric, as scalar indexes don't.
+    #[deprecated(note = "scalar indexes have no metric, use `try_metric_type`")]
     pub fn metric_type(&self) -> MetricType {
+        self.metric_type
+            .expect("index has no metric, use `try_metric_type`")
+    }
+
+    /// The metric of a vector index, `None` for scalar indexes which have none.
+    pub fn try_metric_type(&self) -> Option<MetricType> {
         self.metric_type
     }
 
@@ -102,14 +163,17 @@ impl IndexParams {
     }
 
     pub fn extra_params(&self) -> HashMap<String, String> {
-        HashMap::from([
+        let mut extra = HashMap::from([
             ("index_type".to_owned(), self.index_type().to_string()),
-            ("metric_type".to_owned(), self.metric_type().to_string()),
             (
                 "params".to_owned(),
                 serde_json::to_string(&self.params()).unwrap(),
             ),
-        ])
+        ]);
+        if let Some(metric_type) = self.try_metric_type() {
+            extra.insert("metric_type".to_owned(), metric_type.to_string());
+        }
+        extra
     }
 
     pub fn extra_kv_params(&self) -> Vec<KeyValuePair> {
@@ -118,6 +182,217 @@ impl IndexParams {
             .map(|(k, v)| KeyValuePair { key: k, value: v })
             .collect()
     }
+
+    /// The build parameters parsed by index type, see [`IndexConfig`].
+    pub fn config(&self) -> IndexConfig {
+        IndexConfig::parse(&self.index_type.to_string(), &self.params)
+    }
+}
+
+/// A request to index one field, validated against the field's type, see
+/// [`IndexBuilder`].
+#[derive(Debug, Clone)]
+pub struct IndexRequest {
+    field_name: String,
+    params: IndexParams,
+}
+
+impl IndexRequest {
+    pub fn field_name(&self) -> &str {
+        &self.field_name
+    }
+
+    pub fn params(&self) -> &IndexParams {
+        &self.params
+    }
+}
+
+/// Builds an [`IndexRequest`] for
+/// [`Client::create_index_with`](crate::client::Client::create_index_with).
+///
+/// The index is named after its field unless [`IndexBuilder::name`] is given.
+/// A metric is required for vector fields and must be one their type supports,
+/// scalar indexes are built without one.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use milvus::index::{IndexBuilder, IndexType, MetricType};
+///
+/// let request = IndexBuilder::new()
+///     .field(schema.get_field("embedding").unwrap())
+///     .index_type(IndexType::HNSW)
+///     .metric(MetricType::COSINE)
+///     .param("M", "16")
+///     .param("efConstruction", "200")
+///     .build()?;
+/// client.create_index_with("my_collection", request).await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IndexBuilder {
+    name: Option<String>,
+    field: Option<(String, DataType)>,
+    index_type: Option<IndexType>,
+    metric: Option<MetricType>,
+    params: HashMap<String, String>,
+}
+
+impl IndexBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Names the index, the field name by default.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// The field to index, its type decides which metrics are accepted.
+    pub fn field(mut self, field: &FieldSchema) -> Self {
+        self.field = Some((field.name.clone(), field.dtype));
+        self
+    }
+
+    pub fn index_type(mut self, index_type: IndexType) -> Self {
+        self.index_type = Some(index_type);
+        self
+    }
+
+    pub fn metric(mut self, metric: MetricType) -> Self {
+        self.metric = Some(metric);
+        self
+    }
+
+    /// Adds a build parameter of the index type, e.g. `M` for HNSW, replacing
+    /// any previous value of `key`.
+    pub fn param(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.params.insert(key.into(), value.into());
+        self
+    }
+
+    /// Checks the index is complete and its metric suits the field.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if the field or index type is missing, or
+    /// the metric of a vector field, and `UnsupportedMetric` if the field's
+    /// type does not support the metric.
+    pub fn build(self) -> Result<IndexRequest> {
+        let missing = |param: &str, message: &str| {
+            Error::InvalidParameter(param.to_owned(), message.to_owned())
+        };
+        let (field_name, data_type) = self
+            .field
+            .ok_or_else(|| missing("field", "an index needs a field"))?;
+        let index_type = self
+            .index_type
+            .ok_or_else(|| missing("index_type", "an index needs a type"))?;
+        let metric_type = match self.metric {
+            // scalar indexes have no metric
+            _ if MetricType::supported_for(data_type).is_empty() => None,
+            Some(metric) => {
+                metric.check_supported(&field_name, data_type)?;
+                Some(metric)
+            }
+            None => return Err(missing("metric", "a vector index needs a metric")),
+        };
+
+        Ok(IndexRequest {
+            params: IndexParams {
+                name: self.name.unwrap_or_else(|| field_name.clone()),
+                index_type,
+                metric_type,
+                params: self.params,
+            },
+            field_name,
+        })
+    }
+}
+
+/// Index build parameters parsed by index type, so a described index can be
+/// compared against the one that was requested.
+///
+/// Index types without a typed variant, and known types whose parameters are
+/// missing or malformed, fall back to `Raw` holding every parameter including
+/// `index_type`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IndexConfig {
+    Flat,
+    BinFlat,
+    IvfFlat { nlist: u32 },
+    BinIvfFlat { nlist: u32 },
+    IvfSQ8 { nlist: u32 },
+    IvfPQ { nlist: u32, m: u32, nbits: u32 },
+    Hnsw { m: u32, ef_construction: u32 },
+    SparseInvertedIndex { drop_ratio_build: f32 },
+    Raw(HashMap<String, String>),
+}
+
+impl IndexConfig {
+    /// Parses the build parameters of an index of the given type, as they are
+    /// passed in `IndexParams::params`.
+    pub fn parse(index_type: &str, params: &HashMap<String, String>) -> Self {
+        let config = match IndexType::from_str(index_type) {
+            Ok(IndexType::Flat) => Some(IndexConfig::Flat),
+            Ok(IndexType::BinFlat) => Some(IndexConfig::BinFlat),
+            Ok(IndexType::IvfFlat) => {
+                param(params, "nlist").map(|nlist| IndexConfig::IvfFlat { nlist })
+            }
+            Ok(IndexType::BinIvfFlat) => {
+                param(params, "nlist").map(|nlist| IndexConfig::BinIvfFlat { nlist })
+            }
+            Ok(IndexType::IvfSQ8) => {
+                param(params, "nlist").map(|nlist| IndexConfig::IvfSQ8 { nlist })
+            }
+            Ok(IndexType::IvfPQ) => (|| {
+                Some(IndexConfig::IvfPQ {
+                    nlist: param(params, "nlist")?,
+                    m: param(params, "m")?,
+                    nbits: param(params, "nbits").unwrap_or(8),
+                })
+            })(),
+            Ok(IndexType::HNSW) => (|| {
+                Some(IndexConfig::Hnsw {
+                    m: param(params, "M")?,
+                    ef_construction: param(params, "efConstruction")?,
+                })
+            })(),
+            Ok(IndexType::SparseInvertedIndex) => param(params, "drop_ratio_build")
+                .map(|drop_ratio_build| IndexConfig::SparseInvertedIndex { drop_ratio_build }),
+            _ => None,
+        };
+
+        config.unwrap_or_else(|| {
+            let mut raw = params.clone();
+            raw.insert("index_type".to_owned(), index_type.to_owned());
+            IndexConfig::Raw(raw)
+        })
+    }
+}
+
+fn param<T: FromStr>(params: &HashMap<String, String>, key: &str) -> Option<T> {
+    params.get(key)?.parse().ok()
+}
+
+/// Reads the build parameters of a described index into string values, from the
+/// `params` JSON object when the server sends one and from the remaining top
+/// level key-value pairs otherwise.
+fn described_params(mut params: HashMap<String, String>) -> HashMap<String, String> {
+    let Some(nested) = params
+        .remove("params")
+        .and_then(|json| serde_json::from_str::<HashMap<String, serde_json::Value>>(&json).ok())
+    else {
+        return params;
+    };
+
+    nested
+        .into_iter()
+        .map(|(key, value)| match value {
+            serde_json::Value::String(value) => (key, value),
+            value => (key, value.to_string()),
+        })
+        .collect()
 }
 
 #[derive(Debug, Clone)]
@@ -125,7 +400,9 @@ pub struct IndexInfo {
     index_name: String,
     field_name: String,
     id: i64,
-    params: IndexParams,
+    index_type: String,
+    params: Option<IndexParams>,
+    config: IndexConfig,
     state: IndexState,
 }
 
@@ -138,8 +415,34 @@ impl IndexInfo {
         self.id
     }
 
+    /// The index type as the server names it, also for types [`IndexType`] has
+    /// no variant for such as `INVERTED` or `AUTOINDEX`.
+    pub fn index_type(&self) -> &str {
+        &self.index_type
+    }
+
+    /// The index parameters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`IndexType`] has no variant for the index type.
+    #[deprecated(note = "not every index type has an `IndexType`, use `try_params`")]
     pub fn params(&self) -> &IndexParams {
-        &self.params
+        self.params
+            .as_ref()
+            .expect("unknown index type, use `try_params`")
+    }
+
+    /// The index parameters, `None` if [`IndexType`] has no variant for the
+    /// index type, see [`IndexInfo::config`] for its parameters then.
+    pub fn try_params(&self) -> Option<&IndexParams> {
+        self.params.as_ref()
+    }
+
+    /// The build parameters parsed by index type, `Raw` for index types this
+    /// client has no typed variant for.
+    pub fn config(&self) -> &IndexConfig {
+        &self.config
     }
 
     pub fn state(&self) -> IndexState {
@@ -160,22 +463,288 @@ impl From<IndexDescription> for IndexInfo {
                 .map(|kv| (kv.key.clone(), kv.value.clone())),
         );
 
-        let index_type = IndexType::from_str(&params.remove("index_type").unwrap()).unwrap();
-        let metric_type = MetricType::from_str(&params.remove("metric_type").unwrap()).unwrap();
-        let params = serde_json::from_str(params.get("params").unwrap()).unwrap();
+        let index_type_name = params.remove("index_type").unwrap_or_default();
+        // scalar indexes have no metric
+        let metric_type = params
+            .remove("metric_type")
+            .and_then(|metric| MetricType::from_str(&metric).ok());
+        let params = described_params(params);
+        let config = IndexConfig::parse(&index_type_name, &params);
+        let params = IndexType::from_str(&index_type_name)
+            .ok()
+            .map(|index_type| IndexParams {
+                name: description.index_name.clone(),
+                index_type,
+                metric_type,
+                params,
+            });
 
-        let params = IndexParams::new(
-            description.index_name.clone(),
-            index_type,
-            metric_type,
-            params,
-        );
         Self {
             index_name: description.index_name.clone(),
             field_name: description.field_name.clone(),
             id: description.index_id,
-            params: params,
+            index_type: index_type_name,
+            params,
+            config,
             state: description.state(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supported_metrics() {
+        assert_eq!(
+            MetricType::supported_for(DataType::FloatVector),
+            &[MetricType::L2, MetricType::IP, MetricType::COSINE]
+        );
+        assert_eq!(
+            MetricType::supported_for(DataType::BinaryVector),
+            &[
+                MetricType::HAMMING,
+                MetricType::JACCARD,
+                MetricType::TANIMOTO,
+                MetricType::SUBSTRUCTURE,
+                MetricType::SUPERSTRUCTURE,
+            ]
+        );
+        assert_eq!(
+            MetricType::supported_for(DataType::SparseFloatVector),
+            &[MetricType::IP, MetricType::BM25]
+        );
+        assert!(MetricType::supported_for(DataType::VarChar).is_empty());
+    }
+
+    #[test]
+    fn test_check_supported() {
+        assert!(
+            MetricType::COSINE
+                .check_supported("dense", DataType::FloatVector)
+                .is_ok()
+        );
+        assert!(
+            MetricType::L2
+                .check_supported("title", DataType::VarChar)
+                .is_ok()
+        );
+
+        let Err(crate::error::Error::Collection(err)) =
+            MetricType::COSINE.check_supported("sparse", DataType::SparseFloatVector)
+        else {
+            panic!("expected an unsupported metric error");
+        };
+        assert_eq!(
+            err.to_string(),
+            "metric COSINE is not supported by field \"sparse\", SparseFloatVector supports only: [IP, BM25]"
+        );
+    }
+
+    #[test]
+    fn test_index_builder_hnsw() {
+        let field = FieldSchema::new_float_vector("embedding", "", 128);
+        let request = IndexBuilder::new()
+            .field(&field)
+            .index_type(IndexType::HNSW)
+            .metric(MetricType::COSINE)
+            .param("M", "16")
+            .param("efConstruction", "200")
+            .build()
+            .unwrap();
+
+        assert_eq!(request.field_name(), "embedding");
+        let params = request.params();
+        assert_eq!(params.name(), "embedding");
+        assert!(matches!(params.index_type(), IndexType::HNSW));
+        assert_eq!(params.try_metric_type(), Some(MetricType::COSINE));
+        assert_eq!(
+            params.config(),
+            IndexConfig::Hnsw {
+                m: 16,
+                ef_construction: 200
+            }
+        );
+        let extra = params.extra_params();
+        assert_eq!(extra["index_type"], "HNSW");
+        assert_eq!(extra["metric_type"], "COSINE");
+
+        let named = IndexBuilder::new()
+            .name("embedding_hnsw")
+            .field(&field)
+            .index_type(IndexType::HNSW)
+            .metric(MetricType::L2)
+            .build()
+            .unwrap();
+        assert_eq!(named.params().name(), "embedding_hnsw");
+    }
+
+    #[test]
+    fn test_index_builder_rejects() {
+        let sparse = FieldSchema::new_sparse_float_vector("sparse", "");
+        let err = IndexBuilder::new()
+            .field(&sparse)
+            .index_type(IndexType::SparseInvertedIndex)
+            .metric(MetricType::L2)
+            .build()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Collection(collection::Error::UnsupportedMetric(field, MetricType::L2, ..))
+                if field == "sparse"
+        ));
+        assert!(
+            IndexBuilder::new()
+                .field(&sparse)
+                .index_type(IndexType::SparseInvertedIndex)
+                .metric(MetricType::IP)
+                .build()
+                .is_ok()
+        );
+
+        let err = IndexBuilder::new()
+            .field(&sparse)
+            .index_type(IndexType::SparseInvertedIndex)
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidParameter(param, _) if param == "metric"));
+        let err = IndexBuilder::new()
+            .index_type(IndexType::HNSW)
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidParameter(param, _) if param == "field"));
+
+        // scalar indexes need no metric
+        let title = FieldSchema::new_varchar("title", "", 64);
+        let request = IndexBuilder::new()
+            .field(&title)
+            .index_type(IndexType::Trie)
+            .build()
+            .unwrap();
+        assert_eq!(request.params().name(), "title");
+        assert_eq!(request.params().try_metric_type(), None);
+        assert!(!request.params().extra_params().contains_key("metric_type"));
+        let request = IndexBuilder::new()
+            .field(&title)
+            .index_type(IndexType::Trie)
+            .metric(MetricType::L2)
+            .build()
+            .unwrap();
+        assert_eq!(request.params().try_metric_type(), None);
+    }
+
+    fn string_map(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_index_config_parse() {
+        let hnsw = IndexParams::new(
+            "idx".to_owned(),
+            IndexType::HNSW,
+            MetricType::L2,
+            string_map(&[("M", "16"), ("efConstruction", "200")]),
+        );
+        assert_eq!(
+            hnsw.config(),
+            IndexConfig::Hnsw {
+                m: 16,
+                ef_construction: 200
+            }
+        );
+        assert_eq!(
+            IndexConfig::parse("IVF_FLAT", &string_map(&[("nlist", "128")])),
+            IndexConfig::IvfFlat { nlist: 128 }
+        );
+        assert_eq!(
+            IndexConfig::parse("FLAT", &HashMap::new()),
+            IndexConfig::Flat
+        );
+
+        assert_eq!(
+            IndexConfig::parse("DISKANN", &string_map(&[("search_list", "100")])),
+            IndexConfig::Raw(string_map(&[
+                ("search_list", "100"),
+                ("index_type", "DISKANN")
+            ]))
+        );
+        assert!(matches!(
+            IndexConfig::parse("HNSW", &string_map(&[("M", "sixteen")])),
+            IndexConfig::Raw(raw) if raw["index_type"] == "HNSW"
+        ));
+    }
+
+    #[test]
+    fn test_index_info_from_description() {
+        let description = IndexDescription {
+            index_name: "idx".to_owned(),
+            field_name: "dense".to_owned(),
+            params: vec![
+                KeyValuePair {
+                    key: "index_type".to_owned(),
+                    value: "HNSW".to_owned(),
+                },
+                KeyValuePair {
+                    key: "metric_type".to_owned(),
+                    value: "COSINE".to_owned(),
+                },
+                KeyValuePair {
+                    key: "params".to_owned(),
+                    value: r#"{"M":8,"efConstruction":"64"}"#.to_owned(),
+                },
+            ],
+            ..Default::default()
+        };
+
+        let info = IndexInfo::from(description);
+        assert_eq!(
+            info.config(),
+            &IndexConfig::Hnsw {
+                m: 8,
+                ef_construction: 64
+            }
+        );
+        let params = info.try_params().unwrap();
+        assert_eq!(params.try_metric_type(), Some(MetricType::COSINE));
+        assert_eq!(params.params()["M"], "8");
+
+        #[allow(deprecated)]
+        let metric_type = info.params().metric_type();
+        assert_eq!(metric_type, MetricType::COSINE);
+    }
+
+    #[test]
+    fn test_index_info_from_unknown_description() {
+        let kv = |key: &str, value: &str| KeyValuePair {
+            key: key.to_owned(),
+            value: value.to_owned(),
+        };
+        let inverted = IndexInfo::from(IndexDescription {
+            index_name: "title_index".to_owned(),
+            field_name: "title".to_owned(),
+            params: vec![kv("index_type", "INVERTED")],
+            ..Default::default()
+        });
+        assert_eq!(inverted.field_name(), "title");
+        assert_eq!(inverted.index_type(), "INVERTED");
+        assert!(inverted.try_params().is_none());
+        assert_eq!(
+            inverted.config(),
+            &IndexConfig::Raw(string_map(&[("index_type", "INVERTED")]))
+        );
+
+        let trie = IndexInfo::from(IndexDescription {
+            index_name: "title_trie".to_owned(),
+            params: vec![kv("index_type", "Trie")],
+            ..Default::default()
+        });
+        let params = trie.try_params().unwrap();
+        assert!(matches!(params.index_type(), IndexType::Trie));
+        assert_eq!(params.try_metric_type(), None);
+        assert!(!params.extra_params().contains_key("metric_type"));
+    }
+}
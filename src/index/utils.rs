@@ -13,10 +13,15 @@
 use crate::collection::Error;
 use crate::error::{Error as SuperError, Result};
 use crate::index::IndexInfo;
-use crate::proto::common::{IndexState, KeyValuePair, MsgBase, MsgType};
-use crate::proto::milvus::{CreateIndexRequest, DescribeIndexRequest, DropIndexRequest};
+use crate::proto::common::{ErrorCode, IndexState, KeyValuePair, MsgBase, MsgType};
+use crate::proto::milvus::{
+    CreateIndexRequest, DescribeIndexRequest, DropIndexRequest, IndexDescription,
+};
 use crate::utils::status_to_result;
-use crate::{client::Client, index::IndexParams};
+use crate::{
+    client::Client,
+    index::{IndexParams, IndexRequest},
+};
 use crate::{config, proto};
 use std::time::Duration;
 
@@ -30,14 +35,20 @@ impl Client {
     where
         S: Into<String>,
     {
+        let collection_name = collection_name.into();
         let field_name = field_name.into();
+        let dtype = self.field_data_type(&collection_name, &field_name).await?;
+        if let Some(metric_type) = index_params.try_metric_type() {
+            metric_type.check_supported(&field_name, dtype)?;
+        }
+
         let status = self
             .client
             .clone()
             .create_index(CreateIndexRequest {
                 base: Some(MsgBase::new(MsgType::CreateIndex)),
                 db_name: "".to_string(),
-                collection_name: collection_name.into(),
+                collection_name,
                 field_name,
                 extra_params: index_params.extra_kv_params(),
                 index_name: index_params.name().clone(),
@@ -83,7 +94,7 @@ impl Client {
 
             let index_info = index_infos
                 .iter()
-                .find(|&x| x.params().name() == index_params.name());
+                .find(|&x| x.index_name() == index_params.name());
             if index_info.is_none() {
                 return Err(SuperError::Unexpected(
                     "failed to describe index".to_owned(),
@@ -99,6 +110,30 @@ impl Client {
         }
     }
 
+    /// Creates the index described by a request from
+    /// [`IndexBuilder`](crate::index::IndexBuilder), waiting until it is built
+    /// like [`Client::create_index`].
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection.
+    /// * `request` - The field to index and the parameters of the index.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing `()` if successful, or an error if the index creation fails.
+    pub async fn create_index_with<S>(
+        &self,
+        collection_name: S,
+        request: IndexRequest,
+    ) -> Result<()>
+    where
+        S: Into<String>,
+    {
+        self.create_index(collection_name, request.field_name, request.params)
+            .await
+    }
+
     /// Retrieves information about the indexes for a specified field in a collection.
     ///
     /// # Arguments
@@ -116,14 +151,29 @@ impl Client {
     where
         S: Into<String>,
     {
+        Ok(self
+            .index_descriptions(collection_name.into(), field_name.into())
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    /// The indexes of a field, or of every field if `field_name` is empty, as
+    /// the server describes them.
+    pub(crate) async fn index_descriptions(
+        &self,
+        collection_name: String,
+        field_name: String,
+    ) -> Result<Vec<IndexDescription>> {
         let res = self
             .client
             .clone()
             .describe_index(DescribeIndexRequest {
                 base: Some(MsgBase::new(MsgType::DescribeIndex)),
                 db_name: "".to_string(),
-                collection_name: collection_name.into(),
-                field_name: field_name.into(),
+                collection_name,
+                field_name,
                 index_name: "".to_string(),
                 timestamp: 0,
             })
@@ -131,20 +181,63 @@ impl Client {
             .into_inner();
         status_to_result(&res.status)?;
 
-        Ok(res.index_descriptions.into_iter().map(Into::into).collect())
+        Ok(res.index_descriptions)
+    }
+
+    /// Retrieves information about every index of a collection, on vector and scalar fields alike.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection.
+    ///
+    /// # Returns
+    /// Returns a `Result` containing a vector of `IndexInfo`, empty if the collection has no index.
+    pub async fn describe_indexes<S: Into<String>>(
+        &self,
+        collection_name: S,
+    ) -> Result<Vec<IndexInfo>> {
+        Ok(self
+            .collection_index_descriptions(collection_name.into())
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    /// Every index of a collection as the server describes it, empty if the
+    /// collection has no index.
+    pub(crate) async fn collection_index_descriptions(
+        &self,
+        collection_name: String,
+    ) -> Result<Vec<IndexDescription>> {
+        match self
+            .index_descriptions(collection_name, String::new())
+            .await
+        {
+            Err(SuperError::Server(ErrorCode::IndexNotExist, _)) => Ok(Vec::new()),
+            indexes => indexes,
+        }
     }
 
     /// Drops an index for a specified field in a collection.
     ///
+    /// Dropping an index that does not exist is a no-op on the server and returns `Ok(())`.
+    ///
     /// # Arguments
     ///
     /// * `collection_name` - The name of the collection.
     /// * `field_name` - The name of the field to drop the index for.
+    /// * `index_name` - The name of the index to drop. If `None`, the index of the field is dropped.
     ///
     /// # Returns
     ///
     /// Returns a `Result` containing `()` if successful, or an error if the index drop fails.
-    pub async fn drop_index<S>(&self, collection_name: S, index_name: S) -> Result<()>
+    pub async fn drop_index<S>(
+        &self,
+        collection_name: S,
+        field_name: S,
+        index_name: Option<&str>,
+    ) -> Result<()>
     where
         S: Into<String>,
     {
@@ -155,8 +248,8 @@ impl Client {
                 base: Some(MsgBase::new(MsgType::DropIndex)),
                 db_name: "".to_string(),
                 collection_name: collection_name.into(),
-                field_name: "".to_string(),
-                index_name: index_name.into(),
+                field_name: field_name.into(),
+                index_name: index_name.unwrap_or_default().to_string(),
             })
             .await?
             .into_inner();
@@ -242,25 +335,20 @@ impl Client {
     ///
     /// # Returns
     ///
-    /// Returns a `Result` containing a vector of index names if successful, or an error if the index listing fails.
+    /// Returns a `Result` containing a vector of index names, empty if there is no index, or an error if the index listing fails.
     pub async fn list_indexes<S: Into<String>>(
         &self,
         collection_name: S,
         field_name: Option<S>,
     ) -> Result<Vec<String>> {
-        let res = if let Some(field_name) = field_name {
-            self.describe_index(collection_name, field_name)
-                .await?
-                .into_iter()
-                .map(|x| x.index_name)
-                .collect()
+        let indexes = if let Some(field_name) = field_name {
+            match self.describe_index(collection_name, field_name).await {
+                Err(SuperError::Server(ErrorCode::IndexNotExist, _)) => Vec::new(),
+                indexes => indexes?,
+            }
         } else {
-            self.describe_index(collection_name.into(), "".to_string())
-                .await?
-                .into_iter()
-                .map(|x| x.index_name)
-                .collect()
+            self.describe_indexes(collection_name).await?
         };
-        Ok(res)
+        Ok(indexes.into_iter().map(|x| x.index_name).collect())
     }
 }
@@ -10,7 +10,7 @@ use crate::error::*;
 use crate::proto::common::{KeyValuePair, MsgBase, MsgType};
 use crate::proto::milvus::{QueryCursor, QueryRequest};
 use crate::proto::schema::DataType;
-use crate::utils::status_to_result;
+use crate::utils::{quote_string, status_to_result};
 use crate::value::{Value, ValueVec};
 
 // Constants
@@ -738,9 +738,9 @@ impl QueryIterator {
 
         let pk_filter = if self.pk_is_string {
             format!(
-                "{} > \"{}\"",
+                "{} > {}",
                 self.pk_field_name.as_ref().unwrap(),
-                self.next_id.as_ref().unwrap()
+                quote_string(self.next_id.as_ref().unwrap())
             )
         } else {
             format!(
@@ -1380,6 +1380,7 @@ impl SearchIterator {
             });
         }
 
+        let started = std::time::Instant::now();
         let res = self
             .client
             .clone()
@@ -1508,6 +1509,11 @@ impl SearchIterator {
                 score,
                 field: result_data,
                 id,
+                stats: crate::collection::SearchStats {
+                    all_search_count: raw_data.all_search_count,
+                    recall: raw_data.recalls.get(result.len()).copied(),
+                    elapsed: started.elapsed(),
+                },
             });
 
             offset += k;
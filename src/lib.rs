@@ -14,16 +14,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "arrow")]
+pub mod arrow;
 pub mod authentication;
 pub mod client;
 pub mod collection;
 pub mod data;
 pub mod database;
+pub mod distance;
 pub mod error;
+pub mod export;
+pub mod expr;
 pub mod iterator;
 pub mod mutate;
 pub mod options;
 pub mod partition;
+#[cfg(feature = "polars")]
+pub mod polars;
 pub mod query;
 pub mod resource_group;
 pub mod schema;
@@ -1,26 +1,43 @@
+use std::future::Future;
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
 use crate::error::Result;
 use crate::{
     client::Client,
+    collection::{Collection, Error as CollectionError},
     data::FieldColumn,
     error::Error,
     proto::{
         self,
         common::{MsgBase, MsgType},
-        milvus::{InsertRequest, UpsertRequest},
-        schema::DataType,
+        milvus::{InsertRequest, MutationResult, UpsertRequest},
+        schema::{
+            DataType, FieldData, SparseFloatArray, VectorField, field_data::Field,
+            vector_field::Data as VectorData,
+        },
     },
-    value::ValueVec,
+    query::{IdType, PrimaryKeyType},
+    schema::{CollectionSchema, DYNAMIC_FIELD_NAME, Error as SchemaError, FieldSchema},
+    sparse::{sparse_normalize, sparse_row_to_bytes_into},
+    utils::{append_quoted_string, status_to_result},
+    value::{RowMap, Value, ValueVec},
 };
 
 #[derive(Debug, Clone)]
 pub struct InsertOptions {
     pub(crate) partition_name: String,
+    pub(crate) normalize_sparse: bool,
 }
 
 impl Default for InsertOptions {
     fn default() -> Self {
         Self {
             partition_name: String::new(),
+            normalize_sparse: false,
         }
     }
 }
@@ -38,6 +55,13 @@ impl InsertOptions {
         self.partition_name = partition_name.to_owned();
         self
     }
+
+    /// L2-normalize the values of every sparse vector before sending them,
+    /// see [`crate::sparse::sparse_normalize`].
+    pub fn normalize_sparse(mut self, normalize_sparse: bool) -> Self {
+        self.normalize_sparse = normalize_sparse;
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -45,6 +69,7 @@ pub struct DeleteOptions {
     pub(crate) ids: ValueVec,
     pub(crate) filter: String,
     pub(crate) partition_name: String,
+    pub(crate) primary_key: Option<(String, PrimaryKeyType)>,
 }
 
 impl DeleteOptions {
@@ -53,6 +78,7 @@ impl DeleteOptions {
             ids: ValueVec::None,
             filter: String::new(),
             partition_name: String::new(),
+            primary_key: None,
         }
     }
 
@@ -68,35 +94,290 @@ impl DeleteOptions {
         opt
     }
 
+    /// Scopes the delete to one partition, rows of other partitions matching
+    /// the ids or filter are kept. The partition must exist.
     pub fn partition_name(mut self, partition_name: String) -> Self {
         self.partition_name = partition_name;
         self
     }
+
+    /// Declares the primary key field, so the ids are formatted by `pk_type`
+    /// instead of looking the field up in the schema. The ids must be `Long`
+    /// for `Int64` keys and `String` for `VarChar` keys.
+    pub fn primary_key(mut self, field_name: impl Into<String>, pk_type: PrimaryKeyType) -> Self {
+        self.primary_key = Some((field_name.into(), pk_type));
+        self
+    }
 }
 
-/// Append a quoted string value to the expression, escaping special characters.
-fn append_quoted_string(expr: &mut String, value: &str) {
-    expr.push('"');
-    // Fast path: no escaping needed (common case for IDs)
-    if !value.contains('\\') && !value.contains('"') {
-        expr.push_str(value);
-    } else {
-        for c in value.chars() {
-            match c {
-                '\\' => expr.push_str("\\\\"),
-                '"' => expr.push_str("\\\""),
-                _ => expr.push(c),
+/// Outcome of [`Client::delete_by_ids`], summed over its delete requests.
+#[derive(Debug, Clone, Default)]
+pub struct DeleteByIdsResult {
+    pub delete_cnt: i64,
+    /// Number of delete requests sent, one per batch of ids.
+    pub batches: usize,
+    /// Timestamp of the last delete, to read the deletes back.
+    pub timestamp: u64,
+}
+
+/// Splits primary keys into batches of at most `batch_size`.
+fn split_ids(ids: IdType, batch_size: usize) -> Vec<IdType> {
+    match ids {
+        IdType::Int64(ids) => ids
+            .chunks(batch_size)
+            .map(|chunk| IdType::Int64(chunk.to_vec()))
+            .collect(),
+        IdType::VarChar(ids) => ids
+            .chunks(batch_size)
+            .map(|chunk| IdType::VarChar(chunk.to_vec()))
+            .collect(),
+    }
+}
+
+fn normalize_sparse_columns(fields_data: &mut [FieldColumn]) {
+    for column in fields_data {
+        if let ValueVec::SparseFloatVector(rows) = &mut column.value {
+            rows.iter_mut().for_each(sparse_normalize);
+        }
+    }
+}
+
+/// The field data of one column of an insert, built up from the chunks given
+/// to [`Client::insert_chunks`].
+///
+/// Sparse rows are encoded to their wire format as they arrive, so the parsed
+/// rows of a chunk are dropped with it.
+struct FieldDataBuilder {
+    /// Metadata and values of the chunks so far, no values for sparse columns.
+    column: FieldColumn,
+    /// Encoded rows of a sparse column.
+    sparse: Option<SparseFloatArray>,
+    num_rows: usize,
+}
+
+impl FieldDataBuilder {
+    fn new(column: &FieldColumn) -> Self {
+        Self {
+            column: column.copy_with_metadata(),
+            sparse: (column.dtype == DataType::SparseFloatVector).then(SparseFloatArray::default),
+            num_rows: 0,
+        }
+    }
+
+    /// Appends the rows of `chunk`, encoding sparse ones through `buf`.
+    fn append(&mut self, chunk: FieldColumn, buf: &mut Vec<u8>) -> Result<()> {
+        let chunk_rows = match chunk.valid_data.len() {
+            0 => chunk.len(),
+            n => n,
+        };
+        if chunk.dtype != self.column.dtype {
+            return Err(Error::InvalidParameter(
+                chunk.name,
+                format!(
+                    "chunk of type {:?}, the first chunk has {:?}",
+                    chunk.dtype, self.column.dtype
+                ),
+            ));
+        }
+
+        let appended = match (&mut self.sparse, chunk.value) {
+            (Some(sparse), ValueVec::SparseFloatVector(rows)) => {
+                // null rows are left out, the server places them by `valid_data`
+                let mut valid = chunk.valid_data.iter();
+                for mut row in rows {
+                    if !valid.next().copied().unwrap_or(true) {
+                        continue;
+                    }
+                    sparse_row_to_bytes_into(&mut row, buf);
+                    if let Some((max_idx, _)) = row.last() {
+                        sparse.dim = sparse.dim.max(*max_idx as i64 + 1);
+                    }
+                    sparse.contents.push(buf.as_slice().to_vec());
+                }
+                true
             }
+            (None, value) => append_values(&mut self.column.value, value),
+            (Some(_), _) => false,
+        };
+        if !appended {
+            return Err(Error::InvalidParameter(
+                chunk.name,
+                format!("chunk values do not match its type {:?}", chunk.dtype),
+            ));
+        }
+
+        if !chunk.valid_data.is_empty() || !self.column.valid_data.is_empty() {
+            if self.column.valid_data.is_empty() {
+                self.column.valid_data = vec![true; self.num_rows];
+            }
+            match chunk.valid_data.is_empty() {
+                true => self
+                    .column
+                    .valid_data
+                    .extend(std::iter::repeat_n(true, chunk_rows)),
+                false => self.column.valid_data.extend(chunk.valid_data),
+            }
+        }
+        self.num_rows += chunk_rows;
+        Ok(())
+    }
+
+    fn finish(self) -> FieldData {
+        let dim = self.column.dim;
+        let mut field_data: FieldData = self.column.into();
+        if let Some(sparse) = self.sparse {
+            field_data.field = Some(Field::Vectors(VectorField {
+                data: Some(VectorData::SparseFloatVector(sparse)),
+                dim,
+            }));
+        }
+        field_data
+    }
+}
+
+/// Appends `from` to `into`, false if they hold values of different types.
+fn append_values(into: &mut ValueVec, from: ValueVec) -> bool {
+    match (into, from) {
+        (_, ValueVec::None) => {}
+        (into @ ValueVec::None, from) => *into = from,
+        (ValueVec::Bool(into), ValueVec::Bool(from)) => into.extend(from),
+        (ValueVec::Int(into), ValueVec::Int(from)) => into.extend(from),
+        (ValueVec::Long(into), ValueVec::Long(from)) => into.extend(from),
+        (ValueVec::Float(into), ValueVec::Float(from)) => into.extend(from),
+        (ValueVec::Double(into), ValueVec::Double(from)) => into.extend(from),
+        (ValueVec::Binary(into), ValueVec::Binary(from)) => into.extend(from),
+        (ValueVec::String(into), ValueVec::String(from)) => into.extend(from),
+        (ValueVec::Json(into), ValueVec::Json(from)) => into.extend(from),
+        (ValueVec::Array(into), ValueVec::Array(from)) => into.extend(from),
+        (ValueVec::SparseFloatVector(into), ValueVec::SparseFloatVector(from)) => into.extend(from),
+        _ => return false,
+    }
+    true
+}
+
+/// Outcome of an insert that may have failed for some of its rows.
+///
+/// Row indexes refer to the rows of the inserted columns, so the failed ones can
+/// be picked out with [`InsertResult::failed_rows`] and retried.
+#[derive(Debug, Clone)]
+pub struct InsertResult {
+    /// Primary keys of the inserted rows.
+    pub ids: Option<proto::schema::IDs>,
+    pub insert_cnt: i64,
+    /// Indexes of the rows that were inserted.
+    pub succ_index: Vec<u32>,
+    /// Indexes of the rows the server rejected.
+    pub err_index: Vec<u32>,
+    pub timestamp: u64,
+}
+
+impl InsertResult {
+    /// Whether the server rejected any row.
+    pub fn has_failures(&self) -> bool {
+        !self.err_index.is_empty()
+    }
+
+    /// Selects the rejected rows of `columns`, the columns of the insert.
+    pub fn failed_rows(&self, columns: &[FieldColumn]) -> Vec<FieldColumn> {
+        columns
+            .iter()
+            .map(|column| {
+                let mut failed = column.copy_with_metadata();
+                for &i in &self.err_index {
+                    if let Some(value) = column.get(i as usize) {
+                        failed.push(value);
+                    }
+                }
+                failed
+            })
+            .collect()
+    }
+}
+
+impl From<MutationResult> for InsertResult {
+    fn from(result: MutationResult) -> Self {
+        Self {
+            ids: result.i_ds,
+            insert_cnt: result.insert_cnt,
+            succ_index: result.succ_index,
+            err_index: result.err_index,
+            timestamp: result.timestamp,
+        }
+    }
+}
+
+/// Checks that an insert carries a column for every required field, none for
+/// an auto id primary key or function output, and nothing the schema lacks.
+fn check_insert_columns(collection: &Collection, fields_data: &[FieldColumn]) -> Result<()> {
+    let provided = |name: &str| fields_data.iter().any(|c| c.name == name);
+    if let Some(pk) = collection
+        .fields
+        .iter()
+        .find(|f| f.is_primary_key && f.auto_id && provided(&f.name))
+    {
+        return Err(SchemaError::AutoIdProvided(pk.name.clone()).into());
+    }
+
+    let missing: Vec<String> = collection
+        .fields
+        .iter()
+        .filter(|f| f.is_required() && !provided(&f.name))
+        .map(|f| f.name.clone())
+        .collect();
+    let unexpected: Vec<String> = fields_data
+        .iter()
+        .filter(|c| {
+            c.name != DYNAMIC_FIELD_NAME
+                && !collection
+                    .fields
+                    .iter()
+                    .any(|f| f.name == c.name && !f.is_function_output)
+        })
+        .map(|c| c.name.clone())
+        .collect();
+
+    if missing.is_empty() && unexpected.is_empty() {
+        Ok(())
+    } else {
+        Err(SchemaError::ColumnsMismatch(collection.name.clone(), missing, unexpected).into())
+    }
+}
+
+/// Accepts a failed insert status only when the server names the failed rows.
+fn partial_insert_result(result: MutationResult) -> Result<InsertResult> {
+    if result.err_index.is_empty() {
+        status_to_result(&result.status)?;
+    }
+    Ok(result.into())
+}
+
+/// Servers that predate `upsert_cnt` only report the rows written as `insert_cnt`.
+fn fill_upsert_count(mut result: MutationResult) -> MutationResult {
+    if result.upsert_cnt == 0 {
+        result.upsert_cnt = result.insert_cnt;
+    }
+    result
+}
+
+/// Checks VarChar columns against the `max_length` of their field in the cached schema.
+fn check_varchar_max_length(collection: &Collection, fields_data: &[FieldColumn]) -> Result<()> {
+    for column in fields_data {
+        if column.dtype != DataType::VarChar {
+            continue;
+        }
+        if let Some(field) = collection.fields.iter().find(|f| f.name == column.name) {
+            column.check_max_length(field.max_length)?;
         }
     }
-    expr.push('"');
+
+    Ok(())
 }
 
 impl Client {
     pub async fn insert<S>(
         &self,
         collection_name: S,
-        fields_data: Vec<FieldColumn>,
+        mut fields_data: Vec<FieldColumn>,
         options: Option<InsertOptions>,
     ) -> Result<crate::proto::milvus::MutationResult>
     where
@@ -105,7 +386,110 @@ impl Client {
         let options = options.unwrap_or_default();
         let row_num = fields_data.first().map(|c| c.len()).unwrap_or(0);
         let collection_name = collection_name.into();
+        if options.normalize_sparse {
+            normalize_sparse_columns(&mut fields_data);
+        }
+        let collection = self.collection_cache.get(&collection_name).await?;
+        check_insert_columns(&collection, &fields_data)?;
+        check_varchar_max_length(&collection, &fields_data)?;
+
+        let fields_data = fields_data.into_iter().map(|f| f.into()).collect();
+        self.send_insert(
+            collection_name,
+            options.partition_name,
+            row_num,
+            fields_data,
+        )
+        .await
+    }
 
+    /// Inserts columns produced chunk by chunk, without collecting them first.
+    ///
+    /// Every chunk holds the same columns for the next rows, as would be passed
+    /// to [`Client::insert`]. Each is converted to the request's field data and
+    /// dropped before the next one is taken, sparse rows being encoded through
+    /// one reusable buffer, so only the encoded request is held in full. The
+    /// rows are inserted in one request, as if the chunks had been concatenated.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection.
+    /// * `chunks` - Column chunks, e.g. an iterator adapter over a reader.
+    /// * `options` - Insert options, applied to every chunk.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidParameter` if a chunk has other columns, or columns of
+    /// other types, than the first one.
+    pub async fn insert_chunks<S, I>(
+        &self,
+        collection_name: S,
+        chunks: I,
+        options: Option<InsertOptions>,
+    ) -> Result<MutationResult>
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = Vec<FieldColumn>>,
+    {
+        let options = options.unwrap_or_default();
+        let collection_name = collection_name.into();
+        let collection = self.collection_cache.get(&collection_name).await?;
+
+        let mut builders: Vec<FieldDataBuilder> = Vec::new();
+        let mut buf = Vec::new();
+        for (i, mut chunk) in chunks.into_iter().enumerate() {
+            if options.normalize_sparse {
+                normalize_sparse_columns(&mut chunk);
+            }
+            check_varchar_max_length(&collection, &chunk)?;
+            if i == 0 {
+                check_insert_columns(&collection, &chunk)?;
+                builders = chunk.iter().map(FieldDataBuilder::new).collect();
+            } else if chunk.len() != builders.len()
+                || chunk
+                    .iter()
+                    .any(|c| !builders.iter().any(|b| b.column.name == c.name))
+            {
+                let names = |names: Vec<&str>| names.join(", ");
+                return Err(Error::InvalidParameter(
+                    "chunks".to_owned(),
+                    format!(
+                        "chunk {} has columns [{}], the first chunk has [{}]",
+                        i,
+                        names(chunk.iter().map(|c| c.name.as_str()).collect()),
+                        names(builders.iter().map(|b| b.column.name.as_str()).collect()),
+                    ),
+                ));
+            }
+
+            for column in chunk {
+                if let Some(builder) = builders.iter_mut().find(|b| b.column.name == column.name) {
+                    builder.append(column, &mut buf)?;
+                }
+            }
+        }
+        if builders.is_empty() {
+            check_insert_columns(&collection, &[])?;
+        }
+
+        let row_num = builders.first().map(|b| b.num_rows).unwrap_or(0);
+        let fields_data = builders.into_iter().map(FieldDataBuilder::finish).collect();
+        self.send_insert(
+            collection_name,
+            options.partition_name,
+            row_num,
+            fields_data,
+        )
+        .await
+    }
+
+    async fn send_insert(
+        &self,
+        collection_name: String,
+        partition_name: String,
+        row_num: usize,
+        fields_data: Vec<FieldData>,
+    ) -> Result<MutationResult> {
         let result = self
             .client
             .clone()
@@ -113,9 +497,9 @@ impl Client {
                 base: Some(MsgBase::new(MsgType::Insert)),
                 db_name: "".to_string(),
                 collection_name: collection_name.clone(),
-                partition_name: options.partition_name,
+                partition_name,
                 num_rows: row_num as u32,
-                fields_data: fields_data.into_iter().map(|f| f.into()).collect(),
+                fields_data,
                 hash_keys: Vec::new(),
                 schema_timestamp: 0,
                 namespace: Some("".to_string()),
@@ -129,12 +513,62 @@ impl Client {
         Ok(result)
     }
 
+    /// Inserts columns like [`Client::insert`], reporting which rows failed.
+    ///
+    /// A server error that names the rejected rows is returned as an
+    /// [`InsertResult`] with `err_index` set rather than as an error, so only those
+    /// rows need to be retried. Errors affecting the whole batch are still returned
+    /// as errors.
+    pub async fn insert_partial(
+        &self,
+        collection_name: impl Into<String>,
+        fields_data: Vec<FieldColumn>,
+        options: Option<InsertOptions>,
+    ) -> Result<InsertResult> {
+        let result = self.insert(collection_name, fields_data, options).await?;
+        partial_insert_result(result)
+    }
+
+    /// Inserts rows given as maps from field name to value.
+    ///
+    /// The rows are pivoted into columns using the collection schema, which is
+    /// described on every call. When the collection has dynamic fields enabled,
+    /// keys the schema lacks are stored in the dynamic field and can be read back
+    /// with [`ToRows::get_dynamic`](crate::data::ToRows::get_dynamic), otherwise
+    /// they are rejected.
+    pub async fn insert_rows(
+        &self,
+        collection_name: impl Into<String>,
+        rows: Vec<RowMap>,
+        options: Option<InsertOptions>,
+    ) -> Result<MutationResult> {
+        let collection_name = collection_name.into();
+        let schema: CollectionSchema = self
+            .describe_collection(collection_name.clone())
+            .await?
+            .schema
+            .into();
+        let columns = rows_to_columns(&schema, rows)?;
+        self.insert(collection_name, columns, options).await
+    }
+
     pub async fn delete(
         &self,
         collection_name: impl Into<String>,
         options: &DeleteOptions,
     ) -> Result<crate::proto::milvus::MutationResult> {
         let collection_name = collection_name.into();
+        if !options.partition_name.is_empty()
+            && !self
+                .has_partition(collection_name.clone(), options.partition_name.clone())
+                .await?
+        {
+            return Err(CollectionError::PartitionNotFound(
+                collection_name,
+                options.partition_name.clone(),
+            )
+            .into());
+        }
 
         let expr = self.compose_expr(&collection_name, options).await?;
 
@@ -160,7 +594,66 @@ impl Client {
         Ok(result)
     }
 
+    /// Deletes rows by primary key, sending one delete request per
+    /// `batch_size` keys.
+    ///
+    /// A single `pk in [...]` expression holding millions of keys exceeds the
+    /// server's expression size limit, splitting keeps every request below it.
+    /// VarChar keys are quoted and escaped. The batches are sent one after the
+    /// other and the first failure stops the remaining ones, earlier batches
+    /// stay deleted.
+    pub async fn delete_by_ids(
+        &self,
+        collection_name: impl Into<String>,
+        ids: IdType,
+        batch_size: usize,
+    ) -> Result<DeleteByIdsResult> {
+        if batch_size == 0 {
+            return Err(Error::InvalidParameter(
+                "batch_size".to_owned(),
+                "0, must be at least 1".to_owned(),
+            ));
+        }
+
+        let collection_name = collection_name.into();
+        let mut total = DeleteByIdsResult::default();
+        for batch in split_ids(ids, batch_size) {
+            let ids = match batch {
+                IdType::Int64(ids) => ValueVec::Long(ids),
+                IdType::VarChar(ids) => ValueVec::String(ids),
+            };
+            let result = self
+                .delete(&collection_name, &DeleteOptions::with_ids(ids))
+                .await?;
+            status_to_result(&result.status)?;
+            total.delete_cnt += result.delete_cnt;
+            total.batches += 1;
+            total.timestamp = result.timestamp;
+        }
+
+        Ok(total)
+    }
+
     async fn compose_expr(&self, collection_name: &str, options: &DeleteOptions) -> Result<String> {
+        // A filter takes precedence over the ids, as below
+        let hint = options
+            .primary_key
+            .as_ref()
+            .filter(|_| options.filter.is_empty());
+        if let Some((name, pk_type)) = hint {
+            let ids = match &options.ids {
+                ValueVec::Long(ids) => IdType::Int64(ids.clone()),
+                ValueVec::String(ids) => IdType::VarChar(ids.clone()),
+                ids => {
+                    return Err(Error::InvalidParameter(
+                        "ids".to_owned(),
+                        format!("{ids:?} are not primary keys"),
+                    ));
+                }
+            };
+            return pk_type.ids_expr(name, &ids);
+        }
+
         let expr = match options.filter.len() {
             0 => {
                 let collection = self.collection_cache.get(collection_name).await?;
@@ -207,10 +700,14 @@ impl Client {
         Ok(expr)
     }
 
+    /// Inserts rows, replacing the existing rows with the same primary keys.
+    ///
+    /// The server does not tell updated rows apart from newly inserted ones, the
+    /// `upsert_cnt` of the result is the total number of rows written.
     pub async fn upsert<S>(
         &self,
         collection_name: S,
-        fields_data: Vec<FieldColumn>,
+        mut fields_data: Vec<FieldColumn>,
         options: Option<InsertOptions>,
     ) -> Result<crate::proto::milvus::MutationResult>
     where
@@ -219,6 +716,11 @@ impl Client {
         let options = options.unwrap_or_default();
         let row_num = fields_data.first().map(|c| c.len()).unwrap_or(0);
         let collection_name = collection_name.into();
+        if options.normalize_sparse {
+            normalize_sparse_columns(&mut fields_data);
+        }
+        let collection = self.collection_cache.get(&collection_name).await?;
+        check_varchar_max_length(&collection, &fields_data)?;
 
         let result = self
             .client
@@ -237,6 +739,8 @@ impl Client {
             })
             .await?
             .into_inner();
+        status_to_result(&result.status)?;
+        let result = fill_upsert_count(result);
 
         self.collection_cache
             .update_timestamp(&collection_name, result.timestamp);
@@ -244,3 +748,561 @@ impl Client {
         Ok(result)
     }
 }
+
+/// A bounded pipeline that batches rows and inserts them in the background.
+///
+/// Created by [`Client::insert_sink`]. A batch is inserted once it reaches the
+/// batch size, or once the flush interval has elapsed with rows pending.
+pub struct InsertSink {
+    sender: mpsc::Sender<RowMap>,
+    task: JoinHandle<Result<Vec<MutationResult>>>,
+}
+
+impl InsertSink {
+    /// Queues a row for insertion, waiting while the pipeline is full.
+    ///
+    /// Fails if the pipeline has stopped because an insert failed, the error
+    /// itself is returned by [`InsertSink::close`].
+    pub async fn send(&self, row: RowMap) -> Result<()> {
+        self.sender
+            .send(row)
+            .await
+            .map_err(|_| Error::Unexpected("insert sink is closed".to_owned()))
+    }
+
+    /// Inserts the remaining rows and stops the pipeline.
+    ///
+    /// Returns the result of every insert, in order, or the first insert error.
+    pub async fn close(self) -> Result<Vec<MutationResult>> {
+        drop(self.sender);
+        self.task
+            .await
+            .map_err(|err| Error::Unexpected(format!("insert sink task failed: {}", err)))?
+    }
+}
+
+impl Client {
+    /// Starts an [`InsertSink`] inserting rows into a collection.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection.
+    /// * `batch_size` - Number of rows per insert, also the number of rows that can be queued.
+    /// * `flush_interval` - Longest time pending rows wait before they are inserted.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the sink, or an error if the collection can not be described.
+    pub async fn insert_sink(
+        &self,
+        collection_name: impl Into<String>,
+        batch_size: usize,
+        flush_interval: Duration,
+    ) -> Result<InsertSink> {
+        let collection_name = collection_name.into();
+        let schema: CollectionSchema = self
+            .describe_collection(collection_name.clone())
+            .await?
+            .schema
+            .into();
+        let batch_size = batch_size.max(1);
+        let (sender, receiver) = mpsc::channel(batch_size);
+
+        let client = self.clone();
+        let task = tokio::spawn(run_batches(
+            receiver,
+            batch_size,
+            flush_interval,
+            move |rows| {
+                let client = client.clone();
+                let collection_name = collection_name.clone();
+                let columns = rows_to_columns(&schema, rows);
+                async move { client.insert(collection_name, columns?, None).await }
+            },
+        ));
+
+        Ok(InsertSink { sender, task })
+    }
+}
+
+/// Collects rows from `receiver` into batches and hands each batch to `flush`,
+/// when it is full or when `flush_interval` has elapsed since its first row.
+async fn run_batches<T, F, Fut>(
+    mut receiver: mpsc::Receiver<RowMap>,
+    batch_size: usize,
+    flush_interval: Duration,
+    mut flush: F,
+) -> Result<Vec<T>>
+where
+    F: FnMut(Vec<RowMap>) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut results = Vec::new();
+    let mut batch = Vec::with_capacity(batch_size);
+    let mut ticker =
+        tokio::time::interval_at(tokio::time::Instant::now() + flush_interval, flush_interval);
+    // no ticks are consumed while the batch is empty, don't let them pile up
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            row = receiver.recv() => match row {
+                Some(row) => {
+                    if batch.is_empty() {
+                        ticker.reset();
+                    }
+                    batch.push(row);
+                    if batch.len() >= batch_size {
+                        results.push(flush(std::mem::take(&mut batch)).await?);
+                    }
+                }
+                None => break,
+            },
+            _ = ticker.tick(), if !batch.is_empty() => {
+                results.push(flush(std::mem::take(&mut batch)).await?);
+            }
+        }
+    }
+
+    if !batch.is_empty() {
+        results.push(flush(batch).await?);
+    }
+
+    Ok(results)
+}
+
+/// Pivots rows into one column per field of the schema that takes user data.
+/// A nullable field missing from a row is inserted as null for that row.
+///
+/// With dynamic fields enabled, keys the schema lacks are packed into a JSON
+/// object per row and sent as the `$meta` column, otherwise they are rejected.
+pub(crate) fn rows_to_columns(
+    schema: &CollectionSchema,
+    rows: Vec<RowMap>,
+) -> Result<Vec<FieldColumn>> {
+    let fields: Vec<&FieldSchema> = schema
+        .fields
+        .iter()
+        .filter(|f| !f.auto_id && !f.is_function_output && f.name != DYNAMIC_FIELD_NAME)
+        .collect();
+    let mut columns: Vec<FieldColumn> = fields
+        .iter()
+        .map(|f| FieldColumn::new(f, ValueVec::new(f.dtype)))
+        .collect();
+    let mut dynamic = Vec::new();
+
+    for mut row in rows {
+        for (column, field) in columns.iter_mut().zip(&fields) {
+            // nullable fields may be left out of a row, they are inserted as null
+            let value = match row.remove(&column.name) {
+                Some(value) => value,
+                None if field.nullable => Value::None,
+                None => return Err(SchemaError::FieldDoesNotExists(column.name.clone()).into()),
+            };
+            let dtype = value.data_type();
+            if dtype != column.dtype
+                && !(dtype == DataType::String && column.dtype == DataType::VarChar)
+                && !(dtype == DataType::None && field.nullable)
+                // 16-bit float vectors may be given as their raw bytes
+                && !(dtype == DataType::BinaryVector
+                    && matches!(
+                        column.dtype,
+                        DataType::Float16Vector | DataType::BFloat16Vector
+                    ))
+            {
+                return Err(
+                    SchemaError::FieldWrongType(column.name.clone(), column.dtype, dtype).into(),
+                );
+            }
+            column.push(value);
+        }
+
+        if !schema.enable_dynamic_field {
+            if let Some(name) = row.into_keys().next() {
+                return Err(SchemaError::FieldDoesNotExists(name).into());
+            }
+            continue;
+        }
+        let mut extra = serde_json::Map::new();
+        for (name, value) in row {
+            let json = value.to_json().ok_or_else(|| {
+                SchemaError::FieldWrongType(name.clone(), DataType::Json, value.data_type())
+            })?;
+            extra.insert(name, json);
+        }
+        dynamic.push(serde_json::to_vec(&extra)?);
+    }
+
+    if schema.enable_dynamic_field {
+        columns.push(FieldColumn {
+            name: DYNAMIC_FIELD_NAME.to_owned(),
+            dtype: DataType::Json,
+            value: ValueVec::Json(dynamic),
+            dim: 0,
+            max_length: 0,
+            is_dynamic: true,
+            valid_data: Vec::new(),
+        });
+    }
+
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::FieldSchema;
+    use crate::types::fixtures::{collection, field};
+    use crate::value::Value;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_split_ids() {
+        let batches = split_ids(IdType::Int64((0..5000).collect()), 1000);
+        assert_eq!(batches.len(), 5);
+        for (i, batch) in batches.iter().enumerate() {
+            let IdType::Int64(ids) = batch else {
+                panic!("expected int64 ids");
+            };
+            assert_eq!(ids.len(), 1000);
+            assert_eq!(ids[0], i as i64 * 1000);
+        }
+
+        let batches = split_ids(
+            IdType::VarChar(vec!["a".to_owned(), "b".to_owned(), "c".to_owned()]),
+            2,
+        );
+        let sizes: Vec<usize> = batches
+            .iter()
+            .map(|batch| match batch {
+                IdType::VarChar(ids) => ids.len(),
+                IdType::Int64(_) => panic!("expected varchar ids"),
+            })
+            .collect();
+        assert_eq!(sizes, vec![2, 1]);
+
+        assert!(split_ids(IdType::Int64(Vec::new()), 10).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_run_batches() {
+        let (sender, receiver) = mpsc::channel(100);
+        let flushed = Arc::new(Mutex::new(Vec::new()));
+
+        let sizes = flushed.clone();
+        let task = tokio::spawn(run_batches(
+            receiver,
+            100,
+            Duration::from_secs(3600),
+            move |rows| {
+                sizes.lock().unwrap().push(rows.len());
+                async move { Ok(rows) }
+            },
+        ));
+
+        for i in 0..250i64 {
+            let row = RowMap::from([("id".to_owned(), Value::Long(i))]);
+            sender.send(row).await.unwrap();
+        }
+        drop(sender);
+        let batches = task.await.unwrap().unwrap();
+
+        assert_eq!(*flushed.lock().unwrap(), vec![100, 100, 50]);
+        let ids: Vec<i64> = batches
+            .iter()
+            .flatten()
+            .map(|row| match row["id"] {
+                Value::Long(id) => id,
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(ids, (0..250).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_run_batches_flush_interval() {
+        let (sender, receiver) = mpsc::channel(100);
+        let task = tokio::spawn(run_batches(
+            receiver,
+            100,
+            Duration::from_millis(20),
+            |rows| async move { Ok(rows.len()) },
+        ));
+
+        sender
+            .send(RowMap::from([("id".to_owned(), Value::Long(1))]))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        sender
+            .send(RowMap::from([("id".to_owned(), Value::Long(2))]))
+            .await
+            .unwrap();
+        drop(sender);
+
+        assert_eq!(task.await.unwrap().unwrap(), vec![1, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_run_batches_after_idle() {
+        let (sender, receiver) = mpsc::channel(100);
+        let task = tokio::spawn(run_batches(
+            receiver,
+            100,
+            Duration::from_millis(20),
+            |rows| async move { Ok(rows.len()) },
+        ));
+
+        // the ticks missed while idle must not flush the next rows one by one
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        for i in 0..3i64 {
+            sender
+                .send(RowMap::from([("id".to_owned(), Value::Long(i))]))
+                .await
+                .unwrap();
+        }
+        drop(sender);
+
+        assert_eq!(task.await.unwrap().unwrap(), vec![3]);
+    }
+
+    #[test]
+    fn test_rows_to_columns_dynamic_fields() {
+        use crate::data::ToRows;
+        use crate::schema::CollectionSchemaBuilder;
+
+        let row = || {
+            RowMap::from([
+                ("id".to_owned(), Value::Long(1)),
+                ("color".to_owned(), Value::from("red".to_owned())),
+                ("score".to_owned(), Value::Int32(7)),
+            ])
+        };
+        let mut builder = CollectionSchemaBuilder::new("books", "");
+        builder.add_field(FieldSchema::new_primary_int64("id", "", false));
+
+        let strict = builder.build().unwrap();
+        assert!(matches!(
+            rows_to_columns(&strict, vec![row()]),
+            Err(Error::Schema(SchemaError::FieldDoesNotExists(_)))
+        ));
+
+        let dynamic = builder
+            .add_field(FieldSchema::new_primary_int64("id", "", false))
+            .enable_dynamic_field()
+            .build()
+            .unwrap();
+        let columns = rows_to_columns(&dynamic, vec![row()]).unwrap();
+        assert_eq!(columns.len(), 2);
+        assert!(columns[1].is_dynamic && columns[1].name == DYNAMIC_FIELD_NAME);
+        assert_eq!(columns.get_dynamic(0, "color"), Some("red".into()));
+        assert_eq!(columns.get_dynamic(0, "score"), Some(7.into()));
+        assert_eq!(columns.get_dynamic(0, "id"), None);
+    }
+
+    #[test]
+    fn test_rows_to_columns_missing_nullable_field() {
+        use crate::schema::CollectionSchemaBuilder;
+
+        let schema = CollectionSchemaBuilder::new("books", "")
+            .add_field(FieldSchema::new_primary_int64("id", "", false))
+            .add_field(FieldSchema::new_int64("price", "").with_nullable())
+            .build()
+            .unwrap();
+        let rows = vec![
+            RowMap::from([
+                ("id".to_owned(), Value::Long(1)),
+                ("price".to_owned(), Value::Long(10)),
+            ]),
+            RowMap::from([("id".to_owned(), Value::Long(2))]),
+        ];
+
+        let columns = rows_to_columns(&schema, rows).unwrap();
+        assert_eq!(columns[1].len(), 2);
+        assert_eq!(columns[1].valid_data, vec![true, false]);
+        assert!(matches!(columns[1].get(1), Some(Value::None)));
+
+        let missing_id = vec![RowMap::from([("price".to_owned(), Value::Long(10))])];
+        assert!(matches!(
+            rows_to_columns(&schema, missing_id),
+            Err(Error::Schema(SchemaError::FieldDoesNotExists(_)))
+        ));
+    }
+
+    #[test]
+    fn test_partial_insert_result() {
+        use crate::proto::common::{ErrorCode, Status};
+
+        let status = |code: i32| {
+            Some(Status {
+                code,
+                reason: "row 1 violates max_length".to_owned(),
+                ..Default::default()
+            })
+        };
+        let result = partial_insert_result(MutationResult {
+            status: status(65535),
+            succ_index: vec![0, 2],
+            err_index: vec![1, 3],
+            insert_cnt: 2,
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(result.has_failures());
+        assert_eq!(result.succ_index, vec![0, 2]);
+        assert_eq!(result.err_index, vec![1, 3]);
+
+        let columns = vec![
+            FieldColumn::new(&FieldSchema::new_int64("id", ""), vec![10i64, 11, 12, 13]),
+            FieldColumn::new(
+                &FieldSchema::new_varchar("title", "", 8),
+                vec!["a", "bbbbbbbbbb", "c", "dddddddddd"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect::<Vec<_>>(),
+            ),
+        ];
+        let failed = result.failed_rows(&columns);
+        assert!(matches!(&failed[0].value, ValueVec::Long(ids) if ids == &[11, 13]));
+        assert_eq!(failed[1].len(), 2);
+        assert!(matches!(failed[1].get(1), Some(Value::String(s)) if s == "dddddddddd"));
+
+        let ok = partial_insert_result(MutationResult {
+            status: status(0),
+            succ_index: vec![0, 1],
+            insert_cnt: 2,
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(!ok.has_failures());
+
+        assert!(matches!(
+            partial_insert_result(MutationResult {
+                status: status(65535),
+                ..Default::default()
+            }),
+            Err(Error::Server(ErrorCode::UnexpectedError, _))
+        ));
+    }
+
+    #[test]
+    fn test_fill_upsert_count() {
+        let result = fill_upsert_count(MutationResult {
+            upsert_cnt: 10,
+            insert_cnt: 10,
+            delete_cnt: 10,
+            ..Default::default()
+        });
+        assert_eq!(result.upsert_cnt, 10);
+
+        let legacy = fill_upsert_count(MutationResult {
+            insert_cnt: 7,
+            ..Default::default()
+        });
+        assert_eq!(legacy.upsert_cnt, 7);
+    }
+
+    #[test]
+    fn test_field_data_builder_matches_whole_columns() {
+        let id = FieldSchema::new_int64("id", "");
+        let dense = FieldSchema::new_float_vector("dense", "", 2);
+        let sparse = FieldSchema::new_sparse_float_vector("sparse", "").with_nullable();
+        let columns = |ids: Vec<i64>, vectors: Vec<f32>, rows: Vec<Option<Vec<(u32, f32)>>>| {
+            vec![
+                FieldColumn::new(&id, ids),
+                FieldColumn::new(&dense, vectors),
+                FieldColumn::new_nullable_sparse(&sparse, rows),
+            ]
+        };
+
+        let whole: Vec<FieldData> = columns(
+            vec![1, 2, 3],
+            vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6],
+            vec![Some(vec![(4, 1.0), (1, 0.5)]), None, Some(vec![(9, 0.25)])],
+        )
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+        let chunks = vec![
+            columns(
+                vec![1],
+                vec![0.1, 0.2],
+                vec![Some(vec![(4, 1.0), (1, 0.5)])],
+            ),
+            columns(
+                vec![2, 3],
+                vec![0.3, 0.4, 0.5, 0.6],
+                vec![None, Some(vec![(9, 0.25)])],
+            ),
+        ];
+        let mut builders: Vec<FieldDataBuilder> =
+            chunks[0].iter().map(FieldDataBuilder::new).collect();
+        let mut buf = Vec::new();
+        for chunk in chunks {
+            for (builder, column) in builders.iter_mut().zip(chunk) {
+                builder.append(column, &mut buf).unwrap();
+            }
+        }
+        assert!(builders.iter().all(|b| b.num_rows == 3));
+        let chunked: Vec<FieldData> = builders.into_iter().map(FieldDataBuilder::finish).collect();
+        assert_eq!(chunked, whole);
+
+        let mut builder = FieldDataBuilder::new(&FieldColumn::new(&id, vec![1i64]));
+        let err = builder
+            .append(FieldColumn::new(&dense, vec![0.1f32, 0.2]), &mut buf)
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidParameter(name, _) if name == "dense"));
+    }
+
+    #[test]
+    fn test_check_insert_columns() {
+        let mut id = field("id", DataType::Int64);
+        id.is_primary_key = true;
+        id.auto_id = true;
+        let mut note = field("note", DataType::VarChar);
+        note.nullable = true;
+        let collection = collection(vec![
+            id,
+            field("title", DataType::VarChar),
+            field("dense", DataType::FloatVector),
+            note,
+        ]);
+        let column = |name: &str, dtype| {
+            FieldColumn::new(&FieldSchema::new_int64(name, ""), ValueVec::new(dtype))
+        };
+
+        let valid = vec![
+            column("title", DataType::VarChar),
+            column("dense", DataType::FloatVector),
+        ];
+        assert!(check_insert_columns(&collection, &valid).is_ok());
+
+        let mut with_pk = valid.clone();
+        with_pk.insert(0, column("id", DataType::Int64));
+        let err = check_insert_columns(&collection, &with_pk).unwrap_err();
+        assert!(matches!(
+            &err,
+            Error::Schema(SchemaError::AutoIdProvided(name)) if name == "id"
+        ));
+        let Error::Schema(err) = err else {
+            unreachable!()
+        };
+        assert_eq!(
+            err.to_string(),
+            "primary key \"id\" is auto id, the server generates it so it must not be inserted"
+        );
+
+        let mismatched = vec![
+            column("dense", DataType::FloatVector),
+            column("author", DataType::VarChar),
+        ];
+        let Err(Error::Schema(err)) = check_insert_columns(&collection, &mismatched) else {
+            panic!("expected a columns mismatch");
+        };
+        assert_eq!(
+            err.to_string(),
+            "columns do not match the schema of \"books\", missing: [\"title\"], unexpected: [\"author\"]"
+        );
+    }
+}
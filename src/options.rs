@@ -28,6 +28,8 @@ impl CreateCollectionOptions {
         Self::default().consistency_level(consistency_level)
     }
 
+    /// Number of shards (vchannels) the collection's writes are spread over,
+    /// from 1 to 16 on a default server. 0, the default, lets the server choose.
     pub fn shard_num(mut self, shard_num: i32) -> Self {
         self.shard_num = shard_num;
         self
@@ -102,6 +104,27 @@ impl LoadOptions {
     }
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct GetStatsOptions {
+    pub(crate) partition_names: Vec<String>,
+}
+
+impl GetStatsOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_partition_names(partition_names: Vec<String>) -> Self {
+        Self::default().partition_names(partition_names)
+    }
+
+    /// Scopes the statistics to these partitions, the whole collection when empty.
+    pub fn partition_names(mut self, partition_names: Vec<String>) -> Self {
+        self.partition_names = partition_names;
+        self
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct GetLoadStateOptions {
     pub(crate) partition_names: Vec<String>,
@@ -64,6 +64,35 @@ impl Client {
         ))
     }
 
+    /// Creates a partition unless it already exists.
+    ///
+    /// Unlike checking [`Client::has_partition`] first, this is safe when several
+    /// clients create the same partition concurrently: the server's "already
+    /// exists" error is reported as `false`.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection where the partition will be created.
+    /// * `partition_name` - The name of the partition to be created.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing `true` if the partition was created, or `false` if it already existed.
+    pub async fn create_partition_if_not_exists(
+        &self,
+        collection_name: impl Into<String>,
+        partition_name: impl Into<String>,
+    ) -> Result<bool> {
+        match self
+            .create_partition(collection_name.into(), partition_name.into())
+            .await
+        {
+            Ok(()) => Ok(true),
+            Err(Error::Server(_, reason)) if is_already_exists(&reason) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
     /// Drops a partition from the specified collection.
     ///
     /// # Arguments
@@ -181,26 +210,26 @@ impl Client {
         Ok(res.stats.into_iter().map(|s| (s.key, s.value)).collect())
     }
 
-    /// Gets the loading progress for specified partitions.
+    /// Gets the loading progress of a collection, or of some of its partitions.
     ///
     /// # Arguments
     ///
     /// * `collection_name` - The name of the collection.
-    /// * `partition_names` - An iterator of partition names to check progress for.
+    /// * `partition_names` - Partitions to check progress for, the whole
+    ///   collection if `None`.
     ///
     /// # Returns
     ///
-    /// Returns a `Result` containing the loading progress percentage (0-100).
-    async fn get_loading_progress<'a, S, I>(
+    /// Returns a `Result` containing the loading progress percentage (0-100),
+    /// 0 for partitions that are not being loaded.
+    pub async fn get_loading_progress<S>(
         &self,
         collection_name: S,
-        partition_names: I,
+        partition_names: Option<Vec<String>>,
     ) -> Result<i64>
     where
         S: Into<String>,
-        I: IntoIterator<Item = &'a String>,
     {
-        let partition_names: Vec<String> = partition_names.into_iter().map(|x| x.into()).collect();
         let resp = self
             .client
             .clone()
@@ -208,7 +237,7 @@ impl Client {
                 base: Some(MsgBase::new(MsgType::LoadPartitions)),
                 db_name: "".to_string(),
                 collection_name: collection_name.into(),
-                partition_names: partition_names,
+                partition_names: partition_names.unwrap_or_default(),
             })
             .await?
             .into_inner();
@@ -263,7 +292,11 @@ impl Client {
         ))?;
 
         loop {
-            if self.get_loading_progress(collection_name, &names).await? >= 100 {
+            if self
+                .get_loading_progress(collection_name, Some(names.clone()))
+                .await?
+                >= 100
+            {
                 return Ok(());
             }
 
@@ -305,3 +338,8 @@ impl Client {
         ))
     }
 }
+
+/// Whether a server error reason says the object being created already exists.
+fn is_already_exists(reason: &str) -> bool {
+    reason.to_ascii_lowercase().contains("already exist")
+}
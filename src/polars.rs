@@ -0,0 +1,223 @@
+// Licensed to the LF AI & Data foundation under one
+// or more contributor license agreements. See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership. The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversion of query results to a Polars DataFrame, enabled by the `polars`
+//! feature.
+//!
+//! Each [`FieldColumn`] becomes a column with the same name, except sparse
+//! vectors which become two:
+//!
+//! | Milvus type | Polars type |
+//! |---|---|
+//! | `Bool` | `Boolean` |
+//! | `Int8` / `Int16` / `Int32` / `Int64` | `Int8` / `Int16` / `Int32` / `Int64` |
+//! | `Float` / `Double` | `Float32` / `Float64` |
+//! | `VarChar` / `String` | `String` |
+//! | `Json` | `String`, holding the JSON text |
+//! | `FloatVector` | `List<Float32>` of `dim` items |
+//! | `BinaryVector` / `Float16Vector` / `BFloat16Vector` | `Binary`, the raw bytes of each row |
+//! | `SparseFloatVector` | `{name}_indices: List<UInt32>` and `{name}_values: List<Float32>` |
+//!
+//! The two sparse columns are aligned, the `i`-th value of a row belongs to the
+//! `i`-th index. `Array` columns are not supported.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use milvus::polars::ToDataFrame;
+//!
+//! let results = client.query("my_collection", "id > 0", &options).await?;
+//! let df = results.to_dataframe()?;
+//! ```
+
+use polars::prelude::{
+    Column, DataFrame, DataType as PolarsDataType, Float32Type, IntoSeries, ListBuilderTrait,
+    ListPrimitiveChunkedBuilder, NamedFrom, PlSmallStr, Series, UInt32Type,
+};
+
+use crate::data::FieldColumn;
+use crate::error::{Error, Result};
+use crate::proto::schema::DataType;
+use crate::value::ValueVec;
+
+/// Converts a set of columns into a Polars [`DataFrame`].
+pub trait ToDataFrame {
+    /// Builds a data frame with one column per field, two for sparse vectors,
+    /// see the [module docs](self) for the type mapping.
+    fn to_dataframe(&self) -> Result<DataFrame>;
+}
+
+impl ToDataFrame for [FieldColumn] {
+    fn to_dataframe(&self) -> Result<DataFrame> {
+        let num_rows = self.iter().map(FieldColumn::len).max().unwrap_or(0);
+        let mut columns = Vec::with_capacity(self.len());
+        for column in self {
+            columns.extend(
+                column_to_series(column, num_rows)?
+                    .into_iter()
+                    .map(Column::from),
+            );
+        }
+
+        Ok(DataFrame::new(num_rows, columns)?)
+    }
+}
+
+fn column_to_series(column: &FieldColumn, num_rows: usize) -> Result<Vec<Series>> {
+    let unsupported = || {
+        Error::Unexpected(format!(
+            "field {} of type {:?} can not be converted to polars",
+            column.name, column.dtype
+        ))
+    };
+    let name = PlSmallStr::from(column.name.as_str());
+
+    let series = match (&column.value, column.dtype) {
+        (ValueVec::None, _) => Series::full_null(name, num_rows, &PolarsDataType::Null),
+        (ValueVec::Bool(v), _) => Series::new(name, v),
+        (ValueVec::Int(v), DataType::Int8) => {
+            Series::new(name, v.iter().map(|&x| x as i8).collect::<Vec<_>>())
+        }
+        (ValueVec::Int(v), DataType::Int16) => {
+            Series::new(name, v.iter().map(|&x| x as i16).collect::<Vec<_>>())
+        }
+        (ValueVec::Int(v), _) => Series::new(name, v),
+        (ValueVec::Long(v), _) => Series::new(name, v),
+        (ValueVec::Float(v), DataType::FloatVector) => {
+            let dim = (column.dim as usize).max(1);
+            let mut builder = ListPrimitiveChunkedBuilder::<Float32Type>::new(
+                name,
+                v.len() / dim,
+                v.len(),
+                PolarsDataType::Float32,
+            );
+            for row in v.chunks_exact(dim) {
+                builder.append_slice(row);
+            }
+            builder.finish().into_series()
+        }
+        (ValueVec::Float(v), _) => Series::new(name, v),
+        (ValueVec::Double(v), _) => Series::new(name, v),
+        (ValueVec::String(v), _) => Series::new(name, v),
+        (ValueVec::Json(v), _) => Series::new(
+            name,
+            v.iter()
+                .map(|json| String::from_utf8_lossy(json))
+                .collect::<Vec<_>>(),
+        ),
+        (ValueVec::Binary(v), dtype) => {
+            let width = match dtype {
+                DataType::BinaryVector => column.dim / 8,
+                DataType::Float16Vector | DataType::BFloat16Vector => column.dim * 2,
+                _ => return Err(unsupported()),
+            };
+            let rows: Vec<&[u8]> = v.chunks_exact((width as usize).max(1)).collect();
+            Series::new(name, rows)
+        }
+        (ValueVec::SparseFloatVector(v), _) => {
+            let nnz = v.iter().map(Vec::len).sum();
+            let mut indices = ListPrimitiveChunkedBuilder::<UInt32Type>::new(
+                format!("{}_indices", column.name).into(),
+                v.len(),
+                nnz,
+                PolarsDataType::UInt32,
+            );
+            let mut values = ListPrimitiveChunkedBuilder::<Float32Type>::new(
+                format!("{}_values", column.name).into(),
+                v.len(),
+                nnz,
+                PolarsDataType::Float32,
+            );
+            for row in v {
+                let (row_indices, row_values): (Vec<u32>, Vec<f32>) = row.iter().copied().unzip();
+                indices.append_slice(&row_indices);
+                values.append_slice(&row_values);
+            }
+            return Ok(vec![
+                indices.finish().into_series(),
+                values.finish().into_series(),
+            ]);
+        }
+        (ValueVec::Array(_), _) => return Err(unsupported()),
+    };
+
+    Ok(vec![series])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::FieldSchema;
+
+    #[test]
+    fn test_to_dataframe() {
+        let columns = [
+            FieldColumn::new(
+                &FieldSchema::new_primary_int64("id", "", false),
+                vec![1i64, 2],
+            ),
+            FieldColumn::new(
+                &FieldSchema::new_varchar("title", "", 64),
+                vec!["a".to_string(), "b".to_string()],
+            ),
+            FieldColumn::new(
+                &FieldSchema::new_float_vector("dense", "", 2),
+                vec![0.1f32, 0.2, 0.3, 0.4],
+            ),
+            FieldColumn::new(
+                &FieldSchema::new_sparse_float_vector("sparse", ""),
+                vec![vec![(3u32, 0.5f32)], vec![(1, 0.25), (7, 1.0)]],
+            ),
+        ];
+
+        let df = columns.to_dataframe().unwrap();
+
+        assert_eq!(df.height(), 2);
+        let names: Vec<&str> = df.get_column_names().iter().map(|n| n.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["id", "title", "dense", "sparse_indices", "sparse_values"]
+        );
+        let dtypes = df.dtypes();
+        assert_eq!(dtypes[0], PolarsDataType::Int64);
+        assert_eq!(dtypes[1], PolarsDataType::String);
+        assert_eq!(
+            dtypes[2],
+            PolarsDataType::List(Box::new(PolarsDataType::Float32))
+        );
+        assert_eq!(
+            dtypes[3],
+            PolarsDataType::List(Box::new(PolarsDataType::UInt32))
+        );
+        assert_eq!(
+            dtypes[4],
+            PolarsDataType::List(Box::new(PolarsDataType::Float32))
+        );
+
+        let indices = df.column("sparse_indices").unwrap().list().unwrap();
+        let second = indices.get_as_series(1).unwrap();
+        assert_eq!(second.u32().unwrap().to_vec(), vec![Some(1), Some(7)]);
+    }
+
+    #[test]
+    fn test_to_dataframe_unsupported() {
+        let columns = [FieldColumn::new(
+            &FieldSchema::new_int64("array", ""),
+            ValueVec::Array(Vec::new()),
+        )];
+        assert!(columns.to_dataframe().is_err());
+    }
+}
@@ -34,24 +34,36 @@
 //! ```
 
 use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use prost::Message;
 use prost::bytes::BytesMut;
 
 use crate::client::{Client, ConsistencyLevel};
-use crate::collection::{Collection, SearchResult};
-use crate::data::FieldColumn;
+use crate::collection::{Collection, Hit, SearchResult, SearchStats};
+use crate::data::{FieldColumn, ToRows};
 use crate::error::Error as SuperError;
+use crate::index::{IndexConfig, MetricType};
 use crate::proto::common::{
     DslType, KeyValuePair, MsgBase, MsgType, PlaceholderGroup, PlaceholderType, PlaceholderValue,
 };
-use crate::proto::milvus::{QueryRequest, SearchRequest};
-use crate::proto::schema::DataType;
+use crate::proto::milvus::QueryRequest;
+use crate::proto::schema::{DataType, FunctionType};
+use crate::schema::CollectionSchema;
+use crate::sparse::{SparseVector, sparse_normalize, sparse_vectors_to_proto};
 use crate::types::Field;
-use crate::utils::status_to_result;
-use crate::value::Value;
+use crate::utils::{append_quoted_string, normalize_l2, quote_string, status_to_result};
+use crate::value::{RowMap, Value, ValueVec};
 use crate::{error::*, proto};
 
+/// Output field selecting every field of the collection, see
+/// [`SearchOptions::output_all_fields`]
+pub const ALL_FIELDS: &str = "*";
+
+/// Output field of a query returning the number of matching entities
+const COUNT_FIELD: &str = "count(*)";
+
 /// Timestamp value for Strong consistency level
 /// Ensures that all operations are performed with the latest data
 const STRONG_TIMESTAMP: u64 = 0;
@@ -64,6 +76,73 @@ const BOUNDED_TIMESTAMP: u64 = 2;
 /// Provides eventual consistency guarantees
 const EVENTUALLY_TIMESTAMP: u64 = 1;
 
+/// Default `k` of the RRF ranker, as used by the server
+const DEFAULT_RRF_K: f64 = 60.0;
+
+/// Range of `k` accepted by the RRF ranker
+const RRF_K_MIN: f64 = 1.0;
+const RRF_K_MAX: f64 = 16384.0;
+
+/// Number of low bits of a hybrid timestamp that hold the logical counter,
+/// the physical time in milliseconds is stored above them
+const LOGICAL_BITS: u32 = 18;
+
+/// A query vector for any of the supported vector field types
+///
+/// Converts into the [`Value`] taken by the search methods, so one search
+/// call can be used regardless of the type of the searched field.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use milvus::query::QueryVector;
+///
+/// let dense = QueryVector::FloatVector(vec![0.1, 0.2, 0.3]);
+/// let sparse = QueryVector::SparseVector(vec![(5, 0.3), (42, 1.2)]);
+///
+/// let results = client.search("my_collection", vec![dense.into()], None).await?;
+/// ```
+#[derive(Debug, Clone)]
+pub enum QueryVector {
+    /// Dense vector of 32-bit floats
+    FloatVector(Vec<f32>),
+    /// Binary vector, packed 8 dimensions per byte
+    BinaryVector(Vec<u8>),
+    /// Sparse vector as (index, value) pairs
+    SparseVector(SparseVector),
+    /// Half-precision vector as little-endian IEEE 754 binary16 bytes, 2 bytes per dimension
+    Float16Vector(Vec<u8>),
+}
+
+impl From<QueryVector> for Value<'static> {
+    fn from(v: QueryVector) -> Self {
+        match v {
+            QueryVector::FloatVector(v) => Value::FloatArray(v.into()),
+            QueryVector::BinaryVector(v) => Value::Binary(v.into()),
+            QueryVector::SparseVector(v) => Value::SparseFloatVector(v.into()),
+            QueryVector::Float16Vector(v) => Value::Float16Array(v.into()),
+        }
+    }
+}
+
+impl From<Vec<f32>> for QueryVector {
+    fn from(v: Vec<f32>) -> Self {
+        QueryVector::FloatVector(v)
+    }
+}
+
+impl From<Vec<u8>> for QueryVector {
+    fn from(v: Vec<u8>) -> Self {
+        QueryVector::BinaryVector(v)
+    }
+}
+
+impl From<SparseVector> for QueryVector {
+    fn from(v: SparseVector) -> Self {
+        QueryVector::SparseVector(v)
+    }
+}
+
 /// Represents an ANN (Approximate Nearest Neighbor) search request
 ///
 /// This struct encapsulates all the parameters needed for a single vector search operation
@@ -265,6 +344,12 @@ pub trait BaseRanker: Send + Sync {
     ///
     /// A vector of key-value pairs representing the ranker's configuration
     fn get_params(&self) -> Vec<KeyValuePair>;
+
+    /// Checks the ranker can combine `num_requests` sub-requests, called by
+    /// `hybrid_search` before the request is sent
+    fn validate(&self, _num_requests: usize) -> Result<()> {
+        Ok(())
+    }
 }
 
 /// Weighted ranker for hybrid search
@@ -316,6 +401,26 @@ impl BaseRanker for WeightedRanker {
 
         params
     }
+
+    fn validate(&self, num_requests: usize) -> Result<()> {
+        if self.weights.len() != num_requests {
+            return Err(SuperError::InvalidParameter(
+                "weights".to_owned(),
+                format!(
+                    "WeightedRanker has {} weights but hybrid search has {} sub-requests",
+                    self.weights.len(),
+                    num_requests
+                ),
+            ));
+        }
+        if let Some(weight) = self.weights.iter().find(|w| w.is_nan() || **w < 0.0) {
+            return Err(SuperError::InvalidParameter(
+                "weights".to_owned(),
+                format!("WeightedRanker weights must be non-negative, got {weight}"),
+            ));
+        }
+        Ok(())
+    }
 }
 
 /// RRF (Reciprocal Rank Fusion) ranker for hybrid search
@@ -349,6 +454,31 @@ impl RrfRanker {
     pub fn new(k: f64) -> Self {
         Self { k }
     }
+
+    /// Creates a new RRF ranker, checking `k` is within the range the server
+    /// accepts, `1..=16384`
+    pub fn try_new(k: f64) -> Result<Self> {
+        let ranker = Self { k };
+        ranker.check_k()?;
+        Ok(ranker)
+    }
+
+    fn check_k(&self) -> Result<()> {
+        if !(RRF_K_MIN..=RRF_K_MAX).contains(&self.k) {
+            return Err(SuperError::InvalidParameter(
+                "k".to_owned(),
+                format!("{}, RRF k must be in {}..={}", self.k, RRF_K_MIN, RRF_K_MAX),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for RrfRanker {
+    /// An RRF ranker with the server default `k` of 60
+    fn default() -> Self {
+        Self { k: DEFAULT_RRF_K }
+    }
 }
 
 impl BaseRanker for RrfRanker {
@@ -364,6 +494,10 @@ impl BaseRanker for RrfRanker {
             },
         ]
     }
+
+    fn validate(&self, _num_requests: usize) -> Result<()> {
+        self.check_k()
+    }
 }
 
 /// Options for hybrid search operation
@@ -387,15 +521,16 @@ pub type HybridSearchOptions = SearchOptions;
 ///     .limit(100)
 ///     .offset(0);
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct QueryOptions {
     output_fields: Vec<String>,
     partition_names: Vec<String>,
     guarantee_timestamp: u64,
     query_params: Vec<crate::proto::common::KeyValuePair>,
-    consistency_level: i32,
-    use_default_consistency: bool,
+    consistency_level: Option<ConsistencyLevel>,
     expr_template_values: HashMap<String, crate::proto::schema::TemplateValue>,
+    graceful_time_ms: Option<i64>,
+    primary_key: Option<(String, PrimaryKeyType)>,
 }
 
 // get() shares query()'s options
@@ -407,6 +542,7 @@ pub type GetOptions = QueryOptions;
 ///
 /// This enum allows specifying whether the IDs are integers or strings,
 /// which affects how the query expression is constructed.
+#[derive(Debug, Clone)]
 pub enum IdType {
     /// Integer IDs (i64)
     Int64(Vec<i64>),
@@ -414,17 +550,49 @@ pub enum IdType {
     VarChar(Vec<String>),
 }
 
-impl Default for QueryOptions {
-    fn default() -> Self {
-        Self {
-            output_fields: Vec::new(),
-            partition_names: Vec::new(),
-            guarantee_timestamp: 0,
-            query_params: vec![],
-            consistency_level: 2,
-            use_default_consistency: true,
-            expr_template_values: HashMap::new(),
+/// Declared type of a primary key, to build ID expressions without describing
+/// the collection
+///
+/// See [`QueryOptions::primary_key`] and
+/// [`DeleteOptions::primary_key`](crate::mutate::DeleteOptions::primary_key).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrimaryKeyType {
+    /// Integer keys, formatted as numbers
+    Int64,
+    /// String keys, formatted as quoted strings
+    VarChar,
+}
+
+impl PrimaryKeyType {
+    /// Builds a `field in [...]` expression matching `ids`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the IDs are not of this type
+    pub(crate) fn ids_expr(self, field_name: &str, ids: &IdType) -> Result<String> {
+        let mut expr = format!("{field_name} in [");
+        match (self, ids) {
+            (PrimaryKeyType::Int64, IdType::Int64(ids)) => {
+                let ids: Vec<String> = ids.iter().map(i64::to_string).collect();
+                expr.push_str(&ids.join(","));
+            }
+            (PrimaryKeyType::VarChar, IdType::VarChar(ids)) => {
+                for (i, id) in ids.iter().enumerate() {
+                    if i > 0 {
+                        expr.push(',');
+                    }
+                    append_quoted_string(&mut expr, id);
+                }
+            }
+            (pk_type, ids) => {
+                return Err(SuperError::InvalidParameter(
+                    "ids".to_owned(),
+                    format!("{ids:?} do not match primary key type {pk_type:?}"),
+                ));
+            }
         }
+        expr.push(']');
+        Ok(expr)
     }
 }
 
@@ -494,12 +662,12 @@ impl QueryOptions {
     ///
     /// # Arguments
     ///
-    /// * `consistency_level` - Consistency level as integer
+    /// * `consistency_level` - Consistency level overriding the collection default
     ///
     /// # Returns
     ///
     /// A new `QueryOptions` instance
-    pub fn with_consistency_level(consistency_level: i32) -> Self {
+    pub fn with_consistency_level(consistency_level: ConsistencyLevel) -> Self {
         Self::default().consistency_level(consistency_level)
     }
 
@@ -560,6 +728,25 @@ impl QueryOptions {
         self
     }
 
+    /// Sets the staleness window of Bounded and Customized consistency
+    ///
+    /// The guarantee timestamp is computed as the current time minus
+    /// `graceful_time_ms`, so the request sees every write older than that.
+    /// Ignored for other consistency levels or when a guarantee timestamp is set.
+    /// A negative value is rejected when the request is sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `graceful_time_ms` - Accepted staleness in milliseconds
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn graceful_time_ms(mut self, graceful_time_ms: i64) -> Self {
+        self.graceful_time_ms = Some(graceful_time_ms);
+        self
+    }
+
     /// Sets the query parameters
     ///
     /// # Arguments
@@ -574,31 +761,48 @@ impl QueryOptions {
         self
     }
 
-    /// Sets the consistency level
+    /// Sets the consistency level of the query
+    ///
+    /// When unset, the query uses the level the collection was created with.
     ///
     /// # Arguments
     ///
-    /// * `consistency_level` - Consistency level as integer
+    /// * `consistency_level` - Consistency level overriding the collection default
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn consistency_level(mut self, consistency_level: ConsistencyLevel) -> Self {
+        self.consistency_level = Some(consistency_level);
+        self
+    }
+
+    /// Formerly set whether the query uses the collection's consistency level
+    ///
+    /// The collection default is now used exactly when no
+    /// [`QueryOptions::consistency_level`] is set, so this does nothing.
     ///
     /// # Returns
     ///
     /// Self for method chaining
-    pub fn consistency_level(mut self, consistency_level: i32) -> Self {
-        self.consistency_level = consistency_level;
+    #[deprecated(note = "the collection default applies unless `consistency_level` is set")]
+    pub fn use_default_consistency(self, _use_default_consistency: bool) -> Self {
         self
     }
 
-    /// Sets whether to use default consistency
+    /// Declares the primary key field, so [`Client::get`] formats the IDs by
+    /// `pk_type` instead of looking the field up in the schema
     ///
     /// # Arguments
     ///
-    /// * `use_default_consistency` - Boolean flag
+    /// * `field_name` - Name of the primary key field
+    /// * `pk_type` - Type of the primary key, the IDs must match it
     ///
     /// # Returns
     ///
     /// Self for method chaining
-    pub fn use_default_consistency(mut self, use_default_consistency: bool) -> Self {
-        self.use_default_consistency = use_default_consistency;
+    pub fn primary_key(mut self, field_name: impl Into<String>, pk_type: PrimaryKeyType) -> Self {
+        self.primary_key = Some((field_name.into(), pk_type));
         self
     }
 
@@ -835,6 +1039,11 @@ pub struct SearchOptions {
     pub(crate) ranker: Option<Box<dyn BaseRanker>>,
     pub(crate) expr_template_values: HashMap<String, proto::schema::TemplateValue>,
     pub(crate) other_params: Option<Vec<KeyValuePair>>,
+    pub(crate) normalize_sparse: bool,
+    pub(crate) normalize_query: bool,
+    pub(crate) guarantee_timestamp: u64,
+    pub(crate) graceful_time_ms: Option<i64>,
+    pub(crate) consistency_level: Option<ConsistencyLevel>,
 }
 
 impl Default for SearchOptions {
@@ -849,6 +1058,11 @@ impl Default for SearchOptions {
             ranker: None,
             expr_template_values: HashMap::new(),
             other_params: None,
+            normalize_sparse: false,
+            normalize_query: false,
+            guarantee_timestamp: 0,
+            graceful_time_ms: None,
+            consistency_level: None,
         }
     }
 }
@@ -915,6 +1129,25 @@ impl SearchOptions {
         self.add_param("radius", radius.to_string())
     }
 
+    /// Adds the inner bound of a range search, see [`SearchOptions::radius`]
+    ///
+    /// With a similarity metric such as `IP` or `COSINE` hits score in
+    /// `(radius, range_filter]`, so `range_filter` must be above `radius`. With
+    /// a distance metric such as `L2` hits lie in `[range_filter, radius)`, so
+    /// it must be below. When the metric is set on the options, inconsistent
+    /// bounds are rejected before the request is sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `range_filter` - Bound of the closest hits to leave out
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn range_filter(self, range_filter: f32) -> Self {
+        self.add_param("range_filter", range_filter.to_string())
+    }
+
     /// Sets the filter expression for the search
     ///
     /// # Arguments
@@ -957,6 +1190,16 @@ impl SearchOptions {
         self
     }
 
+    /// Returns every field of the collection with the hits, including the
+    /// dynamic ones, the same as `output_fields(vec!["*".to_owned()])`
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn output_all_fields(self) -> Self {
+        self.output_fields(vec![ALL_FIELDS.to_owned()])
+    }
+
     /// Sets the partition names for the search
     ///
     /// # Arguments
@@ -985,6 +1228,180 @@ impl SearchOptions {
         self
     }
 
+    /// Sets the metric of the search, replacing any `metric_type` param
+    ///
+    /// By default the metric of the index is used. The metric must suit the
+    /// searched field, e.g. only IP for sparse vectors, which is checked before
+    /// the request is sent. The server rejects a metric its index does not
+    /// support with an error naming both.
+    ///
+    /// # Arguments
+    ///
+    /// * `metric` - Metric used to score the hits
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn metric_type(mut self, metric: MetricType) -> Self {
+        self.search_params.retain(|p| p.key != "metric_type");
+        self.add_param("metric_type", metric.to_string())
+    }
+
+    /// Sets whether growing segments are skipped by the search
+    ///
+    /// Skipping growing segments gives more stable latency, at the cost of not
+    /// seeing data that has not been sealed yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `ignore_growing` - Whether to ignore growing segments
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn ignore_growing(self, ignore_growing: bool) -> Self {
+        self.add_param("ignore_growing", ignore_growing.to_string())
+    }
+
+    /// Groups the hits by the value of a scalar field, returning up to `limit`
+    /// groups per query vector
+    ///
+    /// # Arguments
+    ///
+    /// * `field_name` - Name of the field to group by
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn group_by_field(self, field_name: impl Into<String>) -> Self {
+        self.add_param("group_by_field", field_name)
+    }
+
+    /// Sets how many hits each group returns in a grouping search, 1 by default
+    ///
+    /// # Arguments
+    ///
+    /// * `group_size` - Maximum number of hits per group, at least 1
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn group_size(self, group_size: usize) -> Self {
+        self.add_param("group_size", group_size.to_string())
+    }
+
+    /// Sets whether every group must hold exactly `group_size` hits
+    ///
+    /// Without it, groups with fewer matching entities are returned with fewer
+    /// hits. Enforcing it makes the search slower.
+    ///
+    /// # Arguments
+    ///
+    /// * `strict_group_size` - Whether to fill every group to `group_size`
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn strict_group_size(self, strict_group_size: bool) -> Self {
+        self.add_param("strict_group_size", strict_group_size.to_string())
+    }
+
+    /// Sets the guarantee timestamp for the search
+    ///
+    /// The search sees at least all writes up to this timestamp, e.g. the
+    /// `timestamp` of the `MutationResult` returned by an insert. When set, it
+    /// takes precedence over the consistency level.
+    ///
+    /// # Arguments
+    ///
+    /// * `guarantee_timestamp` - Timestamp value
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn guarantee_timestamp(mut self, guarantee_timestamp: u64) -> Self {
+        self.guarantee_timestamp = guarantee_timestamp;
+        self
+    }
+
+    /// Sets the staleness window of Bounded and Customized consistency, see
+    /// [`QueryOptions::graceful_time_ms`]
+    ///
+    /// # Arguments
+    ///
+    /// * `graceful_time_ms` - Accepted staleness in milliseconds
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn graceful_time_ms(mut self, graceful_time_ms: i64) -> Self {
+        self.graceful_time_ms = Some(graceful_time_ms);
+        self
+    }
+
+    /// Sets the consistency level of the search
+    ///
+    /// When unset, the search uses the level the collection was created with.
+    ///
+    /// # Arguments
+    ///
+    /// * `consistency_level` - Consistency level overriding the collection default
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn consistency_level(mut self, consistency_level: ConsistencyLevel) -> Self {
+        self.consistency_level = Some(consistency_level);
+        self
+    }
+
+    /// The level set with [`SearchOptions::consistency_level`], or else a
+    /// `consistency_level` search param
+    fn requested_consistency(&self) -> Option<ConsistencyLevel> {
+        self.consistency_level.or_else(|| {
+            self.search_params
+                .iter()
+                .find(|p| p.key == "consistency_level")
+                .and_then(|p| p.value.parse::<i32>().ok())
+                .and_then(|level| ConsistencyLevel::try_from(level).ok())
+        })
+    }
+
+    /// Sets whether sparse query vectors are L2-normalized before searching
+    ///
+    /// Normalizing sparse vectors can improve IP ranking for some sparse models,
+    /// see [`crate::sparse::sparse_normalize`].
+    ///
+    /// # Arguments
+    ///
+    /// * `normalize_sparse` - Whether to normalize sparse query vectors
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn normalize_sparse(mut self, normalize_sparse: bool) -> Self {
+        self.normalize_sparse = normalize_sparse;
+        self
+    }
+
+    /// Sets whether dense float query vectors are L2-normalized before searching
+    ///
+    /// Useful for COSINE fields when the query vectors are not normalized, however
+    /// the data was inserted. A query vector whose values are all zero is sent
+    /// unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `normalize_query` - Whether to normalize dense query vectors
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn normalize_query(mut self, normalize_query: bool) -> Self {
+        self.normalize_query = normalize_query;
+        self
+    }
+
     /// Adds a search parameter
     ///
     /// # Arguments
@@ -1095,51 +1512,295 @@ impl SearchOptions {
     }
 }
 
-impl Client {
-    /// Gets the guarantee timestamp from consistency level
-    ///
-    /// This method converts a consistency level to the appropriate timestamp
-    /// value used internally by Milvus for consistency guarantees.
-    ///
-    /// # Arguments
-    ///
-    /// * `collection_name` - Name of the collection
-    /// * `consistency_level` - Consistency level to convert
-    ///
-    /// # Returns
-    ///
-    /// Timestamp value corresponding to the consistency level
-    pub(crate) async fn get_gts_from_consistency(
-        &self,
-        collection_name: &str,
-        consistency_level: ConsistencyLevel,
-    ) -> u64 {
-        match consistency_level {
-            ConsistencyLevel::Strong => STRONG_TIMESTAMP,
-            ConsistencyLevel::Bounded => BOUNDED_TIMESTAMP,
-            ConsistencyLevel::Eventually => EVENTUALLY_TIMESTAMP,
-            ConsistencyLevel::Session => self
-                .collection_cache
-                .get_timestamp(collection_name)
-                .unwrap_or(EVENTUALLY_TIMESTAMP),
-
-            // This level not works for now
-            ConsistencyLevel::Customized => 0,
-        }
+/// A complete search, the collection and query vectors along with the options
+///
+/// Every part is set by name, so options added later do not change how a
+/// request is built. Pass it to [`Client::search_with`].
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// use milvus::query::SearchRequest;
+///
+/// let request = SearchRequest::new()
+///     .collection("my_collection")
+///     .data(vec![vec![0.1f32, 0.2, 0.3].into()])
+///     .top_k(5)
+///     .filter("age > 18")
+///     .output_fields(vec!["title".to_string()])
+///     .params([("metric_type", "L2"), ("nprobe", "16")]);
+///
+/// let results = client.search_with(request).await?;
+/// ```
+#[derive(Default)]
+pub struct SearchRequest<'a> {
+    pub(crate) collection_name: String,
+    pub(crate) data: Vec<Value<'a>>,
+    pub(crate) options: SearchOptions,
+}
+
+impl<'a> SearchRequest<'a> {
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    /// Performs a query operation on a collection
-    ///
-    /// This method retrieves data from a collection using a boolean expression
-    /// and optional configuration parameters.
-    ///
-    /// # Arguments
-    ///
-    /// * `collection_name` - Name of the collection to query
-    /// * `expr` - Boolean expression string to filter results
-    /// * `options` - Query configuration options
-    ///
-    /// # Returns
+    /// Sets the collection to search
+    pub fn collection(mut self, collection_name: impl Into<String>) -> Self {
+        self.collection_name = collection_name.into();
+        self
+    }
+
+    /// Sets the query vectors, one result group is returned for each
+    pub fn data(mut self, data: Vec<Value<'a>>) -> Self {
+        self.data = data;
+        self
+    }
+
+    /// Sets the maximum number of hits per query vector
+    pub fn top_k(mut self, top_k: usize) -> Self {
+        self.options = self.options.limit(top_k);
+        self
+    }
+
+    /// Sets the boolean expression the hits must match
+    pub fn filter(mut self, filter: impl Into<String>) -> Self {
+        self.options = self.options.filter(filter.into());
+        self
+    }
+
+    /// Sets the fields returned with the hits
+    pub fn output_fields(mut self, output_fields: Vec<String>) -> Self {
+        self.options = self.options.output_fields(output_fields);
+        self
+    }
+
+    /// Adds search parameters, such as `metric_type` or index specific ones
+    pub fn params<K, V>(mut self, params: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        for (key, value) in params {
+            self.options = self.options.add_param(key, value);
+        }
+        self
+    }
+
+    /// Sets the consistency level, the collection default when unset
+    pub fn consistency(mut self, consistency_level: ConsistencyLevel) -> Self {
+        self.options = self.options.consistency_level(consistency_level);
+        self
+    }
+
+    /// Restricts the search to these partitions
+    pub fn partitions(mut self, partitions: Vec<String>) -> Self {
+        self.options = self.options.partitions(partitions);
+        self
+    }
+
+    /// Replaces the options, for settings without a method of their own
+    pub fn options(mut self, options: SearchOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+/// Search parameter presets trading recall against latency
+///
+/// A preset picks the search parameters of an index from its type and build
+/// parameters, e.g. `ef` for HNSW or `nprobe` for the IVF family. Use it with
+/// [`Client::search_preset`], which reads the index of the searched field.
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// use milvus::query::SearchParams;
+///
+/// let results = client
+///     .search_preset("my_collection", "vector", vectors, SearchParams::high_recall(), None)
+///     .await?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchParams {
+    /// Explores more of the index to find more of the true nearest neighbors
+    HighRecall,
+    /// Explores as little of the index as keeps results usable
+    LowLatency,
+}
+
+impl SearchParams {
+    pub fn high_recall() -> Self {
+        SearchParams::HighRecall
+    }
+
+    pub fn low_latency() -> Self {
+        SearchParams::LowLatency
+    }
+
+    /// Resolves the preset to search parameters for an index built with `index`
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Build parameters of the searched index
+    /// * `limit` - Number of hits asked for, HNSW needs `ef` to be at least this
+    ///
+    /// # Returns
+    ///
+    /// The search parameters, empty for indexes without any such as `FLAT`
+    pub fn for_index(&self, index: &IndexConfig, limit: usize) -> Vec<KeyValuePair> {
+        let high_recall = *self == SearchParams::HighRecall;
+        let param = |key: &str, value: String| {
+            vec![KeyValuePair {
+                key: key.to_owned(),
+                value,
+            }]
+        };
+
+        match index {
+            IndexConfig::Hnsw { .. } => {
+                let ef = if high_recall { 256 } else { 32 };
+                param("ef", ef.max(limit).to_string())
+            }
+            IndexConfig::IvfFlat { nlist }
+            | IndexConfig::BinIvfFlat { nlist }
+            | IndexConfig::IvfSQ8 { nlist }
+            | IndexConfig::IvfPQ { nlist, .. } => {
+                let nprobe = if high_recall {
+                    nlist.div_ceil(8)
+                } else {
+                    nlist.div_ceil(64)
+                };
+                param("nprobe", nprobe.max(1).to_string())
+            }
+            IndexConfig::SparseInvertedIndex { .. } => {
+                let drop_ratio = if high_recall { "0.0" } else { "0.2" };
+                param("drop_ratio_search", drop_ratio.to_owned())
+            }
+            IndexConfig::Flat | IndexConfig::BinFlat | IndexConfig::Raw(_) => Vec::new(),
+        }
+    }
+}
+
+/// Options for [`Client::find_duplicates`]
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// use milvus::query::{FindDuplicatesOptions, IdType};
+///
+/// let options = FindDuplicatesOptions::new()
+///     .limit(100)
+///     .exclude_self(IdType::Int64(vec![1, 2, 3]))
+///     .partition_names(vec!["tenant_a".to_string()]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FindDuplicatesOptions {
+    pub(crate) limit: usize,
+    pub(crate) self_ids: Option<IdType>,
+    pub(crate) partition_names: Vec<String>,
+}
+
+impl Default for FindDuplicatesOptions {
+    fn default() -> Self {
+        Self {
+            limit: 100,
+            self_ids: None,
+            partition_names: Vec::new(),
+        }
+    }
+}
+
+impl FindDuplicatesOptions {
+    /// Creates a new FindDuplicatesOptions instance with default values
+    ///
+    /// # Returns
+    ///
+    /// A new `FindDuplicatesOptions` instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of duplicates returned per query vector
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - Maximum number of duplicates per query vector
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Drops self-matches, for query vectors that are stored in the collection
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - Primary keys of the query vectors, in the same order
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn exclude_self(mut self, ids: IdType) -> Self {
+        self.self_ids = Some(ids);
+        self
+    }
+
+    /// Only looks for duplicates within the given partitions, e.g. to deduplicate
+    /// per tenant
+    ///
+    /// # Arguments
+    ///
+    /// * `partition_names` - Partitions to search, they must exist
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn partition_names(mut self, partition_names: Vec<String>) -> Self {
+        self.partition_names = partition_names;
+        self
+    }
+}
+
+impl Client {
+    /// Gets the guarantee timestamp from consistency level
+    ///
+    /// This method converts a consistency level to the appropriate timestamp
+    /// value used internally by Milvus for consistency guarantees.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - Name of the collection
+    /// * `consistency_level` - Consistency level to convert
+    ///
+    /// # Returns
+    ///
+    /// Timestamp value corresponding to the consistency level
+    pub(crate) async fn get_gts_from_consistency(
+        &self,
+        collection_name: &str,
+        consistency_level: ConsistencyLevel,
+    ) -> u64 {
+        consistency_timestamp(
+            consistency_level,
+            self.collection_cache.get_timestamp(collection_name),
+        )
+    }
+
+    /// Performs a query operation on a collection
+    ///
+    /// This method retrieves data from a collection using a boolean expression
+    /// and optional configuration parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - Name of the collection to query
+    /// * `expr` - Boolean expression string to filter results
+    /// * `options` - Query configuration options
+    ///
+    /// # Returns
     ///
     /// Vector of field columns containing the query results
     ///
@@ -1163,39 +1824,105 @@ impl Client {
     where
         S: Into<String>,
     {
-        let collection_name = collection_name.into();
+        self.query_impl(collection_name.into(), expr, options, false)
+            .await
+    }
+
+    /// Performs a query that sees every write acknowledged before the call
+    ///
+    /// The query runs with Strong consistency whatever the collection's default
+    /// level or the consistency set in `options`, so read-after-write needs no
+    /// manual flush or sleep.
+    ///
+    /// Strong reads wait for the query nodes to catch up with the latest
+    /// timestamp, which adds up to one time tick (200ms on a default server) to
+    /// every call. Prefer `query` with the collection's level outside tests and
+    /// other read-after-write paths.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - Name of the collection to query
+    /// * `expr` - Boolean expression string to filter results
+    /// * `options` - Query configuration options, its consistency settings are ignored
+    ///
+    /// # Returns
+    ///
+    /// Vector of field columns containing the query results
+    pub async fn query_strong<S>(
+        &self,
+        collection_name: S,
+        expr: &str,
+        options: &QueryOptions,
+    ) -> Result<Vec<FieldColumn>>
+    where
+        S: Into<String>,
+    {
+        self.query_impl(collection_name.into(), expr, options, true)
+            .await
+    }
+
+    /// Counts the entities matching `expr`, every entity when it is empty
+    ///
+    /// Runs a `count(*)` query, so deleted entities are not counted, unlike
+    /// the `row_count` of [`Client::get_collection_stats`].
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - Name of the collection to count
+    /// * `expr` - Boolean expression string to filter the counted entities
+    /// * `options` - Query configuration options, set `partition_names` to count
+    ///   only the entities of those partitions. The output fields are replaced
+    ///
+    /// # Returns
+    ///
+    /// Number of matching entities
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let options = QueryOptions::new().partition_names(vec!["2024".to_string()]);
+    /// let count = client.count("my_collection", "", &options).await?;
+    /// ```
+    pub async fn count<S>(
+        &self,
+        collection_name: S,
+        expr: &str,
+        options: &QueryOptions,
+    ) -> Result<i64>
+    where
+        S: Into<String>,
+    {
+        let options = options.clone().output_fields(vec![COUNT_FIELD.to_owned()]);
+        let result = self
+            .query_impl(collection_name.into(), expr, &options, false)
+            .await?;
+        match result.first().map(|column| &column.value) {
+            Some(ValueVec::Long(count)) if !count.is_empty() => Ok(count[0]),
+            _ => Err(SuperError::Unexpected(
+                "no count in query result".to_owned(),
+            )),
+        }
+    }
+
+    async fn query_impl(
+        &self,
+        collection_name: String,
+        expr: &str,
+        options: &QueryOptions,
+        strong: bool,
+    ) -> Result<Vec<FieldColumn>> {
+        self.precheck_collection(&collection_name).await?;
         let collection = self.collection_cache.get(&collection_name).await?;
+        let request = prepare_query_request(
+            collection_name.clone(),
+            expr,
+            options,
+            strong,
+            collection.consistency_level,
+            self.collection_cache.get_timestamp(&collection_name),
+        )?;
 
-        let res = self
-            .client
-            .clone()
-            .query(QueryRequest {
-                base: Some(MsgBase::new(MsgType::Retrieve)),
-                db_name: "".to_string(),
-                collection_name: collection_name.clone(),
-                expr: expr.to_string(),
-                output_fields: options.output_fields.clone(),
-                partition_names: options.partition_names.clone(),
-                travel_timestamp: 0,
-                guarantee_timestamp: if options.guarantee_timestamp > 0 {
-                    options.guarantee_timestamp
-                } else {
-                    self.get_gts_from_consistency(&collection_name, collection.consistency_level)
-                        .await
-                },
-                query_params: options.query_params.clone(),
-                not_return_all_meta: false,
-                consistency_level: if options.consistency_level > 0 {
-                    options.consistency_level
-                } else {
-                    2
-                },
-                use_default_consistency: options.use_default_consistency,
-                expr_template_values: options.expr_template_values.clone(),
-                namespace: Some("".to_string()),
-            })
-            .await?
-            .into_inner();
+        let res = self.client.clone().query(request).await?.into_inner();
 
         status_to_result(&res.status)?;
 
@@ -1240,151 +1967,434 @@ impl Client {
     where
         S: Into<String>,
     {
-        let options = options.unwrap_or_default();
-        // check and prepare params
-        let mut search_params = options.search_params.clone();
-        if let Some(other_params) = &options.other_params {
-            search_params.extend(other_params.clone());
-        }
+        let (raw_data, elapsed) = self.search_raw(collection_name, data, options).await?;
+        parse_search_results(raw_data, elapsed)
+    }
 
-        // Add default parameters if not present
-        if !search_params.iter().any(|p| p.key == "topk") {
-            search_params.push(KeyValuePair {
-                key: "topk".to_string(),
-                value: options.limit.to_string(),
-            });
-        }
+    /// Performs a vector search and hands every hit to `f` as it is decoded
+    ///
+    /// Takes the same arguments as [`Client::search`], but does not build the
+    /// result groups, so a large response is not held twice in memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - Name of the collection to search
+    /// * `data` - Vector data to search for
+    /// * `options` - Optional search configuration
+    /// * `f` - Called with the index of the query vector and each of its hits,
+    ///   best hit first within a group
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the search fails, or the first error returned by `f`,
+    /// which stops decoding the remaining hits
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let mut best = vec![0.0; queries.len()];
+    /// client
+    ///     .search_for_each("my_collection", queries, None, |group, hit| {
+    ///         best[group] = f32::max(best[group], hit.score);
+    ///         Ok(())
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn search_for_each<S, F>(
+        &self,
+        collection_name: S,
+        data: Vec<Value<'_>>,
+        options: Option<SearchOptions>,
+        f: F,
+    ) -> Result<()>
+    where
+        S: Into<String>,
+        F: FnMut(usize, Hit) -> Result<()>,
+    {
+        let (raw_data, _) = self.search_raw(collection_name, data, options).await?;
+        for_each_search_hit(raw_data, f)
+    }
 
-        if !search_params.iter().any(|p| p.key == "round_decimal") {
-            search_params.push(KeyValuePair {
-                key: "round_decimal".to_string(),
-                value: "-1".to_string(),
-            });
-        }
+    /// Performs the search described by `request`
+    ///
+    /// The same as [`Client::search`], with the arguments set by name on a
+    /// [`SearchRequest`].
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - Collection, query vectors and options of the search
+    ///
+    /// # Returns
+    ///
+    /// Vector of search results, one per query vector
+    pub async fn search_with(&self, request: SearchRequest<'_>) -> Result<Vec<SearchResult<'_>>> {
+        self.search(request.collection_name, request.data, Some(request.options))
+            .await
+    }
 
-        if !search_params.iter().any(|p| p.key == "ignore_growing") {
-            search_params.push(KeyValuePair {
-                key: "ignore_growing".to_string(),
-                value: "false".to_string(),
-            });
-        }
+    /// Sends a search request, returning the undecoded result data and the
+    /// round-trip time of the RPC
+    async fn search_raw<S>(
+        &self,
+        collection_name: S,
+        data: Vec<Value<'_>>,
+        options: Option<SearchOptions>,
+    ) -> Result<(proto::schema::SearchResultData, Duration)>
+    where
+        S: Into<String>,
+    {
+        let options = options.unwrap_or_default();
+        let search_params = prepare_search_params(&options)?;
 
-        // Add anns_field if specified in options
-        if !options.anns_field.is_empty() && !search_params.iter().any(|p| p.key == "anns_field") {
-            search_params.push(KeyValuePair {
-                key: "anns_field".to_string(),
-                value: options.anns_field[0].clone(),
-            });
+        let collection_name = collection_name.into();
+        self.precheck_collection(&collection_name).await?;
+        let collection = self.collection_cache.get(&collection_name).await?;
+
+        // Make sure the query vectors and metric match the type of the field
+        // being searched, the only vector field if none is named
+        let anns_field = extract_param(&search_params, "anns_field", "");
+        let metric = MetricType::from_str(&extract_param(&search_params, "metric_type", "")).ok();
+        if !anns_field.is_empty() {
+            let dtype = self.field_data_type(&collection_name, &anns_field).await?;
+            check_query_vectors(&anns_field, dtype, &data)?;
+            if let Some(metric) = metric {
+                metric.check_supported(&anns_field, dtype)?;
+            }
+        } else if let Some(metric) = metric {
+            let mut vector_fields = collection.fields.iter().filter(|f| f.is_vector());
+            if let (Some(field), None) = (vector_fields.next(), vector_fields.next()) {
+                metric.check_supported(&field.name, field.dtype)?;
+            }
         }
+        check_output_fields(&collection.fields, &options.output_fields)?;
 
-        // Merge all parameters into a single params field (similar to Python's get_params)
-        let merged_params = get_params(&search_params);
-        search_params.push(KeyValuePair {
-            key: "params".to_string(),
-            value: merged_params,
-        });
+        let data = if options.normalize_sparse {
+            data.into_iter().map(normalize_sparse_query).collect()
+        } else {
+            data
+        };
+        let data = if options.normalize_query {
+            data.into_iter().map(normalize_dense_query).collect()
+        } else {
+            data
+        };
 
-        let collection_name = collection_name.into();
-        let collection = self.collection_cache.get(&collection_name).await?;
-        let res = self
-            .client
-            .clone()
-            .search(SearchRequest {
-                base: Some(MsgBase::new(MsgType::Search)),
-                db_name: "".to_string(),
-                collection_name: collection_name.clone(),
-                partition_names: options.partition_names.clone(),
-                dsl: options.filter,
-                nq: data.len() as _,
-                placeholder_group: get_place_holder_group(&data)?,
-                dsl_type: DslType::BoolExprV1 as _,
-                output_fields: options
-                    .output_fields
-                    .clone()
-                    .into_iter()
-                    .map(|f| f.into())
-                    .collect(),
-                search_params,
-                travel_timestamp: 0,
-                guarantee_timestamp: self
-                    .get_gts_from_consistency(&collection_name, collection.consistency_level)
-                    .await,
-                not_return_all_meta: false,
-                consistency_level: extract_param(&options.search_params, "consistency_level", "2")
-                    .parse()
-                    .unwrap_or(ConsistencyLevel::Bounded as _),
-                use_default_consistency: extract_param(
-                    &options.search_params,
-                    "use_default_consistency",
-                    "true",
-                )
-                .parse()
-                .unwrap_or(true),
-                search_by_primary_keys: false,
-                expr_template_values: options.expr_template_values.clone(),
-                sub_reqs: vec![],
-                function_score: None,
-                namespace: Some("".to_string()),
-            })
-            .await?
-            .into_inner();
+        let request = prepare_search_request(
+            collection_name.clone(),
+            &data,
+            options,
+            search_params,
+            collection.consistency_level,
+            self.collection_cache.get_timestamp(&collection_name),
+        )?;
+
+        let started = Instant::now();
+        let res = self.client.clone().search(request).await?.into_inner();
         status_to_result(&res.status)?;
         let raw_data = res
             .results
             .ok_or(SuperError::Unexpected("no result for search".to_owned()))?;
-        let mut result = Vec::new();
-        let mut offset = 0;
-        let fields_data = raw_data
-            .fields_data
+
+        Ok((raw_data, started.elapsed()))
+    }
+
+    /// Performs a vector search with a single query vector
+    ///
+    /// This is a convenience wrapper around [`Client::search`] for the common
+    /// case of one query, returning the hits directly instead of a result
+    /// group per query vector.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - Name of the collection to search
+    /// * `query` - Query vector
+    /// * `top_k` - Maximum number of hits to return, overrides the limit in `options`
+    /// * `options` - Optional search configuration
+    ///
+    /// # Returns
+    ///
+    /// Hits for the query vector, ordered by score
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server does not return exactly one result group
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let hits = client
+    ///     .search_one("my_collection", vec![0.1_f32, 0.2, 0.3], 10, None)
+    ///     .await?;
+    /// for hit in hits {
+    ///     println!("{:?}: {}", hit.id, hit.score);
+    /// }
+    /// ```
+    pub async fn search_one<S, V>(
+        &self,
+        collection_name: S,
+        query: V,
+        top_k: usize,
+        options: Option<SearchOptions>,
+    ) -> Result<Vec<Hit>>
+    where
+        S: Into<String>,
+        V: Into<Value<'static>>,
+    {
+        let options = options.unwrap_or_default().limit(top_k);
+        let results = self
+            .search(collection_name, vec![query.into()], Some(options))
+            .await?;
+        single_result_group(results)
+    }
+
+    /// Searches a text field by full-text relevance, ranked by BM25
+    ///
+    /// The schema must have a BM25 function taking `text_field` as input; its
+    /// sparse output field, indexed with the `BM25` metric, is searched with
+    /// `query` and the server turns the text into a sparse vector.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - Name of the collection to search
+    /// * `text_field` - VarChar field the BM25 function reads
+    /// * `query` - Text to search for
+    /// * `top_k` - Maximum number of hits to return
+    ///
+    /// # Returns
+    ///
+    /// Hits for the text, most relevant first
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no BM25 function takes `text_field` as input
+    pub async fn search_text(
+        &self,
+        collection_name: impl Into<String>,
+        text_field: &str,
+        query: &str,
+        top_k: usize,
+    ) -> Result<Vec<Hit>> {
+        let collection_name = collection_name.into();
+        let schema: CollectionSchema = self
+            .describe_collection(collection_name.clone())
+            .await?
+            .schema
+            .into();
+        let sparse_field = bm25_output_field(&schema, text_field)?;
+
+        let options = SearchOptions::new()
+            .anns_field(vec![sparse_field])
+            .add_param("metric_type", MetricType::BM25.to_string());
+        self.search_one(collection_name, query.to_owned(), top_k, Some(options))
+            .await
+    }
+
+    /// Searches a vector field with the search parameters of a preset
+    ///
+    /// The index of `field_name` is described first, so the preset resolves to
+    /// the parameters of its type, see [`SearchParams::for_index`].
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - Name of the collection to search
+    /// * `field_name` - Indexed vector field to search
+    /// * `data` - Query vectors
+    /// * `preset` - Whether to favor recall or latency
+    /// * `options` - Optional search configuration, parameters set here win
+    ///   over the preset
+    ///
+    /// # Returns
+    ///
+    /// Vector of search results, one per query vector
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `field_name` has no index
+    pub async fn search_preset<S, V>(
+        &self,
+        collection_name: S,
+        field_name: &str,
+        data: Vec<V>,
+        preset: SearchParams,
+        options: Option<SearchOptions>,
+    ) -> Result<Vec<SearchResult<'_>>>
+    where
+        S: Into<String>,
+        V: Into<Value<'static>>,
+    {
+        let collection_name = collection_name.into();
+        let index = self
+            .describe_index(collection_name.clone(), field_name.to_owned())
+            .await?
             .into_iter()
-            .map(Into::into)
-            .collect::<Vec<FieldColumn>>();
+            .next()
+            .ok_or_else(|| {
+                SuperError::InvalidParameter(
+                    "field_name".to_owned(),
+                    format!("{field_name} has no index"),
+                )
+            })?;
+
+        let mut options = options
+            .unwrap_or_default()
+            .anns_field(vec![field_name.to_owned()]);
+        for param in preset.for_index(index.config(), options.limit) {
+            if !options.search_params.iter().any(|p| p.key == param.key) {
+                options.search_params.push(param);
+            }
+        }
+        self.search(
+            collection_name,
+            data.into_iter().map(Into::into).collect(),
+            Some(options),
+        )
+        .await
+    }
 
-        // Handle case where no IDs are returned (empty search results)
-        // When there are no results, Milvus may return None for ids
-        let Some(ids) = raw_data.ids else {
-            return Ok(vec![]);
-        };
-        let Some(raw_id) = ids.id_field else {
-            return Ok(vec![]);
+    /// Finds the near-duplicates of each query vector with a range search
+    ///
+    /// Every entity within `radius` of a query vector is returned as one of its
+    /// duplicates. For similarity metrics such as `IP` and `COSINE`, entities
+    /// scoring above `radius` match, for distance metrics such as `L2`, entities
+    /// closer than `radius` match.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - Name of the collection to search
+    /// * `field_name` - Vector field to search
+    /// * `query_vectors` - Vectors to find duplicates for
+    /// * `radius` - Similarity or distance threshold for a duplicate
+    /// * `metric` - Metric type of the field's index
+    /// * `options` - Optional limit, self-match exclusion and partitions
+    ///
+    /// # Returns
+    ///
+    /// One group of hits per query vector, in the same order as `query_vectors`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the IDs given to exclude self-matches do not match the
+    /// number of query vectors, or if one of the partitions does not exist
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use milvus::index::MetricType;
+    ///
+    /// let groups = client
+    ///     .find_duplicates("my_collection", "vector", vectors, 0.95, MetricType::COSINE, None)
+    ///     .await?;
+    /// ```
+    pub async fn find_duplicates<S, V>(
+        &self,
+        collection_name: S,
+        field_name: &str,
+        query_vectors: Vec<V>,
+        radius: f32,
+        metric: MetricType,
+        options: Option<FindDuplicatesOptions>,
+    ) -> Result<Vec<Vec<Hit>>>
+    where
+        S: Into<String>,
+        V: Into<Value<'static>>,
+    {
+        let options = options.unwrap_or_default();
+        let self_ids: Option<Vec<Value>> = match options.self_ids {
+            Some(IdType::Int64(ids)) => Some(ids.into_iter().map(Value::Long).collect()),
+            Some(IdType::VarChar(ids)) => Some(ids.into_iter().map(Value::from).collect()),
+            None => None,
         };
+        if let Some(ids) = &self_ids {
+            check_self_ids(ids, query_vectors.len())?;
+        }
 
-        for k in raw_data.topks {
-            let k = k as usize;
-            let mut score = Vec::new();
-            score.extend_from_slice(&raw_data.scores[offset..offset + k]);
-            let mut result_data = fields_data
+        let collection_name = collection_name.into();
+        if !options.partition_names.is_empty() {
+            let existing = self.list_partitions(collection_name.clone()).await?;
+            if let Some(missing) = options
+                .partition_names
                 .iter()
-                .map(FieldColumn::copy_with_metadata)
-                .collect::<Vec<FieldColumn>>();
-            for j in 0..fields_data.len() {
-                for i in offset..offset + k {
-                    result_data[j].push(fields_data[j].get(i).ok_or(SuperError::Unexpected(
-                        "out of range while indexing field data".to_owned(),
-                    ))?);
-                }
+                .find(|name| !existing.contains(name))
+            {
+                return Err(crate::collection::Error::PartitionNotFound(
+                    collection_name,
+                    missing.clone(),
+                )
+                .into());
             }
+        }
 
-            let id = match raw_id {
-                proto::schema::i_ds::IdField::IntId(ref d) => {
-                    Vec::<Value>::from_iter(d.data[offset..offset + k].iter().map(|&x| x.into()))
-                }
-                proto::schema::i_ds::IdField::StrId(ref d) => Vec::<Value>::from_iter(
-                    d.data[offset..offset + k].iter().map(|x| x.clone().into()),
-                ),
-            };
-
-            result.push(SearchResult {
-                size: k as i64,
-                score,
-                field: result_data,
-                id,
-            });
+        // Ask for one more hit, as the self-match takes one of the slots
+        let limit = options.limit + self_ids.is_some() as usize;
+        let search_options = SearchOptions::with_limit(limit)
+            .anns_field(vec![field_name.to_owned()])
+            .add_param("metric_type", metric.to_string())
+            .radius(radius)
+            .partitions(options.partition_names);
+        let results = self
+            .search(
+                collection_name,
+                query_vectors.into_iter().map(Into::into).collect(),
+                Some(search_options),
+            )
+            .await?;
 
-            offset += k;
+        let mut groups: Vec<Vec<Hit>> = results.iter().map(SearchResult::hits).collect();
+        if let Some(ids) = &self_ids {
+            groups = drop_self_matches(groups, ids);
+        }
+        for group in &mut groups {
+            group.truncate(options.limit);
         }
 
-        Ok(result)
+        Ok(groups)
+    }
+
+    /// Performs a search with a batch of sparse query vectors
+    ///
+    /// Every query vector is sent in a single request, and the server returns one
+    /// result group per query vector, in the same order as `data`.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - Name of the collection to search
+    /// * `data` - Sparse query vectors, one result group is returned for each
+    /// * `options` - Optional search configuration, set `anns_field` when the
+    ///   collection has more than one vector field
+    ///
+    /// # Returns
+    ///
+    /// Vector of search results, one per query vector
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any query vector has no entries, an empty sparse query
+    /// can not match anything
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let queries = vec![vec![(1, 0.5), (7, 0.25)], vec![(3, 1.0)]];
+    /// let options = SearchOptions::with_limit(5).anns_field(vec!["sparse".to_owned()]);
+    /// let results = client.search_sparse("my_collection", queries, Some(options)).await?;
+    /// assert_eq!(results.len(), 2);
+    /// ```
+    pub async fn search_sparse<S>(
+        &self,
+        collection_name: S,
+        data: Vec<SparseVector>,
+        options: Option<SearchOptions>,
+    ) -> Result<Vec<SearchResult<'_>>>
+    where
+        S: Into<String>,
+    {
+        check_sparse_queries(&data)?;
+        self.search(
+            collection_name,
+            data.into_iter().map(Value::from).collect(),
+            options,
+        )
+        .await
     }
 
     /// Performs a hybrid search operation on a collection
@@ -1440,9 +2450,18 @@ impl Client {
     where
         S: Into<String>,
     {
+        ranker.validate(reqs.len())?;
+
         let options = options.unwrap_or_default();
         let collection_name = collection_name.into();
+        self.precheck_collection(&collection_name).await?;
         let collection = self.collection_cache.get(&collection_name).await?;
+        check_output_fields(&collection.fields, &options.output_fields)?;
+        let (level, use_default_consistency) = resolve_consistency(
+            options.requested_consistency(),
+            collection.consistency_level,
+        );
+        let guarantee_timestamp = self.get_gts_from_consistency(&collection_name, level).await;
 
         // Convert AnnSearchRequests to SearchRequests
         let mut search_requests = Vec::new();
@@ -1489,8 +2508,21 @@ impl Client {
                 });
             }
 
-            // Create placeholder group for this request
-            let placeholder_group = get_place_holder_group(&req.data)?;
+            // Serialize the query vectors according to the type of the searched field
+            let field = collection
+                .fields
+                .iter()
+                .find(|f| f.name == req.anns_field)
+                .ok_or_else(|| {
+                    SuperError::InvalidParameter(
+                        "anns_field".to_owned(),
+                        format!(
+                            "{} is not a field of collection {}",
+                            req.anns_field, collection_name
+                        ),
+                    )
+                })?;
+            let placeholder_group = get_field_place_holder_group(&req.data, field)?;
 
             // Create SearchRequest for this AnnSearchRequest
             let search_request = proto::milvus::SearchRequest {
@@ -1504,22 +2536,11 @@ impl Client {
                 output_fields: options.output_fields.clone(),
                 search_params: search_params.clone(),
                 travel_timestamp: 0,
-                guarantee_timestamp: self
-                    .get_gts_from_consistency(&collection_name, collection.consistency_level)
-                    .await,
+                guarantee_timestamp,
                 nq: req.data.len() as i64,
                 not_return_all_meta: false,
-                consistency_level: {
-                    let level = extract_param(&search_params, "consistency_level", "2");
-                    level.parse().unwrap_or(ConsistencyLevel::Bounded as _)
-                },
-                use_default_consistency: extract_param(
-                    &search_params,
-                    "use_default_consistency",
-                    "true",
-                )
-                .parse()
-                .unwrap_or(true),
+                consistency_level: level as i32,
+                use_default_consistency,
                 search_by_primary_keys: false,
                 sub_reqs: vec![],
                 expr_template_values: req.expr_params.unwrap_or_default(),
@@ -1542,25 +2563,16 @@ impl Client {
             requests: search_requests,
             rank_params,
             travel_timestamp: 0,
-            guarantee_timestamp: self
-                .get_gts_from_consistency(&collection_name, collection.consistency_level)
-                .await,
+            guarantee_timestamp,
             not_return_all_meta: false,
             output_fields: options.output_fields,
-            consistency_level: extract_param(&options.search_params, "consistency_level", "2")
-                .parse()
-                .unwrap_or(ConsistencyLevel::Bounded as _),
-            use_default_consistency: extract_param(
-                &options.search_params,
-                "use_default_consistency",
-                "true",
-            )
-            .parse()
-            .unwrap_or(true),
+            consistency_level: level as i32,
+            use_default_consistency,
             function_score: None,
             namespace: Some("".to_string()),
         };
 
+        let started = Instant::now();
         let res = self
             .client
             .clone()
@@ -1574,174 +2586,758 @@ impl Client {
             "no result for hybrid search".to_owned(),
         ))?;
 
-        let mut result = Vec::new();
-        let mut offset = 0;
-        let fields_data = raw_data
-            .fields_data
-            .into_iter()
-            .map(Into::into)
-            .collect::<Vec<FieldColumn>>();
+        parse_search_results(raw_data, started.elapsed())
+    }
 
-        // Handle case where no IDs are returned (empty search results)
-        // When there are no results, Milvus may return None for ids
-        let Some(ids) = raw_data.ids else {
-            return Ok(vec![]);
+    /// Extracts the primary key field from a collection
+    ///
+    /// # Arguments
+    ///
+    /// * `collection` - Collection reference
+    ///
+    /// # Returns
+    ///
+    /// Reference to the primary key field
+    fn extract_primary_field<'a>(&self, collection: &'a Collection) -> Result<&'a Field> {
+        collection
+            .fields
+            .iter()
+            .find(|f| f.is_primary_key)
+            .ok_or(Error::Schema(crate::schema::Error::NoPrimaryKey))
+    }
+
+    /// Packs primary key IDs into a query expression
+    ///
+    /// # Arguments
+    ///
+    /// * `collection` - Collection reference
+    /// * `pks` - Vector of primary key strings
+    ///
+    /// # Returns
+    ///
+    /// Query expression string for the primary keys
+    fn pack_pks_expr(&self, collection: &Collection, pks: Vec<String>) -> Result<String> {
+        let primary_field = self.extract_primary_field(collection)?;
+        let pk_field_name = primary_field.name.clone();
+        let data_type = primary_field.dtype;
+
+        if data_type == DataType::VarChar {
+            let ids: Vec<String> = pks.iter().map(|entry| quote_string(entry)).collect();
+            let expr = format!("{pk_field_name} in [{}]", ids.join(","));
+            return Ok(expr);
+        } else {
+            let mut ids: Vec<i64> = Vec::new();
+            for (i, entry) in pks.iter().enumerate() {
+                match entry.parse::<i64>() {
+                    Ok(id) => ids.push(id),
+                    Err(_) => {
+                        return Err(SuperError::Unexpected(format!(
+                            "Failed to parse primary key '{}' at index {} as integer",
+                            entry, i
+                        )));
+                    }
+                }
+            }
+            let expr = format!("{pk_field_name} in {:?}", ids);
+            return Ok(expr);
+        }
+    }
+
+    /// Gets specific entities by their IDs
+    ///
+    /// This operation retrieves entities from a collection using their primary key IDs.
+    /// It supports both integer and string primary keys, similar to the Python Milvus client's get() method.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection to query
+    /// * `ids` - The primary key IDs to retrieve. Can be either integer or string IDs
+    /// * `options` - Optional configuration for the query operation including output fields, partition names, etc.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing one entry per requested ID, in request order. Each entry
+    /// maps field names to values, or is `None` if no entity has that ID. Duplicate IDs
+    /// yield the same entity multiple times. If no IDs are provided, returns an empty vector.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use milvus_sdk_rust::query::{GetOptions, IdType};
+    ///
+    /// // Get by integer IDs
+    /// let int_ids = IdType::Int64(vec![1, 2, 3]);
+    /// let results = client.get("my_collection", int_ids, None).await?;
+    ///
+    /// // Get by string IDs
+    /// let string_ids = IdType::VarChar(vec!["id1".to_string(), "id2".to_string()]);
+    /// let options = GetOptions::new().output_fields(vec!["id".to_string(), "title".to_string()]);
+    /// let results = client.get("my_collection", string_ids, Some(options)).await?;
+    /// ```
+    pub async fn get<S>(
+        &self,
+        collection_name: S,
+        ids: IdType,
+        options: Option<GetOptions>,
+    ) -> Result<Vec<Option<RowMap>>>
+    where
+        S: Into<String>,
+    {
+        let collection_name = collection_name.into();
+        let option = options.unwrap_or_default();
+        let hinted = match &option.primary_key {
+            Some((name, pk_type)) => Some((name.clone(), pk_type.ids_expr(name, &ids)?)),
+            None => None,
+        };
+        let ids = match ids {
+            IdType::Int64(ids_int64) => ids_int64
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<String>>(),
+            IdType::VarChar(ids_string) => ids_string,
         };
-        let Some(raw_id) = ids.id_field else {
+
+        let ids: Vec<String> = ids.into_iter().map(|x| x.into()).collect();
+
+        //If ids is empty,return an empty vec
+        if ids.is_empty() {
             return Ok(vec![]);
+        }
+
+        let (pk_field_name, expr) = match hinted {
+            Some(hinted) => hinted,
+            None => {
+                let collection = self.collection_cache.get(&collection_name).await?;
+                let pk_field_name = self.extract_primary_field(&collection)?.name.clone();
+                let mut unique_ids = ids.clone();
+                unique_ids.sort();
+                unique_ids.dedup();
+                (pk_field_name, self.pack_pks_expr(&collection, unique_ids)?)
+            }
         };
+        let columns = self.query(collection_name, expr.as_str(), &option).await?;
 
-        for k in raw_data.topks {
-            let k = k as usize;
-            let mut score = Vec::new();
-            score.extend_from_slice(&raw_data.scores[offset..offset + k]);
-            let mut result_data = fields_data
-                .iter()
-                .map(FieldColumn::copy_with_metadata)
-                .collect::<Vec<FieldColumn>>();
-            for j in 0..fields_data.len() {
-                for i in offset..offset + k {
-                    result_data[j].push(fields_data[j].get(i).ok_or(SuperError::Unexpected(
-                        "out of range while indexing field data".to_owned(),
-                    ))?);
-                }
+        Ok(align_rows_to_ids(&pk_field_name, &ids, &columns))
+    }
+}
+
+/// Pivots query result columns into rows ordered like the requested IDs
+///
+/// # Arguments
+///
+/// * `pk_field_name` - Name of the primary key field
+/// * `ids` - Requested IDs, as strings
+/// * `columns` - Query result columns, in any row order
+///
+/// # Returns
+///
+/// One entry per requested ID, `None` if the ID is not in the results
+fn align_rows_to_ids(
+    pk_field_name: &str,
+    ids: &[String],
+    columns: &[FieldColumn],
+) -> Vec<Option<RowMap>> {
+    let mut row_of_id = HashMap::new();
+    if let Some(pk_column) = columns.iter().find(|c| c.name == pk_field_name) {
+        for i in 0..pk_column.len() {
+            match pk_column.get(i) {
+                Some(Value::Long(id)) => row_of_id.insert(id.to_string(), i),
+                Some(Value::String(id)) => row_of_id.insert(id.into_owned(), i),
+                _ => None,
+            };
+        }
+    }
+
+    ids.iter()
+        .map(|id| {
+            let &row = row_of_id.get(id)?;
+            Some(columns.row(row))
+        })
+        .collect()
+}
+
+/// Checks that every output field is a field of the collection
+///
+/// [`ALL_FIELDS`] selects them all, and with dynamic fields enabled any other
+/// name may be a dynamic key, so it is left to the server.
+fn check_output_fields(fields: &[Field], output_fields: &[String]) -> Result<()> {
+    if fields.iter().any(|f| f.is_dynamic) {
+        return Ok(());
+    }
+    match output_fields
+        .iter()
+        .find(|name| *name != ALL_FIELDS && !fields.iter().any(|f| &&f.name == name))
+    {
+        Some(name) => Err(crate::schema::Error::FieldDoesNotExists(name.clone()).into()),
+        None => Ok(()),
+    }
+}
+
+/// Checks that a grouping search asks for at least one hit per group
+fn check_group_size(search_params: &[KeyValuePair]) -> Result<()> {
+    let Some(group_size) = search_params.iter().find(|p| p.key == "group_size") else {
+        return Ok(());
+    };
+    match group_size.value.parse::<i64>() {
+        Ok(size) if size >= 1 => Ok(()),
+        _ => Err(SuperError::InvalidParameter(
+            "group_size".to_owned(),
+            format!("{}, must be at least 1", group_size.value),
+        )),
+    }
+}
+
+/// Checks that `radius` and `range_filter` bound a non-empty range for the
+/// metric of the search, when all three are set
+///
+/// Similarity metrics keep scores in `(radius, range_filter]`, distance metrics
+/// distances in `[range_filter, radius)`.
+fn check_range_bounds(search_params: &[KeyValuePair]) -> Result<()> {
+    let param = |key: &str| search_params.iter().find(|p| p.key == key);
+    let (Some(radius), Some(range_filter)) = (param("radius"), param("range_filter")) else {
+        return Ok(());
+    };
+    let Some(metric) = param("metric_type").and_then(|p| MetricType::from_str(&p.value).ok())
+    else {
+        return Ok(());
+    };
+    let parse = |p: &KeyValuePair| {
+        p.value.parse::<f64>().map_err(|_| {
+            SuperError::InvalidParameter(p.key.clone(), format!("{}, must be a number", p.value))
+        })
+    };
+    let (radius, range_filter) = (parse(radius)?, parse(range_filter)?);
+
+    let message = if metric.higher_is_better() {
+        (range_filter <= radius).then(|| {
+            format!(
+                "{range_filter}, must be greater than radius {radius}: with {metric} a higher \
+                 score is closer and hits score in (radius, range_filter]"
+            )
+        })
+    } else {
+        (range_filter >= radius).then(|| {
+            format!(
+                "{range_filter}, must be less than radius {radius}: with {metric} a lower \
+                 distance is closer and hits lie in [range_filter, radius)"
+            )
+        })
+    };
+    match message {
+        Some(message) => Err(SuperError::InvalidParameter(
+            "range_filter".to_owned(),
+            message,
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Splits the flat search result data returned by the server into one
+/// `SearchResult` per query vector
+///
+/// # Arguments
+///
+/// * `raw_data` - Search result data from the search response
+/// * `elapsed` - Measured round-trip time of the search RPC
+///
+/// # Returns
+///
+/// Vector of search results, one for each query vector
+fn parse_search_results(
+    raw_data: proto::schema::SearchResultData,
+    elapsed: Duration,
+) -> Result<Vec<SearchResult<'static>>> {
+    let mut result = Vec::new();
+    let mut offset = 0;
+    let fields_data = raw_data
+        .fields_data
+        .into_iter()
+        .map(Into::into)
+        .collect::<Vec<FieldColumn>>();
+
+    // Handle case where no IDs are returned (empty search results)
+    // When there are no results, Milvus may return None for ids
+    let Some(ids) = raw_data.ids else {
+        return Ok(vec![]);
+    };
+    let Some(raw_id) = ids.id_field else {
+        return Ok(vec![]);
+    };
+
+    for (nq, k) in raw_data.topks.into_iter().enumerate() {
+        let k = k as usize;
+        let mut score = Vec::new();
+        score.extend_from_slice(&raw_data.scores[offset..offset + k]);
+        let mut result_data = fields_data
+            .iter()
+            .map(FieldColumn::copy_with_metadata)
+            .collect::<Vec<FieldColumn>>();
+        for j in 0..fields_data.len() {
+            for i in offset..offset + k {
+                result_data[j].push(fields_data[j].get(i).ok_or(SuperError::Unexpected(
+                    "out of range while indexing field data".to_owned(),
+                ))?);
             }
+        }
 
-            let id = match raw_id {
-                proto::schema::i_ds::IdField::IntId(ref d) => {
-                    Vec::<Value>::from_iter(d.data[offset..offset + k].iter().map(|&x| x.into()))
-                }
-                proto::schema::i_ds::IdField::StrId(ref d) => Vec::<Value>::from_iter(
-                    d.data[offset..offset + k].iter().map(|x| x.clone().into()),
-                ),
+        let id = match raw_id {
+            proto::schema::i_ds::IdField::IntId(ref d) => {
+                Vec::<Value>::from_iter(d.data[offset..offset + k].iter().map(|&x| x.into()))
+            }
+            proto::schema::i_ds::IdField::StrId(ref d) => {
+                Vec::<Value>::from_iter(d.data[offset..offset + k].iter().map(|x| x.clone().into()))
+            }
+        };
+
+        result.push(SearchResult {
+            size: k as i64,
+            score,
+            field: result_data,
+            id,
+            stats: SearchStats {
+                all_search_count: raw_data.all_search_count,
+                recall: raw_data.recalls.get(nq).copied(),
+                elapsed,
+            },
+        });
+
+        offset += k;
+    }
+
+    Ok(result)
+}
+
+/// Builds the `search_params` of a search request from its options, adding the
+/// defaults the server expects and the merged `params` entry
+///
+/// # Errors
+///
+/// Returns an error if the group size is invalid
+fn prepare_search_params(options: &SearchOptions) -> Result<Vec<KeyValuePair>> {
+    let mut search_params = options.search_params.clone();
+    if let Some(other_params) = &options.other_params {
+        search_params.extend(other_params.clone());
+    }
+
+    // Add default parameters if not present
+    if !search_params.iter().any(|p| p.key == "topk") {
+        search_params.push(KeyValuePair {
+            key: "topk".to_string(),
+            value: options.limit.to_string(),
+        });
+    }
+
+    if !search_params.iter().any(|p| p.key == "round_decimal") {
+        search_params.push(KeyValuePair {
+            key: "round_decimal".to_string(),
+            value: "-1".to_string(),
+        });
+    }
+
+    if !search_params.iter().any(|p| p.key == "ignore_growing") {
+        search_params.push(KeyValuePair {
+            key: "ignore_growing".to_string(),
+            value: "false".to_string(),
+        });
+    }
+
+    // Add anns_field if specified in options
+    if !options.anns_field.is_empty() && !search_params.iter().any(|p| p.key == "anns_field") {
+        search_params.push(KeyValuePair {
+            key: "anns_field".to_string(),
+            value: options.anns_field[0].clone(),
+        });
+    }
+
+    check_group_size(&search_params)?;
+    check_range_bounds(&search_params)?;
+
+    // Merge all parameters into a single params field (similar to Python's get_params)
+    let merged_params = get_params(&search_params);
+    search_params.push(KeyValuePair {
+        key: "params".to_string(),
+        value: merged_params,
+    });
+
+    Ok(search_params)
+}
+
+/// Calls `f` with the query index and every hit of the flat search result
+/// data, without splitting it into one `SearchResult` per query vector
+///
+/// # Arguments
+///
+/// * `raw_data` - Search result data from the search response
+/// * `f` - Callback, decoding stops at the first error it returns
+fn for_each_search_hit<F>(raw_data: proto::schema::SearchResultData, mut f: F) -> Result<()>
+where
+    F: FnMut(usize, Hit) -> Result<()>,
+{
+    let fields_data = raw_data
+        .fields_data
+        .into_iter()
+        .map(Into::into)
+        .collect::<Vec<FieldColumn>>();
+    let Some(raw_id) = raw_data.ids.and_then(|ids| ids.id_field) else {
+        return Ok(());
+    };
+
+    let mut offset = 0;
+    for (nq, &k) in raw_data.topks.iter().enumerate() {
+        for i in offset..offset + k as usize {
+            let id = match &raw_id {
+                proto::schema::i_ds::IdField::IntId(d) => d.data.get(i).map(|&x| x.into()),
+                proto::schema::i_ds::IdField::StrId(d) => d.data.get(i).map(|x| x.clone().into()),
             };
+            let (Some(id), Some(&score)) = (id, raw_data.scores.get(i)) else {
+                return Err(SuperError::Unexpected(
+                    "out of range while indexing search results".to_owned(),
+                ));
+            };
+            f(
+                nq,
+                Hit {
+                    id,
+                    score,
+                    fields: fields_data.row(i),
+                },
+            )?;
+        }
+        offset += k as usize;
+    }
 
-            result.push(SearchResult {
-                size: k as i64,
-                score,
-                field: result_data,
-                id,
-            });
+    Ok(())
+}
 
-            offset += k;
+/// L2-normalizes a sparse query vector, other values are returned unchanged
+fn normalize_sparse_query(v: Value<'_>) -> Value<'_> {
+    match v {
+        Value::SparseFloatVector(cow) => {
+            let mut row = cow.into_owned();
+            sparse_normalize(&mut row);
+            Value::SparseFloatVector(row.into())
         }
+        v => v,
+    }
+}
+
+/// Builds the query request of `query_impl`, reading with Strong consistency
+/// when `strong` is set and else with the level of `options`, or the
+/// collection's `collection_default`
+///
+/// # Errors
+///
+/// Returns an error if the graceful time of `options` is negative
+fn prepare_query_request(
+    collection_name: String,
+    expr: &str,
+    options: &QueryOptions,
+    strong: bool,
+    collection_default: ConsistencyLevel,
+    session_timestamp: Option<u64>,
+) -> Result<QueryRequest> {
+    let requested = if strong {
+        Some(ConsistencyLevel::Strong)
+    } else {
+        options.consistency_level
+    };
+    let (level, use_default_consistency) = resolve_consistency(requested, collection_default);
+    let graceful_timestamp =
+        graceful_guarantee_timestamp(options.graceful_time_ms, level as i32, SystemTime::now())?;
+    let (guarantee_timestamp, consistency_level, use_default_consistency) =
+        if options.guarantee_timestamp > 0 && !strong {
+            (
+                options.guarantee_timestamp,
+                level as i32,
+                use_default_consistency,
+            )
+        } else if let Some(guarantee_timestamp) = graceful_timestamp {
+            (
+                guarantee_timestamp,
+                ConsistencyLevel::Customized as _,
+                false,
+            )
+        } else {
+            (
+                consistency_timestamp(level, session_timestamp),
+                level as i32,
+                use_default_consistency,
+            )
+        };
+
+    Ok(QueryRequest {
+        base: Some(MsgBase::new(MsgType::Retrieve)),
+        db_name: "".to_string(),
+        collection_name,
+        expr: expr.to_string(),
+        output_fields: options.output_fields.clone(),
+        partition_names: options.partition_names.clone(),
+        travel_timestamp: 0,
+        guarantee_timestamp,
+        query_params: options.query_params.clone(),
+        not_return_all_meta: false,
+        consistency_level,
+        use_default_consistency,
+        expr_template_values: options.expr_template_values.clone(),
+        namespace: Some("".to_string()),
+    })
+}
+
+/// Builds the request of `search_raw` from its prepared `search_params`, with
+/// the consistency level of `options` or else the collection's
+/// `collection_default`
+///
+/// # Errors
+///
+/// Returns an error if the graceful time of `options` is negative or the query
+/// vectors can't be encoded
+fn prepare_search_request(
+    collection_name: String,
+    data: &Vec<Value>,
+    options: SearchOptions,
+    search_params: Vec<KeyValuePair>,
+    collection_default: ConsistencyLevel,
+    session_timestamp: Option<u64>,
+) -> Result<proto::milvus::SearchRequest> {
+    // An explicit guarantee timestamp is only honored by the server with the
+    // customized consistency level
+    let (level, use_default_consistency) =
+        resolve_consistency(options.requested_consistency(), collection_default);
+    let graceful_timestamp =
+        graceful_guarantee_timestamp(options.graceful_time_ms, level as i32, SystemTime::now())?;
+    let (guarantee_timestamp, consistency_level, use_default_consistency) =
+        if options.guarantee_timestamp > 0 {
+            (
+                options.guarantee_timestamp,
+                ConsistencyLevel::Customized as _,
+                false,
+            )
+        } else if let Some(guarantee_timestamp) = graceful_timestamp {
+            (
+                guarantee_timestamp,
+                ConsistencyLevel::Customized as _,
+                false,
+            )
+        } else {
+            (
+                consistency_timestamp(level, session_timestamp),
+                level as i32,
+                use_default_consistency,
+            )
+        };
+
+    Ok(proto::milvus::SearchRequest {
+        base: Some(MsgBase::new(MsgType::Search)),
+        db_name: "".to_string(),
+        collection_name,
+        partition_names: options.partition_names,
+        dsl: options.filter,
+        nq: data.len() as _,
+        placeholder_group: get_place_holder_group(data)?,
+        dsl_type: DslType::BoolExprV1 as _,
+        output_fields: options.output_fields,
+        search_params,
+        travel_timestamp: 0,
+        guarantee_timestamp,
+        not_return_all_meta: false,
+        consistency_level,
+        use_default_consistency,
+        search_by_primary_keys: false,
+        expr_template_values: options.expr_template_values,
+        sub_reqs: vec![],
+        function_score: None,
+        namespace: Some("".to_string()),
+    })
+}
+
+/// Converts a consistency level to the guarantee timestamp of a request, using
+/// the collection's last write timestamp for Session consistency
+fn consistency_timestamp(level: ConsistencyLevel, session_timestamp: Option<u64>) -> u64 {
+    match level {
+        ConsistencyLevel::Strong => STRONG_TIMESTAMP,
+        ConsistencyLevel::Bounded => BOUNDED_TIMESTAMP,
+        ConsistencyLevel::Eventually => EVENTUALLY_TIMESTAMP,
+        ConsistencyLevel::Session => session_timestamp.unwrap_or(EVENTUALLY_TIMESTAMP),
+
+        // This level not works for now
+        ConsistencyLevel::Customized => 0,
+    }
+}
 
-        Ok(result)
+/// Picks the consistency level of a request, the requested level or else the
+/// collection default, and whether the server should apply its own default
+///
+/// `use_default_consistency` is only cleared for a requested level, otherwise
+/// the server ignores it.
+fn resolve_consistency(
+    requested: Option<ConsistencyLevel>,
+    collection_default: ConsistencyLevel,
+) -> (ConsistencyLevel, bool) {
+    match requested {
+        Some(level) => (level, false),
+        None => (collection_default, true),
     }
+}
 
-    /// Extracts the primary key field from a collection
-    ///
-    /// # Arguments
-    ///
-    /// * `collection` - Collection reference
-    ///
-    /// # Returns
-    ///
-    /// Reference to the primary key field
-    fn extract_primary_field<'a>(&self, collection: &'a Collection) -> Result<&'a Field> {
-        collection
-            .fields
-            .iter()
-            .find(|f| f.is_primary_key)
-            .ok_or(Error::Schema(crate::schema::Error::NoPrimaryKey))
+/// Computes the guarantee timestamp of a request that accepts `graceful_time_ms`
+/// of staleness, or `None` when no graceful time is set or the consistency level
+/// is neither Bounded nor Customized
+///
+/// # Errors
+///
+/// Returns an error if the graceful time is negative
+fn graceful_guarantee_timestamp(
+    graceful_time_ms: Option<i64>,
+    consistency_level: i32,
+    now: SystemTime,
+) -> Result<Option<u64>> {
+    let Some(graceful_time_ms) = graceful_time_ms else {
+        return Ok(None);
+    };
+    if graceful_time_ms < 0 {
+        return Err(SuperError::InvalidParameter(
+            "graceful_time_ms".to_owned(),
+            graceful_time_ms.to_string(),
+        ));
+    }
+    if consistency_level != ConsistencyLevel::Bounded as i32
+        && consistency_level != ConsistencyLevel::Customized as i32
+    {
+        return Ok(None);
     }
 
-    /// Packs primary key IDs into a query expression
-    ///
-    /// # Arguments
-    ///
-    /// * `collection` - Collection reference
-    /// * `pks` - Vector of primary key strings
-    ///
-    /// # Returns
-    ///
-    /// Query expression string for the primary keys
-    fn pack_pks_expr(&self, collection: &Collection, pks: Vec<String>) -> Result<String> {
-        let primary_field = self.extract_primary_field(collection)?;
-        let pk_field_name = primary_field.name.clone();
-        let data_type = primary_field.dtype;
+    let now_ms = now
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64);
+    Ok(Some(
+        now_ms.saturating_sub(graceful_time_ms as u64) << LOGICAL_BITS,
+    ))
+}
 
-        if data_type == DataType::VarChar {
-            let ids: Vec<String> = pks.iter().map(|entry| format!("'{}'", entry)).collect();
-            let expr = format!("{pk_field_name} in {:?}", ids);
-            return Ok(expr);
-        } else {
-            let mut ids: Vec<i64> = Vec::new();
-            for (i, entry) in pks.iter().enumerate() {
-                match entry.parse::<i64>() {
-                    Ok(id) => ids.push(id),
-                    Err(_) => {
-                        return Err(SuperError::Unexpected(format!(
-                            "Failed to parse primary key '{}' at index {} as integer",
-                            entry, i
-                        )));
-                    }
-                }
-            }
-            let expr = format!("{pk_field_name} in {:?}", ids);
-            return Ok(expr);
+/// L2-normalizes a dense float query vector, other values are returned unchanged
+///
+/// A zero vector has no direction, it is passed through unchanged.
+fn normalize_dense_query(v: Value<'_>) -> Value<'_> {
+    match v {
+        Value::FloatArray(cow) => {
+            let mut row = cow.into_owned();
+            normalize_l2(&mut row);
+            Value::FloatArray(row.into())
         }
+        v => v,
     }
+}
 
-    /// Gets specific entities by their IDs
-    ///
-    /// This operation retrieves entities from a collection using their primary key IDs.
-    /// It supports both integer and string primary keys, similar to the Python Milvus client's get() method.
-    ///
-    /// # Arguments
-    ///
-    /// * `collection_name` - The name of the collection to query
-    /// * `ids` - The primary key IDs to retrieve. Can be either integer or string IDs
-    /// * `options` - Optional configuration for the query operation including output fields, partition names, etc.
-    ///
-    /// # Returns
-    ///
-    /// Returns a `Result` containing a vector of `FieldColumn` representing the queried entities.
-    /// If no IDs are provided, returns an empty vector.
-    ///
-    /// # Example
-    ///
-    /// ```rust,ignore
-    /// use milvus_sdk_rust::query::{GetOptions, IdType};
-    ///
-    /// // Get by integer IDs
-    /// let int_ids = IdType::Int64(vec![1, 2, 3]);
-    /// let results = client.get("my_collection", int_ids, None).await?;
-    ///
-    /// // Get by string IDs
-    /// let string_ids = IdType::VarChar(vec!["id1".to_string(), "id2".to_string()]);
-    /// let options = GetOptions::new().output_fields(vec!["id".to_string(), "title".to_string()]);
-    /// let results = client.get("my_collection", string_ids, Some(options)).await?;
-    /// ```
-    pub async fn get<S>(
-        &self,
-        collection_name: S,
-        ids: IdType,
-        options: Option<GetOptions>,
-    ) -> Result<Vec<FieldColumn>>
-    where
-        S: Into<String>,
-    {
-        let collection_name = collection_name.into();
-        let ids = match ids {
-            IdType::Int64(ids_int64) => ids_int64
-                .iter()
-                .map(|x| x.to_string())
-                .collect::<Vec<String>>(),
-            IdType::VarChar(ids_string) => ids_string,
-        };
+/// Finds the sparse field a BM25 function of the schema fills from `text_field`
+///
+/// # Errors
+///
+/// Returns an error if no BM25 function takes `text_field` as input
+fn bm25_output_field(schema: &CollectionSchema, text_field: &str) -> Result<String> {
+    schema
+        .functions
+        .iter()
+        .find(|f| {
+            f.r#type == FunctionType::Bm25 as i32
+                && f.input_field_names.iter().any(|name| name == text_field)
+        })
+        .and_then(|f| f.output_field_names.first().cloned())
+        .ok_or_else(|| {
+            SuperError::InvalidParameter(
+                "text_field".to_owned(),
+                format!("no BM25 function takes {text_field:?} as input"),
+            )
+        })
+}
 
-        let ids: Vec<String> = ids.into_iter().map(|x| x.into()).collect();
+/// Unwraps the hits of a search that was issued with a single query vector
+///
+/// # Errors
+///
+/// Returns an error if there is not exactly one result group
+fn single_result_group(results: Vec<SearchResult>) -> Result<Vec<Hit>> {
+    match results.as_slice() {
+        [result] => Ok(result.hits()),
+        _ => Err(SuperError::Unexpected(format!(
+            "expected 1 search result group, got {}",
+            results.len()
+        ))),
+    }
+}
 
-        //If ids is empty,return an empty vec
-        if ids.is_empty() {
-            return Ok(vec![]);
+/// Checks that the query vectors can be used to search a field of the given type
+///
+/// Sparse fields also accept text queries, which are turned into sparse
+/// vectors on the server by a BM25 function.
+///
+/// # Arguments
+///
+/// * `field_name` - Name of the searched field, used in the error message
+/// * `dtype` - Data type of the searched field
+/// * `vectors` - Query vectors
+///
+/// # Errors
+///
+/// Returns an error if any query vector does not match the field type
+fn check_query_vectors(field_name: &str, dtype: DataType, vectors: &[Value]) -> Result<()> {
+    let matches = |v: &Value| match dtype {
+        DataType::FloatVector => matches!(v, Value::FloatArray(_)),
+        DataType::BinaryVector => matches!(v, Value::Binary(_)),
+        DataType::Float16Vector => matches!(v, Value::Float16Array(_)),
+        DataType::SparseFloatVector => {
+            matches!(v, Value::SparseFloatVector(_) | Value::String(_))
         }
+        _ => true,
+    };
 
-        let collection = self.collection_cache.get(&collection_name).await?;
-        let expr = self.pack_pks_expr(&collection, ids)?;
-        let option = options.unwrap_or_default();
-        Ok(self.query(collection_name, expr.as_str(), &option).await?)
+    if vectors.iter().all(matches) {
+        Ok(())
+    } else {
+        Err(SuperError::from(crate::collection::Error::IllegalType(
+            field_name.to_owned(),
+            vec![dtype],
+        )))
+    }
+}
+
+fn check_self_ids(ids: &[Value], num_queries: usize) -> Result<()> {
+    if ids.len() == num_queries {
+        Ok(())
+    } else {
+        Err(SuperError::InvalidParameter(
+            "exclude_self".to_owned(),
+            format!("{} ids for {} query vectors", ids.len(), num_queries),
+        ))
+    }
+}
+
+/// Removes from each group the hit whose primary key is the group's query ID
+fn drop_self_matches(groups: Vec<Vec<Hit>>, ids: &[Value]) -> Vec<Vec<Hit>> {
+    let same_id = |a: &Value, b: &Value| match (a, b) {
+        (Value::Long(a), Value::Long(b)) => a == b,
+        (Value::String(a), Value::String(b)) => a == b,
+        _ => false,
+    };
+
+    groups
+        .into_iter()
+        .zip(ids)
+        .map(|(group, id)| {
+            group
+                .into_iter()
+                .filter(|hit| !same_id(&hit.id, id))
+                .collect()
+        })
+        .collect()
+}
+
+fn check_sparse_queries(vectors: &[SparseVector]) -> Result<()> {
+    match vectors.iter().position(|v| v.is_empty()) {
+        Some(i) => Err(SuperError::SparseVectorError(format!(
+            "query vector {} is empty",
+            i
+        ))),
+        None => Ok(()),
     }
 }
 
@@ -1772,6 +3368,82 @@ pub fn get_place_holder_group(vectors: &Vec<Value>) -> Result<Vec<u8>> {
     return Ok(buf.to_vec());
 }
 
+/// Serializes query vectors searching `field` to a placeholder group
+///
+/// Unlike [`get_place_holder_group`], the placeholder type follows the data
+/// type of the field: sparse fields serialize their rows with
+/// [`sparse_vectors_to_proto`], and half-precision fields accept raw bytes.
+/// Values that can not search the field are rejected before the request is sent.
+fn get_field_place_holder_group(vectors: &[Value], field: &Field) -> Result<Vec<u8>> {
+    let group = PlaceholderGroup {
+        placeholders: vec![get_field_place_holder_value(vectors, field)?],
+    };
+    Ok(group.encode_to_vec())
+}
+
+fn get_field_place_holder_value(vectors: &[Value], field: &Field) -> Result<PlaceholderValue> {
+    let mismatch = |v: &Value| {
+        SuperError::InvalidParameter(
+            "data".to_owned(),
+            format!(
+                "{:?} query can not search field {} of type {:?}",
+                v.data_type(),
+                field.name,
+                field.dtype
+            ),
+        )
+    };
+    let place_holder = |r#type: PlaceholderType, values: Vec<Vec<u8>>| PlaceholderValue {
+        tag: "$0".to_string(),
+        r#type: r#type as _,
+        values,
+    };
+
+    match field.dtype {
+        // Text queries against the output of a BM25 function
+        DataType::SparseFloatVector
+            if !vectors.is_empty() && vectors.iter().all(|v| matches!(v, Value::String(_))) =>
+        {
+            get_place_holder_value(vectors)
+        }
+        DataType::SparseFloatVector => {
+            let rows = vectors
+                .iter()
+                .map(|v| match v {
+                    Value::SparseFloatVector(row) => Ok(row.to_vec()),
+                    v => Err(mismatch(v)),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(place_holder(
+                PlaceholderType::SparseFloatVector,
+                sparse_vectors_to_proto(rows).contents,
+            ))
+        }
+        DataType::Float16Vector | DataType::BFloat16Vector => {
+            let r#type = if field.dtype == DataType::Float16Vector {
+                PlaceholderType::Float16Vector
+            } else {
+                PlaceholderType::BFloat16Vector
+            };
+            let values = vectors
+                .iter()
+                .map(|v| match v {
+                    Value::Float16Array(d) | Value::Binary(d) => Ok(d.to_vec()),
+                    v => Err(mismatch(v)),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(place_holder(r#type, values))
+        }
+        DataType::FloatVector | DataType::BinaryVector => {
+            if let Some(v) = vectors.iter().find(|v| v.data_type() != field.dtype) {
+                return Err(mismatch(v));
+            }
+            get_place_holder_value(vectors)
+        }
+        _ => get_place_holder_value(vectors),
+    }
+}
+
 /// Converts vector data to placeholder value format
 ///
 /// This function creates a PlaceholderValue from vector data, handling
@@ -1788,7 +3460,7 @@ pub fn get_place_holder_group(vectors: &Vec<Value>) -> Result<Vec<u8>> {
 /// # Errors
 ///
 /// Returns an error if the vector data is invalid or unsupported
-fn get_place_holder_value(vectors: &Vec<Value>) -> Result<PlaceholderValue> {
+fn get_place_holder_value(vectors: &[Value]) -> Result<PlaceholderValue> {
     let mut place_holder = PlaceholderValue {
         tag: "$0".to_string(),
         r#type: PlaceholderType::None as _,
@@ -1806,6 +3478,7 @@ fn get_place_holder_value(vectors: &Vec<Value>) -> Result<PlaceholderValue> {
         Value::SparseFloatVector(_) => {
             place_holder.r#type = PlaceholderType::SparseFloatVector as _
         }
+        Value::Float16Array(_) => place_holder.r#type = PlaceholderType::Float16Vector as _,
         _ => {
             return Err(SuperError::from(crate::collection::Error::IllegalType(
                 "place holder".to_string(),
@@ -1814,6 +3487,7 @@ fn get_place_holder_value(vectors: &Vec<Value>) -> Result<PlaceholderValue> {
                     DataType::FloatVector,
                     DataType::VarChar,
                     DataType::SparseFloatVector,
+                    DataType::Float16Vector,
                 ],
             )));
         }
@@ -1839,6 +3513,9 @@ fn get_place_holder_value(vectors: &Vec<Value>) -> Result<PlaceholderValue> {
                 let bytes = sparse_row_to_bytes(&mut row);
                 place_holder.values.push(bytes)
             }
+            (Value::Float16Array(d), Value::Float16Array(_)) => {
+                place_holder.values.push(d.to_vec())
+            }
             _ => {
                 return Err(SuperError::from(crate::collection::Error::IllegalType(
                     "place holder".to_string(),
@@ -1847,6 +3524,7 @@ fn get_place_holder_value(vectors: &Vec<Value>) -> Result<PlaceholderValue> {
                         DataType::FloatVector,
                         DataType::VarChar,
                         DataType::SparseFloatVector,
+                        DataType::Float16Vector,
                     ],
                 )));
             }
@@ -1980,3 +3658,623 @@ fn prepare_rank_params(
 
     final_rank_params
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::fixtures::field;
+
+    fn search_result_data(topks: Vec<i64>) -> proto::schema::SearchResultData {
+        let total = topks.iter().sum::<i64>();
+        let year = crate::schema::FieldSchema::new_int64("year", "");
+        proto::schema::SearchResultData {
+            num_queries: topks.len() as i64,
+            topks,
+            scores: (0..total).map(|i| i as f32).collect(),
+            ids: Some(proto::schema::IDs {
+                id_field: Some(proto::schema::i_ds::IdField::IntId(
+                    proto::schema::LongArray {
+                        data: (100..100 + total).collect(),
+                    },
+                )),
+            }),
+            fields_data: vec![
+                FieldColumn::new(&year, (2000..2000 + total).collect::<Vec<_>>()).into(),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_for_each_search_hit() {
+        let mut hits = Vec::new();
+        for_each_search_hit(search_result_data(vec![2, 0, 3]), |group, hit| {
+            hits.push((group, hit));
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(hits.len(), 5);
+        let groups: Vec<usize> = hits.iter().map(|(group, _)| *group).collect();
+        assert_eq!(groups, vec![0, 0, 2, 2, 2]);
+        let (group, hit) = &hits[3];
+        assert_eq!(*group, 2);
+        assert_eq!(hit.score, 3.0);
+        assert!(matches!(hit.id, Value::Long(103)));
+        assert!(matches!(hit.fields.get("year"), Some(Value::Long(2003))));
+
+        let parsed =
+            parse_search_results(search_result_data(vec![2, 0, 3]), Duration::ZERO).unwrap();
+        let total: usize = parsed.iter().map(|r| r.hits().len()).sum();
+        assert_eq!(total, hits.len());
+    }
+
+    #[test]
+    fn test_for_each_search_hit_aborts() {
+        let mut calls = 0;
+        let result = for_each_search_hit(search_result_data(vec![3, 3]), |_, _| {
+            calls += 1;
+            if calls == 2 {
+                return Err(SuperError::Unexpected("stop".to_owned()));
+            }
+            Ok(())
+        });
+
+        assert!(matches!(result, Err(SuperError::Unexpected(msg)) if msg == "stop"));
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn test_search_request_builder() {
+        let request = SearchRequest::new()
+            .collection("books")
+            .data(vec![vec![0.1f32, 0.2].into(), vec![0.3f32, 0.4].into()])
+            .top_k(5)
+            .filter("year > 2000")
+            .output_fields(vec!["title".to_owned()])
+            .params([("metric_type", "IP"), ("nprobe", "16")])
+            .consistency(ConsistencyLevel::Session)
+            .partitions(vec!["2024".to_owned()]);
+
+        assert_eq!(request.collection_name, "books");
+        assert_eq!(request.data.len(), 2);
+        let options = &request.options;
+        assert_eq!(options.filter, "year > 2000");
+        assert_eq!(options.output_fields, vec!["title"]);
+        assert_eq!(options.partition_names, vec!["2024"]);
+        assert_eq!(
+            options.requested_consistency(),
+            Some(ConsistencyLevel::Session)
+        );
+
+        let search_params = prepare_search_params(options).unwrap();
+        let param = |key: &str| extract_param(&search_params, key, "");
+        assert_eq!(param("topk"), "5");
+        assert_eq!(param("metric_type"), "IP");
+        assert_eq!(param("nprobe"), "16");
+        let params: serde_json::Value = serde_json::from_str(&param("params")).unwrap();
+        assert_eq!(params["nprobe"], 16);
+        assert_eq!(params["topk"], 5);
+    }
+
+    #[test]
+    fn test_search_options_metric_type() {
+        let options = SearchOptions::new()
+            .add_param("metric_type", "L2")
+            .metric_type(MetricType::IP)
+            .metric_type(MetricType::COSINE);
+        let metrics: Vec<&str> = options
+            .search_params
+            .iter()
+            .filter(|p| p.key == "metric_type")
+            .map(|p| p.value.as_str())
+            .collect();
+        assert_eq!(metrics, vec!["COSINE"]);
+
+        let params: serde_json::Value =
+            serde_json::from_str(&get_params(&options.search_params)).unwrap();
+        assert_eq!(params["metric_type"], "COSINE");
+    }
+
+    #[test]
+    fn test_check_group_size() {
+        let options = SearchOptions::new().group_by_field("author").group_size(2);
+        assert!(check_group_size(&options.search_params).is_ok());
+        assert!(check_group_size(&SearchOptions::new().search_params).is_ok());
+
+        let options = SearchOptions::new().group_by_field("author").group_size(0);
+        assert!(matches!(
+            check_group_size(&options.search_params),
+            Err(SuperError::InvalidParameter(param, _)) if param == "group_size"
+        ));
+    }
+
+    #[test]
+    fn test_check_range_bounds() {
+        let bounds = |metric: MetricType, radius: f32, range_filter: f32| {
+            let options = SearchOptions::new()
+                .metric_type(metric)
+                .radius(radius)
+                .range_filter(range_filter);
+            check_range_bounds(&options.search_params)
+        };
+        let rejected = |result: Result<()>| matches!(result, Err(SuperError::InvalidParameter(param, _)) if param == "range_filter");
+
+        // Similarity metrics keep scores in (radius, range_filter]
+        for metric in [MetricType::IP, MetricType::COSINE] {
+            assert!(bounds(metric, 0.5, 0.9).is_ok());
+            assert!(rejected(bounds(metric, 0.9, 0.5)));
+            assert!(rejected(bounds(metric, 0.5, 0.5)));
+        }
+
+        // Distance metrics keep distances in [range_filter, radius)
+        assert!(bounds(MetricType::L2, 10.0, 1.0).is_ok());
+        assert!(rejected(bounds(MetricType::L2, 1.0, 10.0)));
+
+        // Nothing to check without the metric or one of the bounds
+        let options = SearchOptions::new().radius(1.0).range_filter(10.0);
+        assert!(check_range_bounds(&options.search_params).is_ok());
+        let options = SearchOptions::new().metric_type(MetricType::IP).radius(0.9);
+        assert!(check_range_bounds(&options.search_params).is_ok());
+
+        let options = SearchOptions::new()
+            .metric_type(MetricType::IP)
+            .radius(0.9)
+            .range_filter(0.5);
+        assert!(rejected(prepare_search_params(&options).map(|_| ())));
+    }
+
+    #[test]
+    fn test_search_params_presets() {
+        let param = |params: Vec<KeyValuePair>, key: &str| -> usize {
+            params
+                .iter()
+                .find(|p| p.key == key)
+                .map(|p| p.value.parse().unwrap())
+                .unwrap()
+        };
+
+        let hnsw = IndexConfig::Hnsw {
+            m: 16,
+            ef_construction: 200,
+        };
+        let high = param(SearchParams::high_recall().for_index(&hnsw, 10), "ef");
+        let low = param(SearchParams::low_latency().for_index(&hnsw, 10), "ef");
+        assert!(high > low, "{high} <= {low}");
+        assert!(low >= 10);
+        assert_eq!(
+            param(SearchParams::low_latency().for_index(&hnsw, 100), "ef"),
+            100
+        );
+
+        let ivf = IndexConfig::IvfFlat { nlist: 1024 };
+        let high = param(SearchParams::high_recall().for_index(&ivf, 10), "nprobe");
+        let low = param(SearchParams::low_latency().for_index(&ivf, 10), "nprobe");
+        assert!(high > low, "{high} <= {low}");
+        assert!(low >= 1);
+
+        assert!(
+            SearchParams::high_recall()
+                .for_index(&IndexConfig::Flat, 10)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_weighted_ranker_validate() {
+        assert!(WeightedRanker::new(vec![0.7, 0.3]).validate(2).is_ok());
+        assert!(RrfRanker::new(60.0).validate(3).is_ok());
+
+        let err = WeightedRanker::new(vec![0.5, 0.3, 0.2])
+            .validate(2)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            SuperError::InvalidParameter(param, reason)
+                if param == "weights" && reason.contains("3 weights") && reason.contains("2 sub-requests")
+        ));
+
+        assert!(matches!(
+            WeightedRanker::new(vec![0.5, -0.1]).validate(2),
+            Err(SuperError::InvalidParameter(param, _)) if param == "weights"
+        ));
+    }
+
+    #[test]
+    fn test_rrf_ranker_k_range() {
+        assert!(RrfRanker::try_new(1.0).is_ok());
+        assert!(RrfRanker::try_new(16384.0).is_ok());
+        for k in [0.0, -5.0, 16385.0, f64::NAN] {
+            assert!(matches!(
+                RrfRanker::try_new(k),
+                Err(SuperError::InvalidParameter(param, _)) if param == "k"
+            ));
+        }
+        assert!(RrfRanker::new(0.0).validate(2).is_err());
+
+        let ranker = RrfRanker::default();
+        assert_eq!(ranker.k, 60.0);
+        assert!(
+            ranker
+                .get_params()
+                .iter()
+                .any(|p| p.key == "k" && p.value == "60")
+        );
+    }
+
+    fn hit(id: i64, score: f32) -> Hit {
+        Hit {
+            id: Value::Long(id),
+            score,
+            fields: RowMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_drop_self_matches() {
+        let groups = vec![
+            vec![hit(1, 1.0), hit(2, 0.99)],
+            vec![hit(1, 0.99), hit(2, 1.0), hit(3, 0.97)],
+        ];
+        let ids = [Value::Long(1), Value::Long(2)];
+        assert!(check_self_ids(&ids, 2).is_ok());
+        assert!(check_self_ids(&ids, 3).is_err());
+
+        let groups = drop_self_matches(groups, &ids);
+        let ids: Vec<Vec<i64>> = groups
+            .iter()
+            .map(|g| {
+                g.iter()
+                    .map(|h| match h.id {
+                        Value::Long(id) => id,
+                        _ => unreachable!(),
+                    })
+                    .collect()
+            })
+            .collect();
+        assert_eq!(ids, vec![vec![2], vec![1, 3]]);
+    }
+
+    #[test]
+    fn test_check_sparse_queries() {
+        assert!(check_sparse_queries(&[vec![(1, 0.5)], vec![(2, 1.0), (4, 0.1)]]).is_ok());
+        assert!(matches!(
+            check_sparse_queries(&[vec![(1, 0.5)], vec![]]),
+            Err(SuperError::SparseVectorError(msg)) if msg == "query vector 1 is empty"
+        ));
+    }
+
+    fn search_result(ids: Vec<i64>, scores: Vec<f32>) -> SearchResult<'static> {
+        SearchResult {
+            size: ids.len() as i64,
+            id: ids.into_iter().map(Value::from).collect(),
+            field: Vec::new(),
+            score: scores,
+            stats: SearchStats::default(),
+        }
+    }
+
+    #[test]
+    fn test_search_result_into_iter() {
+        let result = search_result(vec![7, 3], vec![0.9, 0.4]);
+
+        let mut seen = Vec::new();
+        for hit in &result {
+            seen.push((hit.id, hit.score));
+        }
+        for hit in result {
+            seen.push((hit.id, hit.score));
+        }
+
+        assert_eq!(seen.len(), 4);
+        assert!(matches!(seen[0], (Value::Long(7), 0.9)));
+        assert!(matches!(seen[1], (Value::Long(3), 0.4)));
+        assert!(matches!(seen[3], (Value::Long(3), 0.4)));
+    }
+
+    fn placeholder_type(v: QueryVector) -> i32 {
+        get_place_holder_value(&[v.into()]).unwrap().r#type
+    }
+
+    #[test]
+    fn test_query_vector_placeholder_type() {
+        assert_eq!(
+            placeholder_type(QueryVector::FloatVector(vec![0.1, 0.2])),
+            PlaceholderType::FloatVector as i32
+        );
+        assert_eq!(
+            placeholder_type(QueryVector::BinaryVector(vec![0b1010_1010])),
+            PlaceholderType::BinaryVector as i32
+        );
+        assert_eq!(
+            placeholder_type(QueryVector::SparseVector(vec![(3, 0.5), (7, 1.0)])),
+            PlaceholderType::SparseFloatVector as i32
+        );
+        assert_eq!(
+            placeholder_type(QueryVector::Float16Vector(vec![0x00, 0x3c, 0x00, 0x40])),
+            PlaceholderType::Float16Vector as i32
+        );
+    }
+
+    #[test]
+    fn test_query_vector_placeholder_bytes() {
+        let ph = get_place_holder_value(&[QueryVector::FloatVector(vec![1.0]).into()]).unwrap();
+        assert_eq!(ph.values, vec![1.0f32.to_le_bytes().to_vec()]);
+
+        let ph =
+            get_place_holder_value(&[QueryVector::SparseVector(vec![(2, 0.5)]).into()]).unwrap();
+        let mut expected = 2u32.to_le_bytes().to_vec();
+        expected.extend_from_slice(&0.5f32.to_le_bytes());
+        assert_eq!(ph.values, vec![expected]);
+    }
+
+    #[test]
+    fn test_field_placeholder_follows_field_type() {
+        let sparse = field("sparse", DataType::SparseFloatVector);
+        let rows: Vec<SparseVector> = vec![vec![(7, 1.0), (2, 0.5)], vec![(1, 0.25)]];
+        let data: Vec<Value> = rows.iter().cloned().map(Value::from).collect();
+        let ph = get_field_place_holder_value(&data, &sparse).unwrap();
+        assert_eq!(ph.r#type, PlaceholderType::SparseFloatVector as i32);
+        assert_eq!(ph.values, sparse_vectors_to_proto(rows).contents);
+
+        let ph = get_field_place_holder_value(&[Value::from("vector database")], &sparse).unwrap();
+        assert_eq!(ph.r#type, PlaceholderType::VarChar as i32);
+
+        let err = get_field_place_holder_value(&[Value::from(vec![0.1f32, 0.2])], &sparse);
+        assert!(matches!(err, Err(SuperError::InvalidParameter(param, _)) if param == "data"));
+
+        let dense = field("dense", DataType::FloatVector);
+        let err = get_field_place_holder_value(&[Value::from(vec![(1u32, 0.5f32)])], &dense);
+        assert!(matches!(err, Err(SuperError::InvalidParameter(param, _)) if param == "data"));
+
+        let half = field("half", DataType::Float16Vector);
+        let ph =
+            get_field_place_holder_value(&[Value::from(vec![0x00u8, 0x3c, 0x00, 0x40])], &half)
+                .unwrap();
+        assert_eq!(ph.r#type, PlaceholderType::Float16Vector as i32);
+    }
+
+    #[test]
+    fn test_normalize_dense_query_placeholder_bytes() {
+        let bytes = |v: &[f32]| v.iter().flat_map(|x| x.to_le_bytes()).collect::<Vec<u8>>();
+        let data = vec![
+            Value::from(vec![3.0f32, 4.0]),
+            Value::from(vec![0.0f32, 0.0]),
+        ];
+        let data: Vec<Value> = data.into_iter().map(normalize_dense_query).collect();
+
+        let ph = get_place_holder_value(&data).unwrap();
+        assert_eq!(ph.values, vec![bytes(&[0.6, 0.8]), bytes(&[0.0, 0.0])]);
+
+        let sparse = normalize_dense_query(Value::from(vec![(1u32, 3.0f32)]));
+        assert!(matches!(sparse, Value::SparseFloatVector(v) if v.as_ref() == [(1, 3.0)]));
+        assert!(!SearchOptions::new().normalize_query);
+        assert!(SearchOptions::new().normalize_query(true).normalize_query);
+    }
+
+    #[test]
+    fn test_graceful_guarantee_timestamp() {
+        let now = UNIX_EPOCH + Duration::from_millis(1_700_000_000_000);
+        let customized = ConsistencyLevel::Customized as i32;
+
+        let ts = graceful_guarantee_timestamp(Some(5_000), customized, now)
+            .unwrap()
+            .unwrap();
+        assert_eq!(ts >> LOGICAL_BITS, 1_699_999_995_000);
+        assert_eq!(ts & ((1 << LOGICAL_BITS) - 1), 0);
+        assert_eq!(
+            graceful_guarantee_timestamp(Some(0), ConsistencyLevel::Bounded as i32, now).unwrap(),
+            Some(1_700_000_000_000 << LOGICAL_BITS)
+        );
+
+        assert_eq!(
+            graceful_guarantee_timestamp(None, customized, now).unwrap(),
+            None
+        );
+        assert_eq!(
+            graceful_guarantee_timestamp(Some(5_000), ConsistencyLevel::Session as i32, now)
+                .unwrap(),
+            None
+        );
+        assert!(matches!(
+            graceful_guarantee_timestamp(Some(-1), customized, now),
+            Err(SuperError::InvalidParameter(param, _)) if param == "graceful_time_ms"
+        ));
+    }
+
+    #[test]
+    fn test_check_output_fields() {
+        let fields = vec![
+            Field {
+                is_primary_key: true,
+                ..field("id", DataType::Int64)
+            },
+            field("title", DataType::VarChar),
+        ];
+        let names = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+
+        assert!(check_output_fields(&fields, &[]).is_ok());
+        assert!(check_output_fields(&fields, &names(&["title"])).is_ok());
+        assert!(check_output_fields(&fields, &names(&[ALL_FIELDS])).is_ok());
+        assert!(matches!(
+            check_output_fields(&fields, &names(&["id", "color"])),
+            Err(SuperError::Schema(crate::schema::Error::FieldDoesNotExists(name))) if name == "color"
+        ));
+
+        let mut dynamic = fields.clone();
+        let mut meta = field("$meta", DataType::Int64);
+        meta.is_dynamic = true;
+        dynamic.push(meta);
+        assert!(check_output_fields(&dynamic, &names(&["color"])).is_ok());
+    }
+
+    #[test]
+    fn test_primary_key_type_ids_expr() {
+        let expr = PrimaryKeyType::Int64
+            .ids_expr("id", &IdType::Int64(vec![1, -2, 3]))
+            .unwrap();
+        assert_eq!(expr, "id in [1,-2,3]");
+
+        let ids = IdType::VarChar(vec!["a".to_owned(), r#"b"c"#.to_owned()]);
+        let expr = PrimaryKeyType::VarChar.ids_expr("pk", &ids).unwrap();
+        assert_eq!(expr, r#"pk in ["a","b\"c"]"#);
+
+        assert!(matches!(
+            PrimaryKeyType::Int64.ids_expr("id", &ids),
+            Err(SuperError::InvalidParameter(param, _)) if param == "ids"
+        ));
+        assert!(
+            PrimaryKeyType::VarChar
+                .ids_expr("pk", &IdType::Int64(vec![1]))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_resolve_consistency() {
+        let session = ConsistencyLevel::Session;
+        assert_eq!(resolve_consistency(None, session), (session, true));
+        assert_eq!(
+            resolve_consistency(Some(ConsistencyLevel::Strong), session),
+            (ConsistencyLevel::Strong, false)
+        );
+
+        assert_eq!(QueryOptions::default().consistency_level, None);
+        assert_eq!(SearchOptions::default().requested_consistency(), None);
+        let options = SearchOptions::default().consistency_level(ConsistencyLevel::Strong);
+        assert_eq!(
+            options.requested_consistency(),
+            Some(ConsistencyLevel::Strong)
+        );
+        let options = SearchOptions::default().add_param("consistency_level", "3");
+        assert_eq!(
+            options.requested_consistency(),
+            Some(ConsistencyLevel::Eventually)
+        );
+    }
+
+    #[test]
+    fn test_prepare_query_request_consistency() {
+        let session = ConsistencyLevel::Session;
+        let request = prepare_query_request(
+            "books".to_owned(),
+            "id > 0",
+            &QueryOptions::default(),
+            false,
+            session,
+            Some(42),
+        )
+        .unwrap();
+        assert_eq!(request.collection_name, "books");
+        assert_eq!(request.consistency_level, session as i32);
+        assert!(request.use_default_consistency);
+        assert_eq!(request.guarantee_timestamp, 42);
+
+        let options = QueryOptions::default().consistency_level(ConsistencyLevel::Strong);
+        let request = prepare_query_request(
+            "books".to_owned(),
+            "id > 0",
+            &options,
+            false,
+            session,
+            Some(42),
+        )
+        .unwrap();
+        assert_eq!(request.consistency_level, ConsistencyLevel::Strong as i32);
+        assert!(!request.use_default_consistency);
+        assert_eq!(request.guarantee_timestamp, STRONG_TIMESTAMP);
+
+        let options = QueryOptions::default().guarantee_timestamp(7);
+        let request =
+            prepare_query_request("books".to_owned(), "", &options, true, session, Some(42))
+                .unwrap();
+        assert_eq!(request.consistency_level, ConsistencyLevel::Strong as i32);
+        assert_eq!(request.guarantee_timestamp, STRONG_TIMESTAMP);
+    }
+
+    #[test]
+    fn test_prepare_search_request_consistency() {
+        let bounded = ConsistencyLevel::Bounded;
+        let data: Vec<Value> = vec![vec![0.1f32, 0.2].into()];
+        let request = |options: SearchOptions| {
+            let search_params = prepare_search_params(&options).unwrap();
+            prepare_search_request(
+                "books".to_owned(),
+                &data,
+                options,
+                search_params,
+                bounded,
+                None,
+            )
+            .unwrap()
+        };
+
+        let default = request(SearchOptions::default());
+        assert_eq!(default.nq, 1);
+        assert_eq!(default.consistency_level, bounded as i32);
+        assert!(default.use_default_consistency);
+        assert_eq!(default.guarantee_timestamp, BOUNDED_TIMESTAMP);
+
+        let strong = request(SearchOptions::default().consistency_level(ConsistencyLevel::Strong));
+        assert_eq!(strong.consistency_level, ConsistencyLevel::Strong as i32);
+        assert!(!strong.use_default_consistency);
+        assert_eq!(strong.guarantee_timestamp, STRONG_TIMESTAMP);
+    }
+
+    #[test]
+    fn test_bm25_output_field() {
+        use crate::schema::{CollectionSchemaBuilder, FieldSchema};
+
+        let schema = CollectionSchemaBuilder::new("docs", "")
+            .add_field(FieldSchema::new_primary_int64("id", "", true))
+            .add_field(FieldSchema::new_varchar("text", "", 1024).with_analyzer())
+            .add_field(FieldSchema::new_varchar("title", "", 256))
+            .add_field(FieldSchema::new_sparse_float_vector("sparse", ""))
+            .add_bm25_function("text_bm25", "text", "sparse")
+            .build()
+            .unwrap();
+
+        assert_eq!(bm25_output_field(&schema, "text").unwrap(), "sparse");
+        assert!(matches!(
+            bm25_output_field(&schema, "title"),
+            Err(SuperError::InvalidParameter(param, _)) if param == "text_field"
+        ));
+    }
+
+    #[test]
+    fn test_align_rows_to_ids() {
+        let id_schema = crate::schema::FieldSchema::new_primary_int64("id", "", false);
+        let age_schema = crate::schema::FieldSchema::new_int64("age", "");
+        // server order differs from request order
+        let columns = vec![
+            FieldColumn::new(&id_schema, vec![1i64, 3]),
+            FieldColumn::new(&age_schema, vec![10i64, 30]),
+        ];
+        let ids = vec!["3".to_string(), "1".to_string(), "99".to_string()];
+
+        let rows = align_rows_to_ids("id", &ids, &columns);
+
+        assert_eq!(rows.len(), 3);
+        let row3 = rows[0].as_ref().unwrap();
+        assert!(matches!(row3["id"], Value::Long(3)));
+        assert!(matches!(row3["age"], Value::Long(30)));
+        let row1 = rows[1].as_ref().unwrap();
+        assert!(matches!(row1["id"], Value::Long(1)));
+        assert!(matches!(row1["age"], Value::Long(10)));
+        assert!(rows[2].is_none());
+    }
+
+    #[test]
+    fn test_single_result_group() {
+        let hits = single_result_group(vec![search_result(vec![1, 2], vec![0.9, 0.5])]).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert!(matches!(hits[0].id, Value::Long(1)));
+        assert_eq!(hits[1].score, 0.5);
+    }
+
+    #[test]
+    fn test_single_result_group_empty() {
+        let err = single_result_group(Vec::new()).unwrap_err();
+        assert!(matches!(err, SuperError::Unexpected(_)));
+    }
+}
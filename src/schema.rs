@@ -16,18 +16,24 @@
 
 use crate::error::Result;
 use crate::proto::schema::FieldState;
-use crate::{error, proto::schema::FunctionSchema};
+use crate::{
+    error,
+    proto::schema::{FunctionSchema, FunctionType},
+};
 use prost::alloc::vec::Vec;
 use prost::encoding::bool;
 use thiserror::Error as ThisError;
 
-use crate::proto::{
-    common::KeyValuePair,
-    schema::{self, DataType},
-};
+use crate::proto::{common::KeyValuePair, schema};
 
+/// Type of a field, the protocol enum converts to and from its `i32` wire
+/// value with `as i32` and `DataType::try_from`.
+pub use crate::proto::schema::DataType;
 pub use crate::proto::schema::FieldData;
 
+/// Name of the hidden field the server uses to store dynamic fields
+pub(crate) const DYNAMIC_FIELD_NAME: &str = "$meta";
+
 pub trait Schema {
     // fn name(&self) -> &str;
     // fn description(&self) -> &str;
@@ -156,8 +162,14 @@ pub struct FieldSchema {
     pub dim: i64,                 // only for BinaryVector and FloatVector
     pub max_length: i32,          // only for VarChar
     pub enable_analyzer: bool,    // only for VarChar - enables text analysis for BM25
+    pub enable_match: bool,       // only for VarChar - enables TEXT_MATCH filtering
     pub is_function_output: bool, // true for fields generated by functions (e.g., sparse vectors from BM25)
     pub enable_bm25_stats: bool,  // only for SparseFloatVector - enables BM25 stats collection
+    pub is_partition_key: bool,
+    pub nullable: bool,
+    /// Tokenizer and filters of the analyzer, only for VarChar with the analyzer
+    /// enabled. The server default analyzer if `None`.
+    pub analyzer_params: Option<serde_json::Value>,
 }
 
 impl FieldSchema {
@@ -172,8 +184,12 @@ impl FieldSchema {
             dim: 0,
             max_length: 0,
             enable_analyzer: false,
+            enable_match: false,
+            analyzer_params: None,
             is_function_output: false,
             enable_bm25_stats: false,
+            is_partition_key: false,
+            nullable: false,
         }
     }
 }
@@ -207,6 +223,19 @@ impl From<schema::FieldSchema> for FieldSchema {
             .and_then(|x| x.value.parse().ok())
             .unwrap_or(false);
 
+        let enable_match: bool = fld
+            .type_params
+            .iter()
+            .find(|k| &k.key == "enable_match")
+            .and_then(|x| x.value.parse().ok())
+            .unwrap_or(false);
+
+        let analyzer_params = fld
+            .type_params
+            .iter()
+            .find(|k| &k.key == "analyzer_params")
+            .and_then(|x| serde_json::from_str(&x.value).ok());
+
         let enable_bm25_stats: bool = fld
             .type_params
             .iter()
@@ -214,7 +243,8 @@ impl From<schema::FieldSchema> for FieldSchema {
             .and_then(|x| x.value.parse().ok())
             .unwrap_or(false);
 
-        let dtype = DataType::from_i32(fld.data_type).unwrap();
+        // Types added by newer servers are not known to this client
+        let dtype = DataType::try_from(fld.data_type).unwrap_or(DataType::None);
 
         FieldSchema {
             name: fld.name,
@@ -230,8 +260,12 @@ impl From<schema::FieldSchema> for FieldSchema {
                 }) as _,
             dim,
             enable_analyzer,
+            enable_match,
+            analyzer_params,
             is_function_output: fld.is_function_output,
             enable_bm25_stats,
+            is_partition_key: fld.is_partition_key,
+            nullable: fld.nullable,
         }
     }
 }
@@ -248,8 +282,12 @@ impl FieldSchema {
             dim: 1,
             max_length: 0,
             enable_analyzer: false,
+            enable_match: false,
+            analyzer_params: None,
             is_function_output: false,
             enable_bm25_stats: false,
+            is_partition_key: false,
+            nullable: false,
         }
     }
 
@@ -264,8 +302,12 @@ impl FieldSchema {
             dim: 1,
             max_length: 0,
             enable_analyzer: false,
+            enable_match: false,
+            analyzer_params: None,
             is_function_output: false,
             enable_bm25_stats: false,
+            is_partition_key: false,
+            nullable: false,
         }
     }
 
@@ -280,8 +322,12 @@ impl FieldSchema {
             dim: 1,
             max_length: 0,
             enable_analyzer: false,
+            enable_match: false,
+            analyzer_params: None,
             is_function_output: false,
             enable_bm25_stats: false,
+            is_partition_key: false,
+            nullable: false,
         }
     }
 
@@ -296,8 +342,12 @@ impl FieldSchema {
             dim: 1,
             max_length: 0,
             enable_analyzer: false,
+            enable_match: false,
+            analyzer_params: None,
             is_function_output: false,
             enable_bm25_stats: false,
+            is_partition_key: false,
+            nullable: false,
         }
     }
 
@@ -312,8 +362,12 @@ impl FieldSchema {
             dim: 1,
             max_length: 0,
             enable_analyzer: false,
+            enable_match: false,
+            analyzer_params: None,
             is_function_output: false,
             enable_bm25_stats: false,
+            is_partition_key: false,
+            nullable: false,
         }
     }
 
@@ -328,8 +382,12 @@ impl FieldSchema {
             dim: 1,
             max_length: 0,
             enable_analyzer: false,
+            enable_match: false,
+            analyzer_params: None,
             is_function_output: false,
             enable_bm25_stats: false,
+            is_partition_key: false,
+            nullable: false,
         }
     }
 
@@ -349,8 +407,12 @@ impl FieldSchema {
             chunk_size: 1,
             dim: 1,
             enable_analyzer: false,
+            enable_match: false,
+            analyzer_params: None,
             is_function_output: false,
             enable_bm25_stats: false,
+            is_partition_key: false,
+            nullable: false,
         }
     }
 
@@ -365,8 +427,12 @@ impl FieldSchema {
             dim: 1,
             max_length: 0,
             enable_analyzer: false,
+            enable_match: false,
+            analyzer_params: None,
             is_function_output: false,
             enable_bm25_stats: false,
+            is_partition_key: false,
+            nullable: false,
         }
     }
 
@@ -381,8 +447,12 @@ impl FieldSchema {
             dim: 1,
             max_length: 0,
             enable_analyzer: false,
+            enable_match: false,
+            analyzer_params: None,
             is_function_output: false,
             enable_bm25_stats: false,
+            is_partition_key: false,
+            nullable: false,
         }
     }
 
@@ -397,8 +467,12 @@ impl FieldSchema {
             dim: 1,
             max_length: 0,
             enable_analyzer: false,
+            enable_match: false,
+            analyzer_params: None,
             is_function_output: false,
             enable_bm25_stats: false,
+            is_partition_key: false,
+            nullable: false,
         }
     }
 
@@ -417,9 +491,37 @@ impl FieldSchema {
             chunk_size: 1,
             dim: 1,
             enable_analyzer: false,
+            enable_match: false,
+            analyzer_params: None,
             is_function_output: false,
             enable_bm25_stats: false,
+            is_partition_key: false,
+            nullable: false,
+        }
+    }
+
+    /// A VARCHAR field analyzed with a custom analyzer, for BM25 full-text search.
+    ///
+    /// `analyzer_params` configures the tokenizer and filters, e.g.
+    /// `json!({"tokenizer": "standard", "filter": ["lowercase"]})`, and is sent in
+    /// the field's type params.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AnalyzerParamsNotObject` if `analyzer_params` is not a JSON object
+    pub fn varchar_with_analyzer(
+        name: &str,
+        max_length: i32,
+        analyzer_params: serde_json::Value,
+    ) -> Result<Self> {
+        if !analyzer_params.is_object() {
+            let params = analyzer_params.to_string();
+            return Err(Error::AnalyzerParamsNotObject(name.to_owned(), params).into());
         }
+
+        let mut field = Self::new_varchar(name, "", max_length).with_analyzer();
+        field.analyzer_params = Some(analyzer_params);
+        Ok(field)
     }
 
     /// Enables text analyzer for this VARCHAR field (used for BM25 full-text search).
@@ -429,6 +531,27 @@ impl FieldSchema {
         self
     }
 
+    /// Enables keyword matching on this VARCHAR field, required to filter it with
+    /// [`Expr::text_match`](crate::expr::Expr::text_match). Needs the analyzer,
+    /// see [`FieldSchema::with_analyzer`].
+    pub fn with_match(mut self) -> Self {
+        self.enable_match = true;
+        self
+    }
+
+    /// Marks this field as the partition key, entities are then assigned to
+    /// partitions by the hash of this field. Only applicable for INT64 and VARCHAR fields.
+    pub fn with_partition_key(mut self) -> Self {
+        self.is_partition_key = true;
+        self
+    }
+
+    /// Allows rows to have no value for this field, such rows read back as null.
+    pub fn with_nullable(mut self) -> Self {
+        self.nullable = true;
+        self
+    }
+
     /// Creates a sparse float vector field.
     /// Note: Sparse float vectors can be user-provided or generated by functions like BM25.
     /// Use `mark_as_function_output()` if this field will be populated by a function.
@@ -443,8 +566,12 @@ impl FieldSchema {
             dim: 0,
             max_length: 0,
             enable_analyzer: false,
+            enable_match: false,
+            analyzer_params: None,
             is_function_output: false,
             enable_bm25_stats: false,
+            is_partition_key: false,
+            nullable: false,
         }
     }
 
@@ -477,8 +604,12 @@ impl FieldSchema {
             auto_id: false,
             max_length: 0,
             enable_analyzer: false,
+            enable_match: false,
+            analyzer_params: None,
             is_function_output: false,
             enable_bm25_stats: false,
+            is_partition_key: false,
+            nullable: false,
         }
     }
 
@@ -497,8 +628,12 @@ impl FieldSchema {
             auto_id: false,
             max_length: 0,
             enable_analyzer: false,
+            enable_match: false,
+            analyzer_params: None,
             is_function_output: false,
             enable_bm25_stats: false,
+            is_partition_key: false,
+            nullable: false,
         }
     }
 }
@@ -506,7 +641,10 @@ impl FieldSchema {
 impl From<FieldSchema> for schema::FieldSchema {
     fn from(fld: FieldSchema) -> schema::FieldSchema {
         let params = match fld.dtype {
-            DataType::BinaryVector | DataType::FloatVector => vec![KeyValuePair {
+            DataType::BinaryVector
+            | DataType::FloatVector
+            | DataType::Float16Vector
+            | DataType::BFloat16Vector => vec![KeyValuePair {
                 key: "dim".to_string(),
                 value: fld.dim.to_string(),
             }],
@@ -521,6 +659,18 @@ impl From<FieldSchema> for schema::FieldSchema {
                         value: "true".to_string(),
                     });
                 }
+                if fld.enable_match {
+                    p.push(KeyValuePair {
+                        key: "enable_match".to_string(),
+                        value: "true".to_string(),
+                    });
+                }
+                if let Some(analyzer_params) = &fld.analyzer_params {
+                    p.push(KeyValuePair {
+                        key: "analyzer_params".to_string(),
+                        value: analyzer_params.to_string(),
+                    });
+                }
                 p
             }
             DataType::SparseFloatVector => {
@@ -549,10 +699,10 @@ impl From<FieldSchema> for schema::FieldSchema {
             element_type: 0,
             default_value: None,
             is_dynamic: false,
-            is_partition_key: false,
+            is_partition_key: fld.is_partition_key,
             is_clustering_key: false,
             is_function_output: fld.is_function_output,
-            nullable: false,
+            nullable: fld.nullable,
         }
     }
 }
@@ -564,6 +714,7 @@ pub struct CollectionSchema {
     pub(crate) fields: Vec<FieldSchema>,
     pub(crate) enable_dynamic_field: bool,
     pub(crate) functions: Vec<FunctionSchema>,
+    pub(crate) partition_key_isolation: bool,
 }
 
 impl CollectionSchema {
@@ -580,6 +731,11 @@ impl CollectionSchema {
         self.fields.iter().find(|s| s.is_primary)
     }
 
+    /// The server-side functions of the collection, such as BM25.
+    pub fn functions(&self) -> &[FunctionSchema] {
+        &self.functions
+    }
+
     pub fn validate(&self) -> Result<()> {
         self.primary_column().ok_or_else(|| Error::NoPrimaryKey)?;
         // TODO addidtional schema checks need to be added here
@@ -594,6 +750,76 @@ impl CollectionSchema {
         self.fields.iter().find(|f| f.name == name)
     }
 
+    /// Checks that `other` has the same fields as this schema.
+    ///
+    /// Field names, data types, primary key, auto id, vector dimensions and
+    /// varchar max lengths are compared, descriptions are ignored. The hidden
+    /// dynamic field reported by the server is skipped.
+    pub fn validate_schema(&self, other: &CollectionSchema) -> Result<()> {
+        let mismatch = |reason: String| Error::SchemaMismatch(self.name.clone(), reason);
+        let fields = |schema: &CollectionSchema| -> Vec<FieldSchema> {
+            schema
+                .fields
+                .iter()
+                .filter(|f| f.name != DYNAMIC_FIELD_NAME)
+                .cloned()
+                .collect()
+        };
+        let (ours, theirs) = (fields(self), fields(other));
+
+        if ours.len() != theirs.len() {
+            return Err(mismatch(format!(
+                "expected {} fields, got {}",
+                ours.len(),
+                theirs.len()
+            ))
+            .into());
+        }
+
+        for f in &ours {
+            let Some(o) = theirs.iter().find(|o| o.name == f.name) else {
+                return Err(mismatch(format!("field {:?} is missing", f.name)).into());
+            };
+            if f.dtype != o.dtype {
+                return Err(mismatch(format!(
+                    "field {:?} expected to be {:?}, got {:?}",
+                    f.name, f.dtype, o.dtype
+                ))
+                .into());
+            }
+            if f.is_primary != o.is_primary || f.auto_id != o.auto_id {
+                return Err(mismatch(format!(
+                    "field {:?} differs in primary key or auto id",
+                    f.name
+                ))
+                .into());
+            }
+            let is_dense_vector = matches!(
+                f.dtype,
+                DataType::FloatVector
+                    | DataType::BinaryVector
+                    | DataType::Float16Vector
+                    | DataType::BFloat16Vector
+            );
+            if is_dense_vector && f.dim != o.dim {
+                return Err(mismatch(format!(
+                    "field {:?} expected dim {}, got {}",
+                    f.name, f.dim, o.dim
+                ))
+                .into());
+            }
+            if f.dtype == DataType::VarChar && f.max_length != o.max_length {
+                return Err(mismatch(format!(
+                    "field {:?} expected max length {}, got {}",
+                    f.name, f.max_length, o.max_length
+                ))
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn is_valid_vector_field(&self, field_name: &str) -> Result<()> {
         for f in &self.fields {
             if f.name == field_name {
@@ -634,6 +860,7 @@ impl From<schema::CollectionSchema> for CollectionSchema {
             description: v.description,
             enable_dynamic_field: v.enable_dynamic_field,
             functions: v.functions,
+            partition_key_isolation: false,
         }
     }
 }
@@ -645,6 +872,7 @@ pub struct CollectionSchemaBuilder {
     inner: Vec<FieldSchema>,
     enable_dynamic_field: bool,
     functions: Vec<FunctionSchema>,
+    partition_key_isolation: bool,
 }
 
 impl CollectionSchemaBuilder {
@@ -655,6 +883,7 @@ impl CollectionSchemaBuilder {
             inner: Vec::new(),
             enable_dynamic_field: false,
             functions: Vec::new(),
+            partition_key_isolation: false,
         }
     }
 
@@ -715,6 +944,27 @@ impl CollectionSchemaBuilder {
         self
     }
 
+    /// Enables partition key isolation, so searches filtering on the partition key
+    /// only scan the matching partition. Requires a partition key field.
+    pub fn enable_partition_key_isolation(&mut self) -> &mut Self {
+        self.partition_key_isolation = true;
+        self
+    }
+
+    /// Add a server-side function, such as one built with [`FunctionSchema::bm25`].
+    ///
+    /// The output fields of the function are marked as function outputs, so they
+    /// are generated by the server and must not be inserted.
+    pub fn add_function(&mut self, function: FunctionSchema) -> &mut Self {
+        for field in self.inner.iter_mut() {
+            if function.output_field_names.contains(&field.name) {
+                field.is_function_output = true;
+            }
+        }
+        self.functions.push(function);
+        self
+    }
+
     /// Add a BM25 function for full-text search
     ///
     /// This method automatically marks the output field as a function output field.
@@ -729,28 +979,9 @@ impl CollectionSchemaBuilder {
         input_field: &str,
         output_field: &str,
     ) -> &mut Self {
-        use crate::proto::schema::FunctionType;
-
-        let function = FunctionSchema {
-            name: function_name.to_string(),
-            id: 0,
-            description: "BM25 function for full-text search".to_string(),
-            r#type: FunctionType::Bm25 as i32,
-            input_field_names: vec![input_field.to_string()],
-            output_field_names: vec![output_field.to_string()],
-            input_field_ids: Vec::new(),
-            output_field_ids: Vec::new(),
-            params: Vec::new(),
-        };
-
-        self.functions.push(function);
-
-        // Automatically mark the output field as function output (matches pymilvus behavior)
-        if let Some(field) = self.inner.iter_mut().find(|f| f.name == output_field) {
-            field.is_function_output = true;
-        }
-
-        self
+        let mut function = FunctionSchema::bm25(input_field, output_field);
+        function.name = function_name.to_string();
+        self.add_function(function)
     }
 
     pub fn build(&mut self) -> Result<CollectionSchema> {
@@ -767,16 +998,66 @@ impl CollectionSchemaBuilder {
             return Err(error::Error::from(Error::NoPrimaryKey));
         }
 
+        if self.partition_key_isolation && !self.inner.iter().any(|f| f.is_partition_key) {
+            return Err(error::Error::from(Error::NoPartitionKey));
+        }
+
+        for function in self.functions.iter() {
+            if function.r#type == FunctionType::Bm25 as i32 {
+                self.check_function_fields(&function.input_field_names, DataType::VarChar)?;
+                self.check_function_fields(
+                    &function.output_field_names,
+                    DataType::SparseFloatVector,
+                )?;
+            }
+        }
+
         let this = std::mem::replace(self, CollectionSchemaBuilder::new("".into(), ""));
 
         Ok(CollectionSchema {
             fields: this.inner.into(),
             name: this.name,
             description: this.description,
-            enable_dynamic_field: self.enable_dynamic_field,
+            enable_dynamic_field: this.enable_dynamic_field,
             functions: this.functions,
+            partition_key_isolation: this.partition_key_isolation,
         })
     }
+
+    fn check_function_fields(&self, names: &[String], dtype: DataType) -> Result<()> {
+        for name in names {
+            let field = self
+                .inner
+                .iter()
+                .find(|f| &f.name == name)
+                .ok_or_else(|| Error::FieldDoesNotExists(name.clone()))?;
+            if field.dtype != dtype {
+                return Err(Error::FieldWrongType(name.clone(), dtype, field.dtype).into());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FunctionSchema {
+    /// A BM25 function generating sparse vectors of the VarChar `input_field`
+    /// into the SparseFloatVector `output_field`, named `{input_field}_bm25`.
+    ///
+    /// Add it with [`CollectionSchemaBuilder::add_function`]. The input field
+    /// needs an analyzer, see [`FieldSchema::with_analyzer`].
+    pub fn bm25(input_field: &str, output_field: &str) -> Self {
+        FunctionSchema {
+            name: format!("{input_field}_bm25"),
+            id: 0,
+            description: "BM25 function for full-text search".to_string(),
+            r#type: FunctionType::Bm25 as i32,
+            input_field_names: vec![input_field.to_string()],
+            output_field_names: vec![output_field.to_string()],
+            input_field_ids: Vec::new(),
+            output_field_ids: Vec::new(),
+            params: Vec::new(),
+        }
+    }
 }
 
 #[derive(Debug, ThisError)]
@@ -807,4 +1088,197 @@ pub enum Error {
 
     #[error("field {0:?} must be a vector field")]
     NotVectorField(String),
+
+    #[error("partition key isolation requires a partition key field")]
+    NoPartitionKey,
+
+    #[error("schema mismatch for collection {0:?}: {1}")]
+    SchemaMismatch(String, String),
+
+    #[error("value of field {0:?} at row {1} is {2} bytes long, exceeding max_length {3}")]
+    MaxLengthExceeded(String, usize, usize, i32),
+
+    #[error("primary key {0:?} is auto id, the server generates it so it must not be inserted")]
+    AutoIdProvided(String),
+
+    #[error("columns do not match the schema of {0:?}, missing: {1:?}, unexpected: {2:?}")]
+    ColumnsMismatch(String, Vec<String>, Vec<String>),
+
+    #[error("analyzer params of field {0:?} must be a JSON object, got {1}")]
+    AnalyzerParamsNotObject(String, String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_varchar_with_analyzer() {
+        let analyzer = serde_json::json!({"tokenizer": "standard", "filter": ["lowercase"]});
+        let field = FieldSchema::varchar_with_analyzer("text", 1024, analyzer.clone()).unwrap();
+        assert!(field.enable_analyzer);
+
+        let proto: schema::FieldSchema = field.into();
+        let param = |key: &str| {
+            proto
+                .type_params
+                .iter()
+                .find(|p| p.key == key)
+                .map(|p| p.value.clone())
+        };
+        assert_eq!(param("enable_analyzer").as_deref(), Some("true"));
+        let sent: serde_json::Value =
+            serde_json::from_str(&param("analyzer_params").unwrap()).unwrap();
+        assert_eq!(sent, analyzer);
+
+        let field = FieldSchema::from(proto);
+        assert_eq!(field.analyzer_params, Some(analyzer));
+
+        let err = FieldSchema::varchar_with_analyzer("text", 1024, serde_json::json!("standard"))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            crate::error::Error::Schema(Error::AnalyzerParamsNotObject(name, _)) if name == "text"
+        ));
+    }
+
+    #[test]
+    fn test_data_type_proto_roundtrip() {
+        let dtypes = [
+            DataType::Bool,
+            DataType::Int8,
+            DataType::Int16,
+            DataType::Int32,
+            DataType::Int64,
+            DataType::Float,
+            DataType::Double,
+            DataType::VarChar,
+            DataType::Json,
+            DataType::Array,
+            DataType::FloatVector,
+            DataType::BinaryVector,
+            DataType::Float16Vector,
+            DataType::BFloat16Vector,
+            DataType::SparseFloatVector,
+        ];
+        for dtype in dtypes {
+            assert_eq!(DataType::try_from(dtype as i32).ok(), Some(dtype));
+
+            let field = FieldSchema {
+                name: "field".to_owned(),
+                dtype,
+                dim: 16,
+                ..Default::default()
+            };
+            let decoded = FieldSchema::from(schema::FieldSchema::from(field));
+            assert_eq!(decoded.dtype, dtype);
+            let has_dim = matches!(
+                dtype,
+                DataType::FloatVector
+                    | DataType::BinaryVector
+                    | DataType::Float16Vector
+                    | DataType::BFloat16Vector
+            );
+            if has_dim {
+                assert_eq!(decoded.dim, 16, "{dtype:?}");
+            }
+        }
+
+        let unknown = schema::FieldSchema {
+            data_type: 1000,
+            ..Default::default()
+        };
+        assert_eq!(FieldSchema::from(unknown).dtype, DataType::None);
+    }
+
+    fn test_schema(dim: i64) -> CollectionSchema {
+        CollectionSchemaBuilder::new("test", "")
+            .add_field(FieldSchema::new_primary_int64("id", "", true))
+            .add_field(FieldSchema::new_float_vector("feature", "", dim))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_partition_key_isolation_requires_partition_key() {
+        let err = CollectionSchemaBuilder::new("test", "")
+            .add_field(FieldSchema::new_primary_int64("id", "", true))
+            .add_field(FieldSchema::new_int64("tenant", ""))
+            .add_field(FieldSchema::new_float_vector("feature", "", 8))
+            .enable_partition_key_isolation()
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, error::Error::Schema(Error::NoPartitionKey)));
+
+        let schema = CollectionSchemaBuilder::new("test", "")
+            .add_field(FieldSchema::new_primary_int64("id", "", true))
+            .add_field(FieldSchema::new_int64("tenant", "").with_partition_key())
+            .add_field(FieldSchema::new_float_vector("feature", "", 8))
+            .enable_partition_key_isolation()
+            .build()
+            .unwrap();
+        assert!(schema.partition_key_isolation);
+    }
+
+    #[test]
+    fn test_bm25_function_field_types() {
+        let schema = CollectionSchemaBuilder::new("test", "")
+            .add_field(FieldSchema::new_primary_int64("id", "", true))
+            .add_field(FieldSchema::new_varchar("text", "", 256).with_analyzer())
+            .add_field(FieldSchema::new_sparse_float_vector("sparse", ""))
+            .add_function(FunctionSchema::bm25("text", "sparse"))
+            .build()
+            .unwrap();
+        assert_eq!(schema.functions().len(), 1);
+        assert_eq!(schema.functions()[0].name, "text_bm25");
+        assert!(schema.get_field("sparse").unwrap().is_function_output);
+
+        let err = CollectionSchemaBuilder::new("test", "")
+            .add_field(FieldSchema::new_primary_int64("id", "", true))
+            .add_field(FieldSchema::new_int64("text", ""))
+            .add_field(FieldSchema::new_sparse_float_vector("sparse", ""))
+            .add_function(FunctionSchema::bm25("text", "sparse"))
+            .build()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            error::Error::Schema(Error::FieldWrongType(_, DataType::VarChar, DataType::Int64))
+        ));
+
+        let err = CollectionSchemaBuilder::new("test", "")
+            .add_field(FieldSchema::new_primary_int64("id", "", true))
+            .add_field(FieldSchema::new_varchar("text", "", 256).with_analyzer())
+            .add_field(FieldSchema::new_float_vector("sparse", "", 8))
+            .add_function(FunctionSchema::bm25("text", "sparse"))
+            .build()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            error::Error::Schema(Error::FieldWrongType(_, DataType::SparseFloatVector, _))
+        ));
+
+        let err = CollectionSchemaBuilder::new("test", "")
+            .add_field(FieldSchema::new_primary_int64("id", "", true))
+            .add_field(FieldSchema::new_sparse_float_vector("sparse", ""))
+            .add_function(FunctionSchema::bm25("text", "sparse"))
+            .build()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            error::Error::Schema(Error::FieldDoesNotExists(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_schema() {
+        assert!(test_schema(8).validate_schema(&test_schema(8)).is_ok());
+
+        let err = test_schema(8)
+            .validate_schema(&test_schema(16))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            error::Error::Schema(Error::SchemaMismatch(_, _))
+        ));
+    }
 }
@@ -1,3 +1,6 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
 use crate::error::{Error, Result};
 use crate::proto::schema::SparseFloatArray;
 
@@ -28,16 +31,36 @@ pub type SparseVector = Vec<(u32, f32)>;
 /// - `contents`: Vec of byte arrays, one per row
 /// - `dim`: Maximum dimension across all vectors (max_index + 1)
 pub fn sparse_vectors_to_proto(vectors: Vec<SparseVector>) -> SparseFloatArray {
+    sparse_vectors_to_proto_with_buffer(vectors, &mut Vec::new())
+}
+
+/// Serializes multiple sparse vectors to protobuf format, encoding every row
+/// in a caller-provided scratch buffer.
+///
+/// Produces the same message as [`sparse_vectors_to_proto`]. The message owns one
+/// byte array per row, so each row is still copied out of `buf` into its own
+/// allocation, `buf` is only reused for the encoding.
+///
+/// # Arguments
+/// * `vectors` - Sparse vectors to serialize
+/// * `buf` - Scratch buffer, its previous contents are discarded
+///
+/// # Returns
+/// A `SparseFloatArray` protobuf message, as for [`sparse_vectors_to_proto`]
+pub fn sparse_vectors_to_proto_with_buffer(
+    vectors: Vec<SparseVector>,
+    buf: &mut Vec<u8>,
+) -> SparseFloatArray {
     let mut contents = Vec::with_capacity(vectors.len());
     let mut max_dim = 0i64;
 
     for mut row in vectors {
-        let bytes = sparse_row_to_bytes(&mut row);
+        sparse_row_to_bytes_into(&mut row, buf);
         // After sorting, max index is the last element
         if let Some((max_idx, _)) = row.last() {
             max_dim = max_dim.max((*max_idx as i64) + 1);
         }
-        contents.push(bytes);
+        contents.push(buf.as_slice().to_vec());
     }
 
     SparseFloatArray {
@@ -46,6 +69,75 @@ pub fn sparse_vectors_to_proto(vectors: Vec<SparseVector>) -> SparseFloatArray {
     }
 }
 
+/// Computes the effective dimension of a batch of sparse vectors.
+///
+/// This is the `dim` [`sparse_vectors_to_proto`] reports, without serializing the
+/// rows. Entries need not be sorted.
+///
+/// # Arguments
+/// * `vectors` - Sparse vectors of the batch
+///
+/// # Returns
+/// The maximum index across all rows plus one, or 0 if every row is empty
+pub fn sparse_batch_dim(vectors: &[SparseVector]) -> i64 {
+    vectors
+        .iter()
+        .flatten()
+        .map(|&(index, _)| index as i64 + 1)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Computes the size of the `contents` [`sparse_vectors_to_proto`] would produce.
+///
+/// Every entry takes 8 bytes, so this is a cheap way to size requests before
+/// serializing, e.g. to choose a cap for [`sparse_vectors_to_proto_chunked`].
+///
+/// # Arguments
+/// * `vectors` - Sparse vectors of the batch
+///
+/// # Returns
+/// The total number of serialized bytes across all rows
+pub fn sparse_serialized_size(vectors: &[SparseVector]) -> usize {
+    vectors.iter().map(|row| row.len() * 8).sum()
+}
+
+/// Serializes sparse vectors into multiple protobuf arrays, each under a size cap.
+///
+/// Rows are packed in order, so concatenating the `contents` of the chunks gives
+/// the same rows as [`sparse_vectors_to_proto`]. Each chunk's `dim` is computed
+/// from its own rows. A single row larger than `max_bytes` gets a chunk of its own.
+///
+/// # Arguments
+/// * `vectors` - Sparse vectors to serialize
+/// * `max_bytes` - Maximum size of the serialized rows of a chunk
+///
+/// # Returns
+/// The `SparseFloatArray` chunks, empty if there are no vectors
+pub fn sparse_vectors_to_proto_chunked(
+    vectors: Vec<SparseVector>,
+    max_bytes: usize,
+) -> Vec<SparseFloatArray> {
+    let mut chunks = Vec::new();
+    let mut chunk = Vec::new();
+    let mut chunk_bytes = 0;
+
+    for row in vectors {
+        let row_bytes = row.len() * 8;
+        if !chunk.is_empty() && chunk_bytes + row_bytes > max_bytes {
+            chunks.push(sparse_vectors_to_proto(std::mem::take(&mut chunk)));
+            chunk_bytes = 0;
+        }
+        chunk_bytes += row_bytes;
+        chunk.push(row);
+    }
+    if !chunk.is_empty() {
+        chunks.push(sparse_vectors_to_proto(chunk));
+    }
+
+    chunks
+}
+
 /// Converts a single sparse vector row to bytes.
 ///
 /// # Format
@@ -58,15 +150,135 @@ pub fn sparse_vectors_to_proto(vectors: Vec<SparseVector>) -> SparseFloatArray {
 /// # Returns
 /// Byte representation of the sparse vector
 pub fn sparse_row_to_bytes(row: &mut SparseVector) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(row.len() * 8);
+    sparse_row_to_bytes_into(row, &mut bytes);
+    bytes
+}
+
+/// Converts a single sparse vector row to bytes, reusing `buf` as the output.
+///
+/// The buffer is cleared first, so on return it holds exactly the bytes that
+/// [`sparse_row_to_bytes`] would produce, while its capacity is kept for the next call.
+///
+/// # Arguments
+/// * `row` - A sparse vector (index, value) pairs (sorted in place)
+/// * `buf` - Output buffer, its previous contents are discarded
+pub fn sparse_row_to_bytes_into(row: &mut SparseVector, buf: &mut Vec<u8>) {
     // Sort by index to match Milvus format expectations
-    row.sort_by_key(|(idx, _)| *idx);
+    sort_entries_by(row, |a, b| a.0.cmp(&b.0));
 
-    let mut bytes = Vec::with_capacity(row.len() * 8);
+    buf.clear();
+    buf.reserve(row.len() * 8);
     for (index, value) in row.iter() {
-        bytes.extend_from_slice(&index.to_le_bytes());
-        bytes.extend_from_slice(&value.to_le_bytes());
+        buf.extend_from_slice(&index.to_le_bytes());
+        buf.extend_from_slice(&value.to_le_bytes());
     }
-    bytes
+}
+
+/// Stably sorts entries with `cmp`, skipping the sort when they are already in order.
+///
+/// Rows produced by most pipelines are sorted, so the linear check avoids the sort
+/// in the common case. Equal indices count as in order, which leaves duplicates
+/// exactly where a stable sort would put them.
+fn sort_entries_by<F>(entries: &mut [(u32, f32)], mut cmp: F)
+where
+    F: FnMut(&(u32, f32), &(u32, f32)) -> Ordering,
+{
+    if !entries.is_sorted_by(|a, b| cmp(a, b).is_le()) {
+        entries.sort_by(cmp);
+    }
+}
+
+/// Scales a sparse vector in place so that the L2 norm of its values is 1.
+///
+/// Indices are left untouched. A vector whose values are all zero (or an
+/// empty vector) is left unchanged.
+///
+/// # Arguments
+/// * `vec` - The sparse vector to normalize
+pub fn sparse_normalize(vec: &mut SparseVector) {
+    let norm = vec.iter().map(|(_, v)| v * v).sum::<f32>().sqrt();
+    if norm == 0.0 {
+        return;
+    }
+    for (_, v) in vec.iter_mut() {
+        *v /= norm;
+    }
+}
+
+/// Builds a sparse vector from token counts, e.g. of a bag-of-words model.
+///
+/// Counts of a repeated token id are summed, tokens whose total count is zero
+/// are dropped. The result is sorted by index.
+///
+/// # Arguments
+/// * `counts` - (token id, count) pairs, in any order
+///
+/// # Returns
+/// A sparse vector with the counts as values
+pub fn sparse_from_term_counts(counts: &[(u32, u32)]) -> SparseVector {
+    let mut totals: BTreeMap<u32, u64> = BTreeMap::new();
+    for &(token, count) in counts {
+        *totals.entry(token).or_default() += u64::from(count);
+    }
+    totals
+        .into_iter()
+        .filter(|&(_, count)| count > 0)
+        .map(|(token, count)| (token, count as f32))
+        .collect()
+}
+
+/// Problems found in a batch of sparse vectors by [`sparse_validate_batch`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SparseBatchReport {
+    /// Number of NaN values across all rows
+    pub nan_values: usize,
+    /// Number of rows listing an index more than once
+    pub duplicate_index_rows: usize,
+    /// Number of rows without entries, these are accepted by the server
+    pub empty_rows: usize,
+    /// Largest index across all rows, `None` if every row is empty
+    pub max_index: Option<u32>,
+    /// Whether the server would accept the batch: no NaN values, no duplicate
+    /// indices, and every index below 2^32-1
+    pub is_ingestable: bool,
+}
+
+/// Scans a batch of sparse vectors for problems, without modifying it.
+///
+/// Unlike inserting, which fails on the first bad row, every row is checked
+/// and the problems are counted.
+///
+/// # Arguments
+/// * `vectors` - Sparse vectors of the batch, entries need not be sorted
+///
+/// # Returns
+/// A report of the problems found
+pub fn sparse_validate_batch(vectors: &[SparseVector]) -> SparseBatchReport {
+    let mut report = SparseBatchReport::default();
+    let mut indices = Vec::new();
+    for row in vectors {
+        if row.is_empty() {
+            report.empty_rows += 1;
+            continue;
+        }
+
+        report.nan_values += row.iter().filter(|(_, v)| v.is_nan()).count();
+        let row_max = row.iter().map(|&(index, _)| index).max();
+        report.max_index = report.max_index.max(row_max);
+
+        indices.clear();
+        indices.extend(row.iter().map(|&(index, _)| index));
+        indices.sort_unstable();
+        if indices.windows(2).any(|w| w[0] == w[1]) {
+            report.duplicate_index_rows += 1;
+        }
+    }
+
+    report.is_ingestable = report.nan_values == 0
+        && report.duplicate_index_rows == 0
+        && report.max_index != Some(u32::MAX);
+    report
 }
 
 /// Deserializes a single sparse vector row from bytes.
@@ -114,6 +326,163 @@ pub fn sparse_proto_to_vectors(proto: &SparseFloatArray) -> Result<Vec<SparseVec
         .collect()
 }
 
+/// CSR (compressed sparse row) arrays as used by scipy: `(indptr, indices, data, ncols)`.
+///
+/// - `indptr`: Row offsets into `indices` and `data`, one entry per row plus one
+/// - `indices`: Column indices, sorted within each row
+/// - `data`: Values matching `indices`
+/// - `ncols`: Number of columns
+pub type CsrArrays = (Vec<i64>, Vec<u32>, Vec<f32>, i64);
+
+/// Converts protobuf format to CSR arrays.
+///
+/// # Arguments
+/// * `proto` - A `SparseFloatArray` protobuf message
+///
+/// # Returns
+/// The `CsrArrays` of the rows, with `ncols` set to the `dim` of the array
+///
+/// # Errors
+/// Returns an error if any row fails to parse
+pub fn sparse_proto_to_csr(proto: &SparseFloatArray) -> Result<CsrArrays> {
+    let mut indptr = Vec::with_capacity(proto.contents.len() + 1);
+    let mut indices = Vec::new();
+    let mut data = Vec::new();
+    indptr.push(0);
+
+    for bytes in &proto.contents {
+        let mut row = sparse_row_from_bytes(bytes)?;
+        row.sort_by_key(|(idx, _)| *idx);
+        for (index, value) in row {
+            indices.push(index);
+            data.push(value);
+        }
+        indptr.push(indices.len() as i64);
+    }
+
+    Ok((indptr, indices, data, proto.dim))
+}
+
+/// Builds sparse vectors from CSR arrays.
+///
+/// # Arguments
+/// * `indptr` - Row offsets into `indices` and `data`, one entry per row plus one
+/// * `indices` - Column indices
+/// * `data` - Values matching `indices`
+///
+/// # Returns
+/// One `SparseVector` per row. An empty `indptr` (or `[0]`) yields no rows.
+///
+/// # Errors
+/// Returns an error if `indices` and `data` differ in length, or if `indptr` is
+/// negative, not sorted, or points outside of `indices`
+pub fn csr_to_sparse_vectors(
+    indptr: &[i64],
+    indices: &[u32],
+    data: &[f32],
+) -> Result<Vec<SparseVector>> {
+    if indices.len() != data.len() {
+        return Err(Error::SparseVectorError(format!(
+            "CSR indices and data must have the same length, got {} and {}",
+            indices.len(),
+            data.len()
+        )));
+    }
+    if indptr.first().is_some_and(|&start| start < 0) {
+        return Err(Error::SparseVectorError(
+            "CSR indptr must be non-negative".to_string(),
+        ));
+    }
+
+    indptr
+        .windows(2)
+        .map(|w| {
+            let (start, end) = (w[0], w[1]);
+            if start > end {
+                return Err(Error::SparseVectorError(format!(
+                    "CSR indptr must be sorted, got {} after {}",
+                    end, start
+                )));
+            }
+            if end as usize > indices.len() {
+                return Err(Error::SparseVectorError(format!(
+                    "CSR indptr {} is out of bounds for {} entries",
+                    end,
+                    indices.len()
+                )));
+            }
+            let (start, end) = (start as usize, end as usize);
+            Ok(indices[start..end]
+                .iter()
+                .copied()
+                .zip(data[start..end].iter().copied())
+                .collect())
+        })
+        .collect()
+}
+
+/// Converts a sparse vector to the JSON the Milvus REST API expects, an object
+/// mapping each index, as a string, to its value.
+///
+/// # Arguments
+/// * `vec` - The sparse vector to convert
+///
+/// # Returns
+/// A JSON object such as `{"5": 0.3, "10": 0.7}`, `{}` for an empty vector.
+/// Non-finite values, which Milvus rejects, become `null`.
+pub fn sparse_to_rest_json(vec: &SparseVector) -> serde_json::Value {
+    let entries = vec
+        .iter()
+        .map(|&(index, value)| {
+            // Going through the shortest f32 representation keeps 0.3 from
+            // printing as 0.30000001192092896.
+            let value = value
+                .to_string()
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map_or(serde_json::Value::Null, serde_json::Value::Number);
+            (index.to_string(), value)
+        })
+        .collect();
+    serde_json::Value::Object(entries)
+}
+
+/// Parses a sparse vector from the Milvus REST JSON format, see
+/// [`sparse_to_rest_json`].
+///
+/// # Arguments
+/// * `json` - A JSON object mapping index strings to numbers
+///
+/// # Returns
+/// The sparse vector, sorted by index
+///
+/// # Errors
+/// Returns an error if `json` is not an object, a key is not a `u32` index or a
+/// value is not a number
+pub fn sparse_from_rest_json(json: &serde_json::Value) -> Result<SparseVector> {
+    let entries = json.as_object().ok_or_else(|| {
+        Error::SparseVectorError(format!("sparse vector JSON must be an object, got {json}"))
+    })?;
+
+    let mut vec = entries
+        .iter()
+        .map(|(key, value)| {
+            let index = key.parse::<u32>().map_err(|_| {
+                Error::SparseVectorError(format!("sparse vector index {key:?} is not a u32"))
+            })?;
+            let value = value.as_f64().ok_or_else(|| {
+                Error::SparseVectorError(format!(
+                    "sparse vector value at index {index} is not a number, got {value}"
+                ))
+            })?;
+            Ok((index, value as f32))
+        })
+        .collect::<Result<SparseVector>>()?;
+    vec.sort_by_key(|&(index, _)| index);
+    Ok(vec)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -146,6 +515,55 @@ mod tests {
         assert_eq!(val3, 1.0);
     }
 
+    #[test]
+    fn test_sparse_row_to_bytes_into() {
+        let rows = vec![
+            vec![(9, 0.125), (2, 4.0), (40, 1.5), (7, 0.5)],
+            vec![(1, 1.0)],
+            vec![],
+            vec![(8, 2.0), (3, 0.75)],
+        ];
+
+        // pre-grown and dirty, so stale bytes would show up in shorter rows
+        let mut buf = vec![0xAB; 256];
+        for row in &rows {
+            sparse_row_to_bytes_into(&mut row.clone(), &mut buf);
+            assert_eq!(buf, sparse_row_to_bytes(&mut row.clone()));
+        }
+
+        let expected = sparse_vectors_to_proto(rows.clone());
+        let mut buf = vec![0xCD; 256];
+        for _ in 0..2 {
+            let proto = sparse_vectors_to_proto_with_buffer(rows.clone(), &mut buf);
+            assert_eq!(proto.contents, expected.contents);
+            assert_eq!(proto.dim, expected.dim);
+        }
+    }
+
+    #[test]
+    fn test_sparse_row_to_bytes_presorted() {
+        let sorted = vec![(1, 0.5), (4, 0.25), (4, 2.0), (9, 1.0), (300, 0.125)];
+        let mut unsorted = vec![(300, 0.125), (4, 0.25), (9, 1.0), (1, 0.5), (4, 2.0)];
+
+        let bytes = sparse_row_to_bytes(&mut sorted.clone());
+        assert_eq!(bytes, sparse_row_to_bytes(&mut unsorted));
+        assert_eq!(unsorted, sorted);
+        assert_eq!(sparse_row_from_bytes(&bytes).unwrap(), sorted);
+
+        // a sorted row only pays for the in-order check, one comparison per adjacent pair
+        let count = |row: &mut SparseVector| {
+            let mut comparisons = 0;
+            sort_entries_by(row, |a, b| {
+                comparisons += 1;
+                a.0.cmp(&b.0)
+            });
+            comparisons
+        };
+        assert_eq!(count(&mut sorted.clone()), sorted.len() - 1);
+        assert!(count(&mut vec![(3, 1.0), (1, 1.0), (2, 1.0)]) > 2);
+        assert_eq!(count(&mut Vec::new()), 0);
+    }
+
     #[test]
     fn test_sparse_row_from_bytes() {
         let mut bytes = Vec::new();
@@ -213,6 +631,61 @@ mod tests {
         assert_eq!(parsed[2], vec![(100, 10.0), (200, 20.0)]);
     }
 
+    #[test]
+    fn test_sparse_from_term_counts() {
+        let counts = [(7, 2), (3, 1), (7, 3), (5, 0), (9, 0), (9, 4), (3, 0)];
+        assert_eq!(
+            sparse_from_term_counts(&counts),
+            vec![(3, 1.0), (7, 5.0), (9, 4.0)]
+        );
+        assert!(sparse_from_term_counts(&[(1, 0), (1, 0)]).is_empty());
+        assert!(sparse_from_term_counts(&[]).is_empty());
+        // Totals may exceed u32
+        assert_eq!(
+            sparse_from_term_counts(&[(0, u32::MAX), (0, 1)]),
+            vec![(0, 4294967296.0)]
+        );
+    }
+
+    #[test]
+    fn test_sparse_validate_batch() {
+        let clean = vec![vec![(7, 0.5), (2, 1.0)], vec![(40, 0.25)]];
+        assert_eq!(
+            sparse_validate_batch(&clean),
+            SparseBatchReport {
+                max_index: Some(40),
+                is_ingestable: true,
+                ..Default::default()
+            }
+        );
+        assert!(sparse_validate_batch(&[]).is_ingestable);
+
+        let nan = vec![vec![(1, f32::NAN), (2, f32::NAN)], vec![(3, 1.0)]];
+        let report = sparse_validate_batch(&nan);
+        assert_eq!(report.nan_values, 2);
+        assert!(!report.is_ingestable);
+
+        let duplicates = vec![vec![(5, 1.0), (1, 1.0), (5, 2.0)], vec![(5, 1.0)]];
+        let report = sparse_validate_batch(&duplicates);
+        assert_eq!(report.duplicate_index_rows, 1);
+        assert!(!report.is_ingestable);
+
+        let empty = vec![vec![], vec![(3, 1.0)], vec![]];
+        let report = sparse_validate_batch(&empty);
+        assert_eq!(report.empty_rows, 2);
+        assert_eq!(report.max_index, Some(3));
+        assert!(report.is_ingestable);
+        assert_eq!(sparse_validate_batch(&[vec![]]).max_index, None);
+
+        let too_large = vec![vec![(u32::MAX, 1.0)]];
+        let report = sparse_validate_batch(&too_large);
+        assert_eq!(report.max_index, Some(u32::MAX));
+        assert!(!report.is_ingestable);
+
+        // The batch is left as given
+        assert_eq!(duplicates[0], vec![(5, 1.0), (1, 1.0), (5, 2.0)]);
+    }
+
     #[test]
     fn test_empty_sparse_vector() {
         let vectors = vec![vec![], vec![(5, 0.5)]];
@@ -226,4 +699,153 @@ mod tests {
         assert_eq!(parsed[0].len(), 0);
         assert_eq!(parsed[1], vec![(5, 0.5)]);
     }
+
+    #[test]
+    fn test_sparse_batch_dim() {
+        let mixed = vec![
+            vec![(3, 0.5), (42, 1.0)],
+            vec![],
+            vec![(100, 0.1), (7, 0.2)],
+        ];
+        assert_eq!(sparse_batch_dim(&mixed), 101);
+        assert_eq!(sparse_batch_dim(&mixed), sparse_vectors_to_proto(mixed).dim);
+
+        assert_eq!(sparse_batch_dim(&[]), 0);
+        assert_eq!(sparse_batch_dim(&[vec![], vec![]]), 0);
+        assert_eq!(sparse_batch_dim(&[vec![(0, 1.0)]]), 1);
+        assert_eq!(sparse_batch_dim(&[vec![(9, 0.5), (2, 0.5)]]), 10);
+    }
+
+    #[test]
+    fn test_sparse_serialized_size() {
+        let rows = vec![
+            vec![(3, 0.5), (42, 1.0)],
+            vec![],
+            vec![(100, 0.1), (7, 0.2), (8, 0.3)],
+        ];
+        let size = sparse_serialized_size(&rows);
+        assert_eq!(size, 40);
+
+        let proto = sparse_vectors_to_proto(rows);
+        assert_eq!(size, proto.contents.iter().map(Vec::len).sum::<usize>());
+        assert_eq!(sparse_serialized_size(&[]), 0);
+    }
+
+    #[test]
+    fn test_sparse_normalize() {
+        let mut row = vec![(3, 3.0), (10, 4.0)];
+        sparse_normalize(&mut row);
+
+        let norm = row.iter().map(|(_, v)| v * v).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-6);
+        assert_eq!(row[0], (3, 0.6));
+        assert_eq!(row[1], (10, 0.8));
+    }
+
+    #[test]
+    fn test_sparse_normalize_zero_norm() {
+        let mut row = vec![(1, 0.0), (2, 0.0)];
+        sparse_normalize(&mut row);
+        assert_eq!(row, vec![(1, 0.0), (2, 0.0)]);
+
+        let mut empty: SparseVector = Vec::new();
+        sparse_normalize(&mut empty);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_sparse_proto_to_csr() {
+        let vectors = vec![
+            vec![(10, 1.0), (5, 0.5)],
+            vec![],
+            vec![(0, 0.25), (20, 2.0)],
+        ];
+        let proto = sparse_vectors_to_proto(vectors);
+
+        let (indptr, indices, data, ncols) = sparse_proto_to_csr(&proto).unwrap();
+
+        assert_eq!(indptr, vec![0, 2, 2, 4]);
+        assert_eq!(indices, vec![5, 10, 0, 20]);
+        assert_eq!(data, vec![0.5, 1.0, 0.25, 2.0]);
+        assert_eq!(ncols, 21);
+    }
+
+    #[test]
+    fn test_csr_roundtrip() {
+        let vectors = vec![
+            vec![(5, 0.5), (10, 1.0)],
+            vec![],
+            vec![(0, 0.25), (20, 2.0)],
+        ];
+        let proto = sparse_vectors_to_proto(vectors.clone());
+
+        let (indptr, indices, data, _) = sparse_proto_to_csr(&proto).unwrap();
+        let parsed = csr_to_sparse_vectors(&indptr, &indices, &data).unwrap();
+
+        assert_eq!(parsed, vectors);
+    }
+
+    #[test]
+    fn test_csr_to_sparse_vectors_empty() {
+        assert!(csr_to_sparse_vectors(&[], &[], &[]).unwrap().is_empty());
+        assert!(csr_to_sparse_vectors(&[0], &[], &[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_csr_to_sparse_vectors_invalid() {
+        // indptr not sorted
+        assert!(csr_to_sparse_vectors(&[0, 2, 1], &[1, 2], &[0.1, 0.2]).is_err());
+        // indptr out of bounds
+        assert!(csr_to_sparse_vectors(&[0, 3], &[1, 2], &[0.1, 0.2]).is_err());
+        // negative indptr
+        assert!(csr_to_sparse_vectors(&[-1, 1], &[1, 2], &[0.1, 0.2]).is_err());
+        // mismatched indices and data
+        assert!(csr_to_sparse_vectors(&[0, 2], &[1, 2], &[0.1]).is_err());
+    }
+
+    #[test]
+    fn test_sparse_vectors_to_proto_chunked() {
+        let vectors: Vec<SparseVector> = (0..1000u32)
+            .map(|i| vec![(i, 0.5), (i + 1000, 1.0)])
+            .collect();
+        let single = sparse_vectors_to_proto(vectors.clone());
+
+        let chunks = sparse_vectors_to_proto_chunked(vectors, 1024);
+
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.contents.iter().map(Vec::len).sum::<usize>() <= 1024);
+            let local_dim = sparse_proto_to_vectors(chunk)
+                .unwrap()
+                .iter()
+                .filter_map(|row| row.last().map(|(idx, _)| *idx as i64 + 1))
+                .max()
+                .unwrap_or(0);
+            assert_eq!(chunk.dim, local_dim);
+        }
+        let contents: Vec<Vec<u8>> = chunks.into_iter().flat_map(|c| c.contents).collect();
+        assert_eq!(contents, single.contents);
+    }
+
+    #[test]
+    fn test_sparse_rest_json_roundtrip() {
+        let vec: SparseVector = vec![(5, 0.3), (10, 0.7), (4294967294, -1.5)];
+        let json = sparse_to_rest_json(&vec);
+        assert_eq!(
+            json,
+            serde_json::json!({"5": 0.3, "10": 0.7, "4294967294": -1.5})
+        );
+        assert_eq!(sparse_from_rest_json(&json).unwrap(), vec);
+
+        let empty = sparse_to_rest_json(&Vec::new());
+        assert_eq!(empty.to_string(), "{}");
+        assert!(sparse_from_rest_json(&empty).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sparse_from_rest_json_invalid() {
+        assert!(sparse_from_rest_json(&serde_json::json!([0.3])).is_err());
+        assert!(sparse_from_rest_json(&serde_json::json!({"-1": 0.3})).is_err());
+        assert!(sparse_from_rest_json(&serde_json::json!({"5": "0.3"})).is_err());
+    }
 }
@@ -9,6 +9,35 @@ pub struct Field {
     pub description: String,
     pub dtype: DataType,
     pub is_primary_key: bool,
+    pub max_length: i32,
+    pub auto_id: bool,
+    pub nullable: bool,
+    pub has_default_value: bool,
+    pub is_dynamic: bool,
+    pub is_function_output: bool,
+}
+
+impl Field {
+    pub fn is_vector(&self) -> bool {
+        matches!(
+            self.dtype,
+            DataType::FloatVector
+                | DataType::BinaryVector
+                | DataType::Float16Vector
+                | DataType::BFloat16Vector
+                | DataType::SparseFloatVector
+        )
+    }
+
+    /// Whether inserts must carry a column for this field, false for fields the
+    /// server fills in itself or can leave empty.
+    pub fn is_required(&self) -> bool {
+        !(self.auto_id
+            || self.nullable
+            || self.has_default_value
+            || self.is_dynamic
+            || self.is_function_output)
+    }
 }
 
 impl From<proto::schema::FieldSchema> for Field {
@@ -19,6 +48,53 @@ impl From<proto::schema::FieldSchema> for Field {
             description: value.description,
             dtype: DataType::from_i32(value.data_type).unwrap_or(DataType::None),
             is_primary_key: value.is_primary_key,
+            max_length: value
+                .type_params
+                .iter()
+                .find(|k| k.key == "max_length")
+                .and_then(|x| x.value.parse().ok())
+                .unwrap_or(0),
+            auto_id: value.auto_id,
+            nullable: value.nullable,
+            has_default_value: value.default_value.is_some(),
+            is_dynamic: value.is_dynamic,
+            is_function_output: value.is_function_output,
+        }
+    }
+}
+
+/// Fixtures shared by the unit tests of modules that work on described fields.
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::*;
+    use crate::collection::Collection;
+
+    /// A field with every flag off, tests set the ones they need on the result.
+    pub(crate) fn field(name: &str, dtype: DataType) -> Field {
+        Field {
+            id: 0,
+            name: name.to_owned(),
+            description: String::new(),
+            dtype,
+            is_primary_key: false,
+            max_length: 0,
+            auto_id: false,
+            nullable: false,
+            has_default_value: false,
+            is_dynamic: false,
+            is_function_output: false,
+        }
+    }
+
+    pub(crate) fn collection(fields: Vec<Field>) -> Collection {
+        Collection {
+            id: 0,
+            name: "books".to_owned(),
+            auto_id: true,
+            num_shards: 1,
+            consistency_level: crate::client::ConsistencyLevel::Bounded,
+            description: String::new(),
+            fields,
         }
     }
 }
@@ -19,12 +19,65 @@ use crate::{
     proto::common::{ErrorCode, Status},
 };
 
+/// Appends `value` to a boolean expression as a double-quoted string literal.
+///
+/// `"` and `\` are escaped with a backslash, as are newlines, carriage returns and
+/// tabs. Other control characters are written as `\uXXXX` escapes, so the literal
+/// can always be parsed back to `value`.
+pub(crate) fn append_quoted_string(expr: &mut String, value: &str) {
+    expr.push('"');
+    // Fast path: no escaping needed (common case for IDs)
+    if !value
+        .chars()
+        .any(|c| c == '\\' || c == '"' || c.is_control())
+    {
+        expr.push_str(value);
+    } else {
+        for c in value.chars() {
+            match c {
+                '\\' => expr.push_str("\\\\"),
+                '"' => expr.push_str("\\\""),
+                '\n' => expr.push_str("\\n"),
+                '\r' => expr.push_str("\\r"),
+                '\t' => expr.push_str("\\t"),
+                c if c.is_control() => expr.push_str(&format!("\\u{:04x}", c as u32)),
+                _ => expr.push(c),
+            }
+        }
+    }
+    expr.push('"');
+}
+
+/// Quotes a VarChar value as a string literal, see [`append_quoted_string`].
+pub(crate) fn quote_string(value: &str) -> String {
+    let mut literal = String::with_capacity(value.len() + 2);
+    append_quoted_string(&mut literal, value);
+    literal
+}
+
+/// Scales `vec` in place to unit L2 norm, returns `false` and leaves it unchanged
+/// when the norm is zero.
+pub(crate) fn normalize_l2(vec: &mut [f32]) -> bool {
+    let norm = vec.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm == 0.0 {
+        return false;
+    }
+    for v in vec.iter_mut() {
+        *v /= norm;
+    }
+    true
+}
+
 pub fn status_to_result(status: &Option<Status>) -> Result<(), Error> {
     let status = status
         .clone()
         .ok_or(Error::Unexpected("no status".to_owned()))?;
 
     match ErrorCode::from_i32(status.error_code) {
+        // failures may be reported only in `code`, which replaces `error_code`
+        Some(ErrorCode::Success) if status.code != 0 => {
+            Err(Error::Server(ErrorCode::UnexpectedError, status.reason))
+        }
         Some(i) => match i {
             ErrorCode::Success => Ok(()),
             _ => Err(Error::from(status)),
@@ -35,3 +88,36 @@ pub fn status_to_result(status: &Option<Status>) -> Result<(), Error> {
         ))),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_string() {
+        assert_eq!(quote_string("plain"), r#""plain""#);
+        assert_eq!(quote_string(r#"a"b\c"#), r#""a\"b\\c""#);
+        assert_eq!(
+            quote_string("line\nbreak\ttab\r"),
+            r#""line\nbreak\ttab\r""#
+        );
+        assert_eq!(quote_string("bell\u{7}"), r#""bell\u0007""#);
+        assert_eq!(quote_string("ünï"), r#""ünï""#);
+    }
+
+    #[test]
+    fn test_status_to_result_code() {
+        let status = |code: i32| {
+            Some(Status {
+                code,
+                reason: "collection not found".to_owned(),
+                ..Default::default()
+            })
+        };
+        assert!(status_to_result(&status(0)).is_ok());
+        assert!(matches!(
+            status_to_result(&status(100)),
+            Err(Error::Server(ErrorCode::UnexpectedError, reason)) if reason == "collection not found"
+        ));
+    }
+}
@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::collections::HashMap;
 
 use crate::proto::{
     self,
@@ -8,6 +9,9 @@ use crate::proto::{
     },
 };
 
+/// A single result row, mapping field names to their values.
+pub type RowMap = HashMap<String, Value<'static>>;
+
 #[derive(Debug, Clone)]
 pub enum Value<'a> {
     None,
@@ -26,6 +30,11 @@ pub enum Value<'a> {
     StructArray(Cow<'a, proto::schema::StructArrayField>),
     VectorArray(Cow<'a, proto::schema::VectorArray>),
     SparseFloatVector(Cow<'a, [(u32, f32)]>),
+    /// The raw little-endian bytes of a float16 vector, two per dimension.
+    ///
+    /// Float16Vector columns read back as this variant, they used to read back
+    /// as `Binary`. Matches on `Value` need an arm for it.
+    Float16Array(Cow<'a, [u8]>),
 }
 
 macro_rules! impl_from_for_field_data_column {
@@ -67,9 +76,29 @@ impl Value<'_> {
             Value::StructArray(_) => DataType::ArrayOfStruct,
             Value::VectorArray(_) => DataType::ArrayOfVector,
             Value::SparseFloatVector(_) => DataType::SparseFloatVector,
+            Value::Float16Array(_) => DataType::Float16Vector,
         }
     }
 
+    /// Converts a scalar, JSON or float vector value to JSON, as stored in a
+    /// dynamic field. Other types have no JSON form and yield `None`.
+    pub fn to_json(&self) -> Option<serde_json::Value> {
+        Some(match self {
+            Value::None => serde_json::Value::Null,
+            Value::Bool(v) => (*v).into(),
+            Value::Int8(v) => (*v).into(),
+            Value::Int16(v) => (*v).into(),
+            Value::Int32(v) => (*v).into(),
+            Value::Long(v) => (*v).into(),
+            Value::Float(v) => (*v).into(),
+            Value::Double(v) => (*v).into(),
+            Value::String(v) => v.as_ref().into(),
+            Value::Json(v) => serde_json::from_slice(v).ok()?,
+            Value::FloatArray(v) => v.iter().copied().collect(),
+            _ => return None,
+        })
+    }
+
     /// Convert borrowed data to owned data
     pub fn into_owned(self) -> Value<'static> {
         match self {
@@ -89,6 +118,7 @@ impl Value<'_> {
             Value::StructArray(cow) => Value::StructArray(Cow::Owned(cow.into_owned())),
             Value::VectorArray(cow) => Value::VectorArray(Cow::Owned(cow.into_owned())),
             Value::SparseFloatVector(cow) => Value::SparseFloatVector(Cow::Owned(cow.into_owned())),
+            Value::Float16Array(cow) => Value::Float16Array(Cow::Owned(cow.into_owned())),
         }
     }
 }
@@ -232,7 +262,7 @@ impl ValueVec {
             DataType::Double => Self::Double(Vec::new()),
             DataType::String => Self::String(Vec::new()),
             DataType::VarChar => Self::String(Vec::new()),
-            DataType::Json => Self::String(Vec::new()),
+            DataType::Json => Self::Json(Vec::new()),
             DataType::Array => Self::Array(Vec::new()),
             DataType::BinaryVector => Self::Binary(Vec::new()),
             DataType::FloatVector => Self::Float(Vec::new()),
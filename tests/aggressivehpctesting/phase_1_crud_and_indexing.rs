@@ -158,7 +158,7 @@ async fn high_concurrency_crud_and_indexing() -> Result<()> {
     client.flush(AGGRESSIVE_COLLECTION_NAME).await?;
     sleep(Duration::from_secs(5)).await; // Give time for stats to update
     let stats = client
-        .get_collection_stats(AGGRESSIVE_COLLECTION_NAME)
+        .get_collection_stats(AGGRESSIVE_COLLECTION_NAME, None)
         .await?;
     let row_count = stats.get("row_count").unwrap().parse::<i64>().unwrap();
     assert_eq!(
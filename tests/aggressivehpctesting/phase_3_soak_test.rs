@@ -167,7 +167,7 @@ async fn run_full_sdk_cycle(
     println!("Cycle: Released collection");
 
     client
-        .drop_index(collection_name, DEFAULT_VEC_FIELD)
+        .drop_index(collection_name, DEFAULT_VEC_FIELD, None)
         .await?;
     println!("Cycle: Dropped index");
 
@@ -45,6 +45,14 @@ async fn test_describe_alias() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_describe_missing_alias() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let alias = gen_random_name();
+    assert!(client.describe_alias(&alias).await.is_err());
+    Ok(())
+}
+
 #[tokio::test]
 async fn list_aliases() -> Result<()> {
     let alias1 = "test_list_alias_1";
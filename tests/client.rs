@@ -154,10 +154,10 @@ async fn test_alter_collection_field() -> Result<()> {
             "varchar_field",
             HashMap::from([("max_length".to_string(), "200".to_string())]),
         ),
-        // Test vector field - alter mmap_enabled
+        // Test vector field - alter mmap.enabled
         (
             "vector_field",
-            HashMap::from([("mmap_enabled".to_string(), "true".to_string())]),
+            HashMap::from([("mmap.enabled".to_string(), "true".to_string())]),
         ),
     ];
 
@@ -174,6 +174,35 @@ async fn test_alter_collection_field() -> Result<()> {
         );
     }
 
+    let described = client.describe_collection(collection_name).await?;
+    let vector_field = described
+        .schema
+        .fields
+        .into_iter()
+        .find(|f| f.name == "vector_field")
+        .unwrap();
+    assert!(
+        vector_field
+            .type_params
+            .iter()
+            .any(|p| p.key == "mmap.enabled" && p.value == "true"),
+        "{:?}",
+        vector_field.type_params
+    );
+
+    // Properties that can not be altered on the field type are rejected
+    let result = client
+        .alter_collection_field(
+            collection_name,
+            "vector_field",
+            HashMap::from([("max_length".to_string(), "200".to_string())]),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(milvus::error::Error::InvalidParameter(key, _)) if key == "max_length"
+    ));
+
     // Cleanup
     client.drop_collection(collection_name).await.unwrap();
     Ok(())
@@ -272,3 +301,142 @@ async fn rename_collection() -> Result<()> {
     client.drop_collection(collection.collection_name).await?;
     Ok(())
 }
+
+#[tokio::test]
+async fn create_client_with_config() -> Result<()> {
+    let config = ClientConfig::new(URL)
+        .username("root")
+        .password("Milvus")
+        .timeout(std::time::Duration::from_secs(30))
+        .connect_timeout(std::time::Duration::from_secs(5))
+        .max_message_size(64 * 1024 * 1024);
+    let client = Client::with_config(config).await?;
+
+    let applied = client.config();
+    assert_eq!(applied.address, URL);
+    assert_eq!(applied.username.as_deref(), Some("root"));
+    assert_eq!(applied.password.as_deref(), Some("Milvus"));
+    assert_eq!(applied.timeout, std::time::Duration::from_secs(30));
+    assert_eq!(
+        applied.connect_timeout,
+        Some(std::time::Duration::from_secs(5))
+    );
+    assert_eq!(applied.max_message_size, 64 * 1024 * 1024);
+    assert!(!format!("{:?}", applied).contains("Milvus"));
+
+    client.list_collections().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_client_with_database() -> Result<()> {
+    let db_name = format!("test_db_{}", gen_random_name());
+    Client::new(URL)
+        .await?
+        .create_database(&db_name, None)
+        .await?;
+
+    let client = Client::with_config(ClientConfig::new(URL).database(&db_name)).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", true))
+        .add_field(FieldSchema::new_float_vector(DEFAULT_VEC_FIELD, "", 8))
+        .build()?;
+    client.create_collection(schema, None).await?;
+
+    assert!(client.has_collection(&collection_name).await?);
+    assert!(
+        !client
+            .with_database("default")
+            .has_collection(&collection_name)
+            .await?
+    );
+
+    client.drop_collection(&collection_name).await?;
+    client
+        .with_database("default")
+        .drop_database(&db_name)
+        .await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_client_with_connect_retry() -> Result<()> {
+    let client = ClientBuilder::new(URL)
+        .connect_retry(3, std::time::Duration::from_millis(200))
+        .build()
+        .await?;
+    assert_eq!(client.config().connect_attempts, 3);
+    client.list_collections().await?;
+
+    // nothing listens on this port, so every attempt fails
+    let result = ClientBuilder::new("http://127.0.0.1:1")
+        .connect_retry(2, std::time::Duration::from_millis(10))
+        .build()
+        .await;
+    assert!(matches!(result, Err(milvus::error::Error::Timeout(_))));
+    Ok(())
+}
+
+#[tokio::test]
+async fn ping() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let latency = client.ping().await?;
+    assert!(latency > std::time::Duration::ZERO);
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_metrics() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let metrics = client.get_metrics(SYSTEM_INFO_METRICS).await?;
+    assert!(metrics.get("nodes_info").is_some(), "{metrics}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn flush_all() -> Result<()> {
+    let (client, first) = create_test_collection(true).await?;
+    let (_, second) = create_test_collection(true).await?;
+    for schema in [&first, &second] {
+        let column = FieldColumn::new(
+            schema.get_field(DEFAULT_VEC_FIELD).unwrap(),
+            gen_random_f32_vector(10),
+        );
+        client.insert(schema.name(), vec![column], None).await?;
+    }
+
+    // Collections of tests running concurrently may be dropped while flushing
+    let flushed = match client.flush_all().await {
+        Ok(flushed) => flushed,
+        Err(milvus::error::Error::PartialFlush(flushed, _)) => flushed,
+        Err(err) => return Err(err),
+    };
+    for schema in [&first, &second] {
+        let segments = flushed.get(schema.name()).unwrap();
+        assert!(!segments.is_empty(), "{}: {:?}", schema.name(), segments);
+        client.drop_collection(schema.name()).await?;
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn flush_all_and_wait() -> Result<()> {
+    let (client, first) = create_test_collection(true).await?;
+    let (_, second) = create_test_collection(true).await?;
+    for schema in [&first, &second] {
+        let column = FieldColumn::new(
+            schema.get_field(DEFAULT_VEC_FIELD).unwrap(),
+            gen_random_f32_vector(10),
+        );
+        client.insert(schema.name(), vec![column], None).await?;
+    }
+
+    client
+        .flush_all_and_wait(std::time::Duration::from_secs(120))
+        .await?;
+    for schema in [&first, &second] {
+        client.drop_collection(schema.name()).await?;
+    }
+    Ok(())
+}
@@ -14,21 +14,29 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use milvus::client::ConsistencyLevel;
-use milvus::collection::{Collection, ParamValue};
-use milvus::data::FieldColumn;
-use milvus::error::Result;
-use milvus::index::{IndexParams, IndexType, MetricType};
-use milvus::mutate::InsertOptions;
-use milvus::options::LoadOptions;
-use milvus::query::{QueryOptions, SearchOptions};
+use milvus::client::{Client, ClientConfig, ConsistencyLevel};
+use milvus::collection::{Collection, ParamValue, TTL_PROPERTY};
+use milvus::data::{FieldColumn, ToRows};
+use milvus::error::{Error, Result};
+use milvus::expr::Expr;
+use milvus::index::{IndexBuilder, IndexConfig, IndexParams, IndexType, MetricType};
+use milvus::mutate::{DeleteOptions, InsertOptions};
+use milvus::options::{CreateCollectionOptions, LoadOptions};
+use milvus::proto::common::{KeyValuePair, LoadState, SegmentState};
+use milvus::proto::schema::i_ds::IdField;
+use milvus::proto::schema::{DataType, FunctionSchema, FunctionType};
+use milvus::query::{
+    AnnSearchRequest, FindDuplicatesOptions, IdType, QueryOptions, RrfRanker, SearchOptions,
+};
+use milvus::schema::{CollectionSchema, CollectionSchemaBuilder, FieldSchema};
+use milvus::sparse::SparseVector;
 use std::collections::HashMap;
 use tokio::time::{sleep, Duration};
 
 mod common;
 use common::*;
 
-use milvus::value::ValueVec;
+use milvus::value::{RowMap, Value, ValueVec};
 
 #[tokio::test]
 #[ignore]
@@ -39,6 +47,32 @@ async fn manual_compaction_empty_collection() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+#[ignore]
+async fn compaction_plans() -> Result<()> {
+    let (client, schema) = create_test_collection(true).await?;
+    for _ in 0..2 {
+        let embed_data = gen_random_f32_vector(100);
+        let embed_column =
+            FieldColumn::new(schema.get_field(DEFAULT_VEC_FIELD).unwrap(), embed_data);
+        client
+            .insert(schema.name(), vec![embed_column], None)
+            .await?;
+        client.flush(schema.name()).await?;
+    }
+
+    let info = client.manual_compaction(schema.name(), None).await?;
+    let plans = client.get_compaction_state_with_plans(info.id).await?;
+
+    // Small segments may or may not be planned for merging
+    for merge in &plans.merges {
+        assert!(!merge.sources.is_empty());
+    }
+
+    client.drop_collection(schema.name()).await?;
+    Ok(())
+}
+
 #[tokio::test]
 #[ignore]
 async fn collection_upsert() -> Result<()> {
@@ -123,10 +157,22 @@ async fn collection_index() -> Result<()> {
     assert!(index_list.len() == 1, "{}", index_list.len());
     let index = &index_list[0];
 
-    assert_eq!(index.params().name(), index_params.name());
-    assert_eq!(index.params().extra_params(), index_params.extra_params());
+    assert_eq!(index.index_name(), index_params.name());
+    assert_eq!(
+        index.try_params().unwrap().extra_params(),
+        index_params.extra_params()
+    );
+    assert_eq!(index.config(), &IndexConfig::IvfFlat { nlist: 32 });
+
+    client
+        .drop_index(schema.name(), DEFAULT_VEC_FIELD, Some(DEFAULT_INDEX_NAME))
+        .await?;
+    let index_list = client
+        .describe_index(schema.name(), DEFAULT_VEC_FIELD)
+        .await
+        .unwrap_or_default();
+    assert!(index_list.is_empty(), "{}", index_list.len());
 
-    client.drop_index(schema.name(), DEFAULT_VEC_FIELD).await?;
     client.drop_collection(schema.name()).await?;
     Ok(())
 }
@@ -172,6 +218,246 @@ async fn collection_search() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+#[ignore]
+async fn collection_search_for_each() -> Result<()> {
+    // the fixture has indexed and loaded the collection with ENTITYNUM rows
+    let (client, schema) = create_test_collection(true).await?;
+
+    let queries: Vec<_> = (0..3).map(|_| gen_random_f32_vector(1).into()).collect();
+    let options = || SearchOptions::with_limit(7).output_fields(vec!["id".to_owned()]);
+    let results = client
+        .search(schema.name(), queries.clone(), Some(options()))
+        .await?;
+    let total: i64 = results.iter().map(|r| r.size).sum();
+
+    let mut calls = vec![0; queries.len()];
+    client
+        .search_for_each(schema.name(), queries, Some(options()), |group, _| {
+            calls[group] += 1;
+            Ok(())
+        })
+        .await?;
+
+    assert_eq!(calls.iter().sum::<i64>(), total);
+    assert_eq!(calls, vec![7, 7, 7]);
+
+    client.drop_collection(schema.name()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_double_field() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", false))
+        .add_field(FieldSchema::new_float_vector(DEFAULT_VEC_FIELD, "", 4))
+        .add_field(FieldSchema::new_double("price", ""))
+        .build()?;
+    client.create_collection(schema.clone(), None).await?;
+
+    let prices = vec![
+        f64::MAX,
+        f64::MIN_POSITIVE,
+        0.1 + 0.2,
+        -(1.0 + f64::EPSILON),
+    ];
+    let columns = vec![
+        FieldColumn::new(schema.get_field("id").unwrap(), vec![0i64, 1, 2, 3]),
+        FieldColumn::new(
+            schema.get_field(DEFAULT_VEC_FIELD).unwrap(),
+            gen_random_f32_vector_custom(4, 4),
+        ),
+        FieldColumn::double(schema.get_field("price").unwrap(), prices.clone()),
+    ];
+    client.insert(&collection_name, columns, None).await?;
+    client.flush(&collection_name).await?;
+    let index_params = IndexParams::new(
+        DEFAULT_INDEX_NAME.to_owned(),
+        IndexType::Flat,
+        MetricType::L2,
+        HashMap::new(),
+    );
+    client
+        .create_index(&collection_name, DEFAULT_VEC_FIELD, index_params)
+        .await?;
+    client
+        .load_collection(&collection_name, Some(LoadOptions::default()))
+        .await?;
+
+    let options = QueryOptions::default()
+        .output_fields(vec!["id".to_owned(), "price".to_owned()])
+        .consistency_level(ConsistencyLevel::Strong);
+    let result = client.query(&collection_name, "id >= 0", &options).await?;
+    let ids = result.iter().find(|c| c.name == "id").unwrap();
+    let price = result.iter().find(|c| c.name == "price").unwrap();
+    assert_eq!(price.dtype, DataType::Double);
+    for i in 0..price.len() {
+        let (Some(Value::Long(id)), Some(Value::Double(value))) = (ids.get(i), price.get(i)) else {
+            panic!("unexpected values in row {i}");
+        };
+        assert_eq!(value, prices[id as usize]);
+    }
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_load_fields_expands_loaded_set() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", false))
+        .add_field(FieldSchema::new_float_vector(DEFAULT_VEC_FIELD, "", 4))
+        .add_field(FieldSchema::new_varchar("title", "", 32))
+        .add_field(FieldSchema::new_int64("year", ""))
+        .build()?;
+    client.create_collection(schema.clone(), None).await?;
+
+    let columns = vec![
+        FieldColumn::new(
+            schema.get_field("id").unwrap(),
+            (0..10).collect::<Vec<i64>>(),
+        ),
+        FieldColumn::new(
+            schema.get_field(DEFAULT_VEC_FIELD).unwrap(),
+            gen_random_f32_vector_custom(10, 4),
+        ),
+        FieldColumn::new(
+            schema.get_field("title").unwrap(),
+            (0..10).map(|i| format!("title_{i}")).collect::<Vec<_>>(),
+        ),
+        FieldColumn::new(
+            schema.get_field("year").unwrap(),
+            (0..10).map(|i| 2000 + i).collect::<Vec<i64>>(),
+        ),
+    ];
+    client.insert(&collection_name, columns, None).await?;
+    client.flush(&collection_name).await?;
+    let index_params = IndexParams::new(
+        DEFAULT_INDEX_NAME.to_owned(),
+        IndexType::Flat,
+        MetricType::L2,
+        HashMap::new(),
+    );
+    client
+        .create_index(&collection_name, DEFAULT_VEC_FIELD, index_params)
+        .await?;
+
+    let load_fields = vec![
+        "id".to_owned(),
+        DEFAULT_VEC_FIELD.to_owned(),
+        "title".to_owned(),
+    ];
+    client
+        .load_collection(
+            &collection_name,
+            Some(LoadOptions::new().load_fields(load_fields)),
+        )
+        .await?;
+
+    let options = QueryOptions::new()
+        .output_fields(vec!["year".to_owned()])
+        .consistency_level(ConsistencyLevel::Strong);
+    let not_loaded = client.query(&collection_name, "id >= 0", &options).await;
+    assert!(not_loaded.is_err());
+
+    let err = client
+        .load_fields(&collection_name, vec!["color".to_owned()])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Schema(_)));
+
+    client
+        .load_fields(&collection_name, vec!["year".to_owned()])
+        .await?;
+    let result = client.query(&collection_name, "id >= 0", &options).await?;
+    let year = result.iter().find(|c| c.name == "year").unwrap();
+    assert_eq!(year.len(), 10);
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_search_output_fields() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", false))
+        .add_field(FieldSchema::new_float_vector(DEFAULT_VEC_FIELD, "", 4))
+        .add_field(FieldSchema::new_varchar("title", "", 32))
+        .add_field(FieldSchema::new_int64("year", ""))
+        .build()?;
+    client.create_collection(schema.clone(), None).await?;
+
+    let columns = vec![
+        FieldColumn::new(
+            schema.get_field("id").unwrap(),
+            (0..20).collect::<Vec<i64>>(),
+        ),
+        FieldColumn::new(
+            schema.get_field(DEFAULT_VEC_FIELD).unwrap(),
+            gen_random_f32_vector_custom(20, 4),
+        ),
+        FieldColumn::new(
+            schema.get_field("title").unwrap(),
+            (0..20).map(|i| format!("title_{i}")).collect::<Vec<_>>(),
+        ),
+        FieldColumn::new(
+            schema.get_field("year").unwrap(),
+            (0..20).map(|i| 2000 + i).collect::<Vec<i64>>(),
+        ),
+    ];
+    client.insert(&collection_name, columns, None).await?;
+    client.flush(&collection_name).await?;
+    let index_params = IndexParams::new(
+        DEFAULT_INDEX_NAME.to_owned(),
+        IndexType::Flat,
+        MetricType::L2,
+        HashMap::new(),
+    );
+    client
+        .create_index(&collection_name, DEFAULT_VEC_FIELD, index_params)
+        .await?;
+    client
+        .load_collection(&collection_name, Some(LoadOptions::default()))
+        .await?;
+
+    let search = |options: SearchOptions| {
+        client.search(
+            &collection_name,
+            vec![gen_random_f32_vector_custom(1, 4).into()],
+            Some(options),
+        )
+    };
+
+    let result =
+        search(SearchOptions::with_limit(5).output_fields(vec!["title".to_owned()])).await?;
+    let names: Vec<&str> = result[0].field.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["title"]);
+
+    let result = search(SearchOptions::with_limit(5).output_all_fields()).await?;
+    let names: Vec<&str> = result[0].field.iter().map(|c| c.name.as_str()).collect();
+    assert!(
+        names.contains(&"title") && names.contains(&"year"),
+        "{names:?}"
+    );
+
+    let err = search(SearchOptions::with_limit(5).output_fields(vec!["color".to_owned()]))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Schema(_)));
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
+
 #[tokio::test]
 #[ignore]
 async fn collection_range_search() -> Result<()> {
@@ -220,3 +506,1826 @@ async fn collection_range_search() -> Result<()> {
     client.drop_collection(schema.name()).await?;
     Ok(())
 }
+
+#[tokio::test]
+#[ignore]
+async fn collection_search_stats() -> Result<()> {
+    let (client, schema) = create_test_collection(true).await?;
+
+    let option = SearchOptions::with_limit(10).add_param("nprobe", "16");
+    let query_vec = gen_random_f32_vector(1);
+
+    let result = client
+        .search(schema.name(), vec![query_vec.into()], Some(option))
+        .await?;
+
+    let stats = result[0].stats();
+    assert!(stats.elapsed > Duration::ZERO);
+    assert!(stats.all_search_count >= 0);
+
+    client.drop_collection(schema.name()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_field_data_type() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", true))
+        .add_field(FieldSchema::new_float_vector(
+            DEFAULT_VEC_FIELD,
+            "",
+            DEFAULT_DIM,
+        ))
+        .add_field(FieldSchema::new_sparse_float_vector("sparse", ""))
+        .build()?;
+    client.create_collection(schema, None).await?;
+
+    assert_eq!(
+        client.field_data_type(&collection_name, "sparse").await?,
+        DataType::SparseFloatVector
+    );
+    assert_eq!(
+        client
+            .field_data_type(&collection_name, DEFAULT_VEC_FIELD)
+            .await?,
+        DataType::FloatVector
+    );
+    assert!(
+        client
+            .field_data_type(&collection_name, "no_such_field")
+            .await
+            .is_err()
+    );
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_create_if_not_exists() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let build_schema = |dim| {
+        CollectionSchemaBuilder::new(&collection_name, "")
+            .add_field(FieldSchema::new_primary_int64("id", "", true))
+            .add_field(FieldSchema::new_float_vector(DEFAULT_VEC_FIELD, "", dim))
+            .build()
+    };
+
+    assert!(
+        client
+            .create_collection_if_not_exists(build_schema(DEFAULT_DIM)?, None)
+            .await?
+    );
+    assert!(
+        !client
+            .create_collection_if_not_exists(build_schema(DEFAULT_DIM)?, None)
+            .await?
+    );
+    let err = client
+        .create_collection_if_not_exists(build_schema(DEFAULT_DIM * 2)?, None)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Schema(milvus::schema::Error::SchemaMismatch(_, _))
+    ));
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_summary() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", true))
+        .add_field(FieldSchema::new_float_vector(
+            DEFAULT_VEC_FIELD,
+            "",
+            DEFAULT_DIM,
+        ))
+        .build()?;
+    client.create_collection(schema.clone(), None).await?;
+
+    let summary = client.collection_summary(schema.name()).await?;
+    assert!(summary.exists);
+    assert_eq!(summary.num_entities, 0);
+    assert!(!summary.is_indexed());
+
+    let feature = gen_random_f32_vector(100);
+    let feature_column = FieldColumn::new(schema.get_field(DEFAULT_VEC_FIELD).unwrap(), feature);
+    client
+        .insert(schema.name(), vec![feature_column], None)
+        .await?;
+    client.flush(schema.name()).await?;
+
+    let index_params = IndexParams::new(
+        DEFAULT_INDEX_NAME.to_owned(),
+        IndexType::IvfFlat,
+        MetricType::L2,
+        HashMap::from([("nlist".to_owned(), "32".to_owned())]),
+    );
+    client
+        .create_index(schema.name(), DEFAULT_VEC_FIELD, index_params)
+        .await?;
+    client
+        .load_collection(schema.name(), Some(LoadOptions::default()))
+        .await?;
+
+    let summary = client.collection_summary(schema.name()).await?;
+    assert!(summary.exists);
+    assert_eq!(summary.collection_name, schema.name());
+    assert_eq!(summary.num_entities, 100);
+    assert_eq!(summary.load_state, LoadState::Loaded);
+    assert_eq!(summary.loading_progress, 100);
+    assert_eq!(summary.indexes.len(), 1);
+    assert!(summary.is_indexed());
+
+    client.drop_collection(schema.name()).await?;
+
+    let summary = client.collection_summary(schema.name()).await?;
+    assert!(!summary.exists);
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_search_with_guarantee_timestamp() -> Result<()> {
+    // the fixture has indexed and loaded the collection
+    let (client, schema) = create_test_collection(true).await?;
+
+    // Insert after loading, so the row is only visible once the write is consumed
+    let query_vec = gen_random_f32_vector(1);
+    let embed_column = FieldColumn::new(
+        schema.get_field(DEFAULT_VEC_FIELD).unwrap(),
+        query_vec.clone(),
+    );
+    let insert_result = client
+        .insert(schema.name(), vec![embed_column], None)
+        .await?;
+    assert!(insert_result.timestamp > 0);
+
+    let option = SearchOptions::with_limit(1)
+        .add_param("nprobe", "16")
+        .guarantee_timestamp(insert_result.timestamp);
+    let result = client
+        .search(schema.name(), vec![query_vec.into()], Some(option))
+        .await?;
+
+    assert_eq!(result[0].size, 1);
+    let Some(IdField::IntId(inserted)) = insert_result.i_ds.and_then(|ids| ids.id_field) else {
+        panic!("invalid insert result");
+    };
+    assert!(matches!(result[0].id[0], Value::Long(id) if id == inserted.data[0]));
+
+    client.drop_collection(schema.name()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_search_sparse_batch() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", true))
+        .add_field(FieldSchema::new_sparse_float_vector("sparse", ""))
+        .build()?;
+    client.create_collection(schema.clone(), None).await?;
+
+    let rows: Vec<Vec<(u32, f32)>> = (0..100u32)
+        .map(|i| vec![(i % 10, 1.0), (10 + i % 7, 0.5), (20 + i, 0.25)])
+        .collect();
+    let sparse_column = FieldColumn::new(schema.get_field("sparse").unwrap(), rows);
+    client
+        .insert(&collection_name, vec![sparse_column], None)
+        .await?;
+    client.flush(&collection_name).await?;
+
+    let index_params = IndexParams::new(
+        "sparse_index".to_owned(),
+        IndexType::SparseInvertedIndex,
+        MetricType::IP,
+        HashMap::from_iter([("drop_ratio_build".to_owned(), "0.0".to_owned())]),
+    );
+    client
+        .create_index(&collection_name, "sparse", index_params)
+        .await?;
+    client
+        .load_collection(&collection_name, Some(LoadOptions::default()))
+        .await?;
+
+    let queries = vec![vec![(1, 1.0)], vec![(3, 0.5), (12, 1.0)], vec![(25, 2.0)]];
+    let options = || SearchOptions::with_limit(5).anns_field(vec!["sparse".to_owned()]);
+    let result = client
+        .search_sparse(&collection_name, queries, Some(options()))
+        .await?;
+    assert_eq!(result.len(), 3);
+    assert!(result.iter().all(|group| group.size > 0));
+
+    let err = client
+        .search_sparse(
+            &collection_name,
+            vec![vec![(1, 1.0)], vec![]],
+            Some(options()),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::SparseVectorError(_)));
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_search_sparse_output_dim() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", true))
+        .add_field(FieldSchema::new_sparse_float_vector("sparse", ""))
+        .build()?;
+    client.create_collection(schema.clone(), None).await?;
+
+    // every row ends at index 127, so any returned batch has dim 128
+    let rows: Vec<SparseVector> = (0..50u32)
+        .map(|i| vec![(i % 10, 1.0), (127, 0.1)])
+        .collect();
+    let sparse_column = FieldColumn::new(schema.get_field("sparse").unwrap(), rows);
+    client
+        .insert(&collection_name, vec![sparse_column], None)
+        .await?;
+    client.flush(&collection_name).await?;
+
+    let index_params = IndexParams::new(
+        "sparse_index".to_owned(),
+        IndexType::SparseInvertedIndex,
+        MetricType::IP,
+        HashMap::from_iter([("drop_ratio_build".to_owned(), "0.0".to_owned())]),
+    );
+    client
+        .create_index(&collection_name, "sparse", index_params)
+        .await?;
+    client
+        .load_collection(&collection_name, Some(LoadOptions::default()))
+        .await?;
+
+    let options = SearchOptions::with_limit(5)
+        .anns_field(vec!["sparse".to_owned()])
+        .output_fields(vec!["sparse".to_owned()]);
+    let result = client
+        .search_sparse(&collection_name, vec![vec![(1, 1.0)]], Some(options))
+        .await?;
+    assert!(result[0].size > 0);
+    assert_eq!(result[0].sparse_dim("sparse"), Some(128));
+    assert_eq!(result[0].sparse_dim("id"), None);
+    assert!(
+        result[0]
+            .hits()
+            .iter()
+            .all(|hit| matches!(hit.fields.get("sparse"), Some(Value::SparseFloatVector(_))))
+    );
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_hybrid_search_dense_and_sparse() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let dim = 8;
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", false))
+        .add_field(FieldSchema::new_float_vector("dense", "", dim))
+        .add_field(FieldSchema::new_sparse_float_vector("sparse", ""))
+        .build()?;
+    client.create_collection(schema.clone(), None).await?;
+
+    let ids: Vec<i64> = (0..100).collect();
+    let sparse_rows: Vec<SparseVector> = (0..100u32)
+        .map(|i| vec![(i % 10, 1.0), (10 + i, 0.5)])
+        .collect();
+    let columns = vec![
+        FieldColumn::new(schema.get_field("id").unwrap(), ids),
+        FieldColumn::new(
+            schema.get_field("dense").unwrap(),
+            gen_random_f32_vector_custom(100, dim),
+        ),
+        FieldColumn::new(schema.get_field("sparse").unwrap(), sparse_rows),
+    ];
+    client.insert(&collection_name, columns, None).await?;
+    client.flush(&collection_name).await?;
+
+    let dense_index = IndexParams::new(
+        "dense_index".to_owned(),
+        IndexType::Flat,
+        MetricType::L2,
+        HashMap::new(),
+    );
+    client
+        .create_index(&collection_name, "dense", dense_index)
+        .await?;
+    let sparse_index = IndexParams::new(
+        "sparse_index".to_owned(),
+        IndexType::SparseInvertedIndex,
+        MetricType::IP,
+        HashMap::from_iter([("drop_ratio_build".to_owned(), "0.0".to_owned())]),
+    );
+    client
+        .create_index(&collection_name, "sparse", sparse_index)
+        .await?;
+    client
+        .load_collection(&collection_name, Some(LoadOptions::default()))
+        .await?;
+
+    let metric = |metric: &str| {
+        vec![KeyValuePair {
+            key: "metric_type".to_owned(),
+            value: metric.to_owned(),
+        }]
+    };
+    let sparse_query: SparseVector = vec![(3, 1.0)];
+    let reqs = vec![
+        AnnSearchRequest::new(
+            vec![gen_random_f32_vector_custom(1, dim)],
+            "dense".to_owned(),
+            metric("L2"),
+            5,
+        ),
+        AnnSearchRequest::new(vec![sparse_query], "sparse".to_owned(), metric("IP"), 5),
+    ];
+    let result = client
+        .hybrid_search(
+            &collection_name,
+            reqs,
+            Box::new(RrfRanker::default()),
+            Some(SearchOptions::with_limit(5)),
+        )
+        .await?;
+    assert_eq!(result.len(), 1);
+    assert!(result[0].size > 0);
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_export_jsonl() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let dim = 4;
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", false))
+        .add_field(FieldSchema::new_float_vector("dense", "", dim))
+        .add_field(FieldSchema::new_sparse_float_vector("sparse", ""))
+        .enable_dynamic_field()
+        .build()?;
+    client.create_collection(schema, None).await?;
+
+    let num_rows = 25;
+    let rows = (0..num_rows)
+        .map(|i| {
+            RowMap::from([
+                ("id".to_owned(), Value::Long(i)),
+                (
+                    "dense".to_owned(),
+                    Value::from(gen_random_f32_vector_custom(1, dim)),
+                ),
+                ("sparse".to_owned(), Value::from(vec![(i as u32, 1.0f32)])),
+                ("rank".to_owned(), Value::Long(i * 10)),
+            ])
+        })
+        .collect();
+    client.insert_rows(&collection_name, rows, None).await?;
+    client.flush(&collection_name).await?;
+
+    for (field, index_type, metric) in [
+        ("dense", IndexType::Flat, MetricType::L2),
+        ("sparse", IndexType::SparseInvertedIndex, MetricType::IP),
+    ] {
+        let index_params =
+            IndexParams::new(format!("{field}_index"), index_type, metric, HashMap::new());
+        client
+            .create_index(&collection_name, field, index_params)
+            .await?;
+    }
+    client
+        .load_collection(&collection_name, Some(LoadOptions::default()))
+        .await?;
+
+    let mut out = Vec::new();
+    let exported = client.export_jsonl(&collection_name, &mut out, 10).await?;
+    assert_eq!(exported, num_rows as usize);
+
+    let lines: Vec<serde_json::Value> = String::from_utf8(out)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), num_rows as usize);
+    let first = &lines[0];
+    assert_eq!(first["dense"].as_array().unwrap().len(), dim as usize);
+    assert!(first["sparse"].is_object());
+    assert_eq!(first["rank"], first["id"].as_i64().unwrap() * 10);
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_import_jsonl() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", false))
+        .add_field(FieldSchema::new_float_vector("dense", "", 2))
+        .add_field(FieldSchema::new_sparse_float_vector("sparse", ""))
+        .enable_dynamic_field()
+        .build()?;
+    client.create_collection(schema, None).await?;
+
+    let jsonl = (0..5)
+        .map(|i| {
+            format!(
+                r#"{{"id": {i}, "dense": [{i}.0, 1.0], "sparse": {{"{i}": 0.5}}, "tag": "t{i}"}}"#
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let imported = client
+        .import_jsonl(&collection_name, jsonl.as_bytes(), 2, None)
+        .await?;
+    assert_eq!(imported, 5);
+
+    let err = client
+        .import_jsonl(
+            &collection_name,
+            &br#"{"id": 9, "dense": "oops", "sparse": {}}"#[..],
+            2,
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Import(1, _)));
+
+    client.flush(&collection_name).await?;
+    for (field, index_type, metric) in [
+        ("dense", IndexType::Flat, MetricType::L2),
+        ("sparse", IndexType::SparseInvertedIndex, MetricType::IP),
+    ] {
+        let index_params =
+            IndexParams::new(format!("{field}_index"), index_type, metric, HashMap::new());
+        client
+            .create_index(&collection_name, field, index_params)
+            .await?;
+    }
+    client
+        .load_collection(&collection_name, Some(LoadOptions::default()))
+        .await?;
+
+    let options = QueryOptions::default().output_fields(vec!["tag".to_owned()]);
+    let results = client
+        .query_strong(&collection_name, "id == 3", &options)
+        .await?;
+    assert_eq!(results.get_dynamic(0, "tag"), Some("t3".into()));
+    let results = client
+        .query_strong(&collection_name, "id >= 0", &QueryOptions::default())
+        .await?;
+    assert_eq!(results[0].len(), 5);
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_find_duplicates() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let dim = 8;
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", false))
+        .add_field(FieldSchema::new_float_vector(DEFAULT_VEC_FIELD, "", dim))
+        .build()?;
+    client.create_collection(schema.clone(), None).await?;
+
+    // 1, 2 and 3 are near-identical, 4 points in another direction
+    let base = [1.0f32, 0.5, 0.25, 0.0, 0.0, 0.0, 0.0, 0.0];
+    let mut vectors = Vec::new();
+    for delta in [0.0, 0.001, 0.002] {
+        vectors.extend(base.iter().map(|x| x + delta));
+    }
+    vectors.extend([0.0f32, 0.0, 0.0, 0.0, 1.0, 0.5, 0.25, 0.0]);
+    let pk_column = FieldColumn::new(schema.get_field("id").unwrap(), vec![1i64, 2, 3, 4]);
+    let vec_column = FieldColumn::new(schema.get_field(DEFAULT_VEC_FIELD).unwrap(), vectors);
+    client
+        .insert(&collection_name, vec![pk_column, vec_column], None)
+        .await?;
+    client.flush(&collection_name).await?;
+
+    let index_params = IndexParams::new(
+        "flat".to_owned(),
+        IndexType::Flat,
+        MetricType::COSINE,
+        HashMap::new(),
+    );
+    client
+        .create_index(&collection_name, DEFAULT_VEC_FIELD, index_params)
+        .await?;
+    client
+        .load_collection(&collection_name, Some(LoadOptions::default()))
+        .await?;
+
+    let options = FindDuplicatesOptions::new().exclude_self(IdType::Int64(vec![1, 4]));
+    let groups = client
+        .find_duplicates(
+            &collection_name,
+            DEFAULT_VEC_FIELD,
+            vec![base.to_vec(), vec![0.0, 0.0, 0.0, 0.0, 1.0, 0.5, 0.25, 0.0]],
+            0.99,
+            MetricType::COSINE,
+            Some(options),
+        )
+        .await?;
+
+    assert_eq!(groups.len(), 2);
+    let mut duplicates: Vec<i64> = groups[0]
+        .iter()
+        .map(|hit| match hit.id {
+            Value::Long(id) => id,
+            _ => panic!("unexpected id type"),
+        })
+        .collect();
+    duplicates.sort();
+    assert_eq!(duplicates, vec![2, 3]);
+    assert!(groups[1].is_empty());
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_list_with_state() -> Result<()> {
+    let (client, loaded) = create_test_collection(true).await?;
+    let (_, released) = create_test_collection(true).await?;
+    // the fixture loads both collections
+    client.release_collection(released.name()).await?;
+
+    let states: HashMap<String, LoadState> = client
+        .list_collections_with_state()
+        .await?
+        .into_iter()
+        .collect();
+    assert_eq!(states[loaded.name()], LoadState::Loaded);
+    assert_eq!(states[released.name()], LoadState::NotLoad);
+
+    client.drop_collection(loaded.name()).await?;
+    client.drop_collection(released.name()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_insert_nullable_sparse() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", false))
+        .add_field(FieldSchema::new_sparse_float_vector("sparse", "").with_nullable())
+        .build()?;
+    client.create_collection(schema.clone(), None).await?;
+
+    let pk_column = FieldColumn::new(schema.get_field("id").unwrap(), vec![1i64, 2, 3]);
+    let sparse_column = FieldColumn::new_nullable_sparse(
+        schema.get_field("sparse").unwrap(),
+        vec![Some(vec![(1, 0.5)]), None, Some(vec![(4, 1.0)])],
+    );
+    client
+        .insert(&collection_name, vec![pk_column, sparse_column], None)
+        .await?;
+    client.flush(&collection_name).await?;
+
+    let index_params = IndexParams::new(
+        "sparse_index".to_owned(),
+        IndexType::SparseInvertedIndex,
+        MetricType::IP,
+        HashMap::new(),
+    );
+    client
+        .create_index(&collection_name, "sparse", index_params)
+        .await?;
+    client
+        .load_collection(&collection_name, Some(LoadOptions::default()))
+        .await?;
+
+    let options = QueryOptions::new().output_fields(vec!["id".to_owned(), "sparse".to_owned()]);
+    let result = client
+        .query(&collection_name, "id in [1, 2, 3]", &options)
+        .await?;
+    let ids = result.iter().find(|c| c.name == "id").unwrap();
+    let sparse = result.iter().find(|c| c.name == "sparse").unwrap();
+    for row in 0..ids.len() {
+        match ids.get(row) {
+            Some(Value::Long(2)) => assert!(matches!(sparse.get(row), Some(Value::None))),
+            _ => assert!(matches!(sparse.get(row), Some(Value::SparseFloatVector(_)))),
+        }
+    }
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_insert_sink() -> Result<()> {
+    let (client, schema) = create_test_collection(false).await?;
+
+    let sink = client
+        .insert_sink(schema.name(), 100, Duration::from_secs(60))
+        .await?;
+    for id in 0..250i64 {
+        let mut row = RowMap::new();
+        row.insert("id".to_owned(), Value::Long(id));
+        row.insert(
+            DEFAULT_VEC_FIELD.to_owned(),
+            gen_random_f32_vector_custom(1, DEFAULT_DIM).into(),
+        );
+        sink.send(row).await?;
+    }
+    let results = sink.close().await?;
+
+    assert_eq!(results.len(), 3);
+    let mut ids = Vec::new();
+    for result in results {
+        let Some(IdField::IntId(inserted)) = result.i_ds.and_then(|ids| ids.id_field) else {
+            panic!("invalid insert result");
+        };
+        ids.extend(inserted.data);
+    }
+    ids.sort();
+    assert_eq!(ids, (0..250).collect::<Vec<_>>());
+
+    client.drop_collection(schema.name()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_persistent_segment_info() -> Result<()> {
+    let (client, schema) = create_test_collection(true).await?;
+    client.flush(schema.name()).await?;
+
+    let segments = client.get_persistent_segment_info(schema.name()).await?;
+    assert!(segments.iter().any(|segment| {
+        matches!(segment.state, SegmentState::Sealed | SegmentState::Flushed)
+            && segment.num_rows > 0
+    }));
+
+    client.drop_collection(schema.name()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_load_fields() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", true))
+        .add_field(FieldSchema::new_float_vector(
+            DEFAULT_VEC_FIELD,
+            "",
+            DEFAULT_DIM,
+        ))
+        .add_field(FieldSchema::new_varchar("title", "", 64))
+        .build()?;
+    client.create_collection(schema.clone(), None).await?;
+
+    let vec_column = FieldColumn::new(
+        schema.get_field(DEFAULT_VEC_FIELD).unwrap(),
+        gen_random_f32_vector(10),
+    );
+    let title_column = FieldColumn::new(
+        schema.get_field("title").unwrap(),
+        (0..10).map(|i| format!("title {}", i)).collect::<Vec<_>>(),
+    );
+    client
+        .insert(&collection_name, vec![vec_column, title_column], None)
+        .await?;
+
+    let index_params = IndexParams::new(
+        DEFAULT_INDEX_NAME.to_owned(),
+        IndexType::Flat,
+        MetricType::L2,
+        HashMap::new(),
+    );
+    client
+        .create_index(&collection_name, DEFAULT_VEC_FIELD, index_params)
+        .await?;
+
+    // Leaving out the vector field is rejected before reaching the server
+    let missing_vector = LoadOptions::new().load_fields(vec!["id".to_owned(), "title".to_owned()]);
+    assert!(
+        client
+            .load_collection(&collection_name, Some(missing_vector))
+            .await
+            .is_err()
+    );
+
+    let options = LoadOptions::new()
+        .load_fields(vec!["id".to_owned(), DEFAULT_VEC_FIELD.to_owned()])
+        .skip_load_dynamic_field(true);
+    client
+        .load_collection(&collection_name, Some(options))
+        .await?;
+
+    let result = client
+        .search(
+            &collection_name,
+            vec![gen_random_f32_vector(1).into()],
+            Some(SearchOptions::with_limit(3)),
+        )
+        .await?;
+    assert_eq!(result[0].size, 3);
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_refresh_load() -> Result<()> {
+    // the fixture has indexed and loaded the collection
+    let (client, schema) = create_test_collection(true).await?;
+
+    let query_vec = gen_random_f32_vector(1);
+    let embed_column = FieldColumn::new(
+        schema.get_field(DEFAULT_VEC_FIELD).unwrap(),
+        query_vec.clone(),
+    );
+    let insert_result = client
+        .insert(schema.name(), vec![embed_column], None)
+        .await?;
+    client.flush(schema.name()).await?;
+    client.refresh_load(schema.name()).await?;
+
+    let option = SearchOptions::with_limit(1).guarantee_timestamp(insert_result.timestamp);
+    let result = client
+        .search(schema.name(), vec![query_vec.into()], Some(option))
+        .await?;
+    let Some(IdField::IntId(inserted)) = insert_result.i_ds.and_then(|ids| ids.id_field) else {
+        panic!("invalid insert result");
+    };
+    assert!(matches!(result[0].id[0], Value::Long(id) if id == inserted.data[0]));
+
+    client.drop_collection(schema.name()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_varchar_pk_escaping() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_varchar("pk", "", false, 64))
+        .add_field(FieldSchema::new_float_vector(DEFAULT_VEC_FIELD, "", 4))
+        .build()?;
+    client.create_collection(schema.clone(), None).await?;
+
+    let pks = vec![
+        r#"a"b\c"#.to_owned(),
+        "line\nbreak".to_owned(),
+        "plain".to_owned(),
+    ];
+    let pk_column = FieldColumn::new(schema.get_field("pk").unwrap(), pks.clone());
+    let vec_column = FieldColumn::new(
+        schema.get_field(DEFAULT_VEC_FIELD).unwrap(),
+        gen_random_f32_vector_custom(3, 4),
+    );
+    client
+        .insert(&collection_name, vec![pk_column, vec_column], None)
+        .await?;
+    client.flush(&collection_name).await?;
+
+    let index_params = IndexParams::new(
+        DEFAULT_INDEX_NAME.to_owned(),
+        IndexType::Flat,
+        MetricType::L2,
+        HashMap::new(),
+    );
+    client
+        .create_index(&collection_name, DEFAULT_VEC_FIELD, index_params)
+        .await?;
+    client
+        .load_collection(&collection_name, Some(LoadOptions::default()))
+        .await?;
+
+    let rows = client
+        .get(&collection_name, IdType::VarChar(pks.clone()), None)
+        .await?;
+    assert_eq!(rows.len(), 3);
+    for (row, pk) in rows.iter().zip(&pks) {
+        let row = row.as_ref().expect("row should be found by its escaped pk");
+        assert!(matches!(&row["pk"], Value::String(v) if v == pk));
+    }
+
+    client
+        .delete(
+            &collection_name,
+            &DeleteOptions::with_ids(ValueVec::String(vec![pks[0].clone()])),
+        )
+        .await?;
+    let rows = client
+        .get(&collection_name, IdType::VarChar(pks.clone()), None)
+        .await?;
+    assert!(rows[0].is_none());
+    assert!(rows[1].is_some());
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_delete_by_ids_in_batches() -> Result<()> {
+    // the fixture has indexed and loaded the collection with ENTITYNUM rows
+    let (client, schema) = create_test_collection(false).await?;
+    let ids: Vec<i64> = (0..5000).collect();
+    let columns = vec![
+        FieldColumn::new(schema.get_field("id").unwrap(), ids.clone()),
+        FieldColumn::new(
+            schema.get_field(DEFAULT_VEC_FIELD).unwrap(),
+            gen_random_f32_vector(5000),
+        ),
+    ];
+    client.insert(schema.name(), columns, None).await?;
+    client.flush(schema.name()).await?;
+
+    let result = client
+        .delete_by_ids(schema.name(), IdType::Int64(ids), 1000)
+        .await?;
+    assert_eq!(result.batches, 5);
+    assert_eq!(result.delete_cnt, 5000);
+
+    let options = QueryOptions::new().consistency_level(ConsistencyLevel::Strong);
+    assert_eq!(client.count(schema.name(), "", &options).await?, ENTITYNUM);
+
+    client.drop_collection(schema.name()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_ttl_read_back() -> Result<()> {
+    let (client, schema) = create_test_collection(true).await?;
+    assert_eq!(client.describe_collection(schema.name()).await?.ttl, None);
+
+    client
+        .alter_collection_properties(
+            schema.name(),
+            HashMap::from([(TTL_PROPERTY.to_owned(), "3600".to_owned())]),
+        )
+        .await?;
+    let description = client.describe_collection(schema.name()).await?;
+    assert_eq!(description.ttl, Some(Duration::from_secs(60 * 60)));
+
+    client.drop_collection(schema.name()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_insert_chunks_matches_insert() -> Result<()> {
+    // the fixture has indexed and loaded the collection with ENTITYNUM rows
+    let (client, schema) = create_test_collection(false).await?;
+    let vectors = gen_random_f32_vector(1100);
+    let dim = DEFAULT_DIM as usize;
+    let columns = |ids: std::ops::Range<i64>| {
+        let rows = ids.start as usize * dim..ids.end as usize * dim;
+        vec![
+            FieldColumn::new(schema.get_field("id").unwrap(), ids.collect::<Vec<i64>>()),
+            FieldColumn::new(
+                schema.get_field(DEFAULT_VEC_FIELD).unwrap(),
+                vectors[rows].to_vec(),
+            ),
+        ]
+    };
+
+    // chunks are produced lazily by the adapter, 100 rows at a time
+    let chunks = (0..10).map(|i| columns(i * 100..(i + 1) * 100));
+    let chunked = client.insert_chunks(schema.name(), chunks, None).await?;
+    let whole = client
+        .insert(schema.name(), columns(1000..1100), None)
+        .await?;
+    assert_eq!(chunked.insert_cnt, 1000);
+    assert_eq!(whole.insert_cnt, 100);
+    client.flush(schema.name()).await?;
+
+    let options = QueryOptions::new().consistency_level(ConsistencyLevel::Strong);
+    assert_eq!(
+        client.count(schema.name(), "", &options).await?,
+        ENTITYNUM + 1100
+    );
+
+    let options = options.output_fields(vec![DEFAULT_VEC_FIELD.to_owned()]);
+    for id in [555i64, 1055] {
+        let result = client
+            .query(schema.name(), &format!("id == {id}"), &options)
+            .await?;
+        let column = result.iter().find(|c| c.name == DEFAULT_VEC_FIELD).unwrap();
+        let start = id as usize * dim;
+        assert!(matches!(
+            column.get(0),
+            Some(Value::FloatArray(v)) if v.as_ref() == &vectors[start..start + dim]
+        ));
+    }
+
+    client.drop_collection(schema.name()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_describe_hnsw_index() -> Result<()> {
+    // replace the fixture's IVF_FLAT index, which needs the collection released
+    let (client, schema) = create_test_collection(true).await?;
+    client.release_collection(schema.name()).await?;
+    client
+        .drop_index(schema.name(), DEFAULT_VEC_FIELD, Some(DEFAULT_INDEX_NAME))
+        .await?;
+    let index_params = IndexParams::new(
+        DEFAULT_INDEX_NAME.to_owned(),
+        IndexType::HNSW,
+        MetricType::L2,
+        HashMap::from([
+            ("M".to_owned(), "16".to_owned()),
+            ("efConstruction".to_owned(), "200".to_owned()),
+        ]),
+    );
+    client
+        .create_index(schema.name(), DEFAULT_VEC_FIELD, index_params.clone())
+        .await?;
+
+    let index_list = client
+        .describe_index(schema.name(), DEFAULT_VEC_FIELD)
+        .await?;
+    assert_eq!(index_list.len(), 1);
+    assert_eq!(
+        index_list[0].config(),
+        &IndexConfig::Hnsw {
+            m: 16,
+            ef_construction: 200
+        }
+    );
+    assert_eq!(index_list[0].config(), &index_params.config());
+
+    client.drop_collection(schema.name()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_query_strong() -> Result<()> {
+    // the fixture has indexed and loaded the collection
+    let (client, schema) = create_test_collection(false).await?;
+
+    let pk_col = FieldColumn::new(schema.get_field("id").unwrap(), vec![424242i64]);
+    let vec_col = FieldColumn::new(
+        schema.get_field(DEFAULT_VEC_FIELD).unwrap(),
+        gen_random_f32_vector(1),
+    );
+    client
+        .insert(schema.name(), vec![pk_col, vec_col], None)
+        .await?;
+
+    let options = QueryOptions::default().output_fields(vec!["id".to_owned()]);
+    let result = client
+        .query_strong(schema.name(), "id == 424242", &options)
+        .await?;
+    let id_column = result.iter().find(|c| c.name == "id").unwrap();
+    assert!(matches!(&id_column.value, ValueVec::Long(ids) if ids == &[424242]));
+
+    client.drop_collection(schema.name()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_insert_auto_id_pk_rejected() -> Result<()> {
+    let (client, schema) = create_test_collection(true).await?;
+    let pk_col = FieldColumn::new(schema.get_field("id").unwrap(), vec![1i64]);
+    let vec_col = FieldColumn::new(
+        schema.get_field(DEFAULT_VEC_FIELD).unwrap(),
+        gen_random_f32_vector(1),
+    );
+
+    let err = client
+        .insert(schema.name(), vec![pk_col, vec_col], None)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, Error::Schema(milvus::schema::Error::AutoIdProvided(name)) if name == "id"),
+        "{err}"
+    );
+
+    client.drop_collection(schema.name()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_handle() -> Result<()> {
+    let (client, schema) = create_test_collection(false).await?;
+    let collection = client.collection(schema.name());
+    assert_eq!(collection.name(), schema.name());
+
+    let pk_col = FieldColumn::new(schema.get_field("id").unwrap(), vec![7i64, 8, 9]);
+    let vec_col = FieldColumn::new(
+        schema.get_field(DEFAULT_VEC_FIELD).unwrap(),
+        gen_random_f32_vector(3),
+    );
+    collection.insert(vec![pk_col, vec_col], None).await?;
+    collection.flush().await?;
+
+    // the fixture has indexed and loaded the collection, reload it from the flushed segments
+    collection.release().await?;
+    collection.load(Some(LoadOptions::default())).await?;
+
+    let options = QueryOptions::default().output_fields(vec!["id".to_owned()]);
+    let expr = "id in [7, 8, 9]";
+    let from_handle = client
+        .collection(schema.name())
+        .query(expr, &options)
+        .await?;
+    let from_client = client.query(schema.name(), expr, &options).await?;
+    let ids = |columns: &[FieldColumn]| {
+        let column = columns.iter().find(|c| c.name == "id").unwrap();
+        let ValueVec::Long(ids) = &column.value else {
+            panic!("unexpected id column {:?}", column.value);
+        };
+        let mut ids = ids.clone();
+        ids.sort();
+        ids
+    };
+    assert_eq!(ids(&from_handle), vec![7, 8, 9]);
+    assert_eq!(ids(&from_handle), ids(&from_client));
+    assert_eq!(
+        collection.stats().await?,
+        client.get_collection_stats(schema.name(), None).await?
+    );
+
+    collection.release().await?;
+    client.drop_collection(schema.name()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_shard_num() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", true))
+        .add_field(FieldSchema::new_float_vector(
+            DEFAULT_VEC_FIELD,
+            "",
+            DEFAULT_DIM,
+        ))
+        .build()?;
+
+    let err = client
+        .create_collection(
+            schema.clone(),
+            Some(CreateCollectionOptions::with_shard_num(17)),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidParameter(param, _) if param == "shard_num"));
+
+    client
+        .create_collection(schema, Some(CreateCollectionOptions::with_shard_num(2)))
+        .await?;
+    let description = client.describe_collection(&collection_name).await?;
+    assert_eq!(description.shards_num, 2);
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_search_group_size() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", false))
+        .add_field(FieldSchema::new_int64("author", ""))
+        .add_field(FieldSchema::new_float_vector(DEFAULT_VEC_FIELD, "", 4))
+        .build()?;
+    client.create_collection(schema.clone(), None).await?;
+
+    let rows = 30i64;
+    let columns = vec![
+        FieldColumn::new(
+            schema.get_field("id").unwrap(),
+            (0..rows).collect::<Vec<_>>(),
+        ),
+        FieldColumn::new(
+            schema.get_field("author").unwrap(),
+            (0..rows).map(|i| i % 5).collect::<Vec<_>>(),
+        ),
+        FieldColumn::new(
+            schema.get_field(DEFAULT_VEC_FIELD).unwrap(),
+            gen_random_f32_vector_custom(rows, 4),
+        ),
+    ];
+    client.insert(&collection_name, columns, None).await?;
+    client.flush(&collection_name).await?;
+
+    let index_params = IndexParams::new(
+        DEFAULT_INDEX_NAME.to_owned(),
+        IndexType::Flat,
+        MetricType::L2,
+        HashMap::new(),
+    );
+    client
+        .create_index(&collection_name, DEFAULT_VEC_FIELD, index_params)
+        .await?;
+    client
+        .load_collection(&collection_name, Some(LoadOptions::default()))
+        .await?;
+
+    let options = SearchOptions::with_limit(3)
+        .anns_field(vec![DEFAULT_VEC_FIELD.to_owned()])
+        .add_param("metric_type", "L2")
+        .output_fields(vec!["author".to_owned()])
+        .group_by_field("author")
+        .group_size(2);
+    let results = client
+        .search(
+            &collection_name,
+            vec![Value::from(gen_random_f32_vector_custom(1, 4))],
+            Some(options),
+        )
+        .await?;
+    assert_eq!(results.len(), 1);
+
+    let mut hits_per_author: HashMap<i64, usize> = HashMap::new();
+    for hit in &results[0] {
+        let Value::Long(author) = hit.fields["author"] else {
+            panic!("unexpected author {:?}", hit.fields["author"]);
+        };
+        *hits_per_author.entry(author).or_default() += 1;
+    }
+    assert!(!hits_per_author.is_empty() && hits_per_author.len() <= 3);
+    assert!(hits_per_author.values().all(|&hits| hits <= 2));
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_load_state() -> Result<()> {
+    // the fixture has indexed and loaded the collection
+    let (client, schema) = create_test_collection(true).await?;
+    client.release_collection(schema.name()).await?;
+    assert_eq!(
+        client.get_load_state(schema.name(), None).await?,
+        LoadState::NotLoad
+    );
+
+    client
+        .load_collection(schema.name(), Some(LoadOptions::default()))
+        .await?;
+    client
+        .wait_until_loaded(schema.name(), None, Duration::from_secs(60))
+        .await?;
+    assert_eq!(
+        client.get_load_state(schema.name(), None).await?,
+        LoadState::Loaded
+    );
+
+    client.drop_collection(schema.name()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_multiple_vector_fields() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", false))
+        .add_field(FieldSchema::new_float_vector("text_dense", "", 4))
+        .add_field(FieldSchema::new_float_vector("image_dense", "", 8))
+        .build()?;
+    client.create_collection(schema.clone(), None).await?;
+
+    let rows = 20i64;
+    let columns = vec![
+        FieldColumn::new(
+            schema.get_field("id").unwrap(),
+            (0..rows).collect::<Vec<_>>(),
+        ),
+        FieldColumn::new(
+            schema.get_field("text_dense").unwrap(),
+            gen_random_f32_vector_custom(rows, 4),
+        ),
+        FieldColumn::new(
+            schema.get_field("image_dense").unwrap(),
+            gen_random_f32_vector_custom(rows, 8),
+        ),
+    ];
+    client.insert(&collection_name, columns, None).await?;
+    client.flush(&collection_name).await?;
+
+    let index_params = |name: &str| {
+        IndexParams::new(
+            name.to_owned(),
+            IndexType::Flat,
+            MetricType::L2,
+            HashMap::new(),
+        )
+    };
+    client
+        .create_index(&collection_name, "text_dense", index_params("text_index"))
+        .await?;
+
+    let err = client
+        .load_collection(&collection_name, Some(LoadOptions::default()))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            &err,
+            Error::Collection(milvus::collection::Error::VectorFieldNotIndexed(missing))
+                if missing == &["image_dense"]
+        ),
+        "{err}"
+    );
+
+    client
+        .create_index(&collection_name, "image_dense", index_params("image_index"))
+        .await?;
+    client
+        .load_collection(&collection_name, Some(LoadOptions::default()))
+        .await?;
+
+    for (field, dim) in [("text_dense", 4), ("image_dense", 8)] {
+        let options = SearchOptions::with_limit(5)
+            .anns_field(vec![field.to_owned()])
+            .add_param("metric_type", "L2");
+        let results = client
+            .search(
+                &collection_name,
+                vec![Value::from(gen_random_f32_vector_custom(1, dim))],
+                Some(options),
+            )
+            .await?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].size, 5, "search on {field}");
+    }
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_load_with_scalar_index() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", true))
+        .add_field(FieldSchema::new_varchar("title", "", 64))
+        .add_field(FieldSchema::new_float_vector(DEFAULT_VEC_FIELD, "", 4))
+        .build()?;
+    client.create_collection(schema.clone(), None).await?;
+
+    let rows = 10i64;
+    let columns = vec![
+        FieldColumn::new(
+            schema.get_field("title").unwrap(),
+            (0..rows).map(|i| format!("title {i}")).collect::<Vec<_>>(),
+        ),
+        FieldColumn::new(
+            schema.get_field(DEFAULT_VEC_FIELD).unwrap(),
+            gen_random_f32_vector_custom(rows, 4),
+        ),
+    ];
+    client.insert(&collection_name, columns, None).await?;
+    client.flush(&collection_name).await?;
+
+    let title_index = IndexBuilder::new()
+        .field(schema.get_field("title").unwrap())
+        .index_type(IndexType::Trie)
+        .build()?;
+    client
+        .create_index_with(&collection_name, title_index)
+        .await?;
+    let vector_index = IndexBuilder::new()
+        .field(schema.get_field(DEFAULT_VEC_FIELD).unwrap())
+        .index_type(IndexType::Flat)
+        .metric(MetricType::L2)
+        .build()?;
+    client
+        .create_index_with(&collection_name, vector_index)
+        .await?;
+
+    client
+        .load_collection(&collection_name, Some(LoadOptions::default()))
+        .await?;
+    assert_eq!(
+        client.get_load_state(&collection_name, None).await?,
+        LoadState::Loaded
+    );
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_insert_rows_dynamic_fields() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", false))
+        .add_field(FieldSchema::new_float_vector(DEFAULT_VEC_FIELD, "", 4))
+        .enable_dynamic_field()
+        .build()?;
+    client.create_collection(schema, None).await?;
+
+    let rows = (0..10i64)
+        .map(|i| {
+            RowMap::from([
+                ("id".to_owned(), Value::Long(i)),
+                (
+                    DEFAULT_VEC_FIELD.to_owned(),
+                    Value::from(gen_random_f32_vector_custom(1, 4)),
+                ),
+                ("color".to_owned(), Value::from(format!("color_{i}"))),
+                ("rank".to_owned(), Value::Long(i * 10)),
+            ])
+        })
+        .collect();
+    let result = client.insert_rows(&collection_name, rows, None).await?;
+    assert_eq!(result.insert_cnt, 10);
+    client.flush(&collection_name).await?;
+
+    client
+        .create_index(
+            &collection_name,
+            DEFAULT_VEC_FIELD,
+            IndexParams::new(
+                DEFAULT_INDEX_NAME.to_owned(),
+                IndexType::Flat,
+                MetricType::L2,
+                HashMap::new(),
+            ),
+        )
+        .await?;
+    client
+        .load_collection(&collection_name, Some(LoadOptions::default()))
+        .await?;
+
+    let options =
+        QueryOptions::default().output_fields(vec!["color".to_owned(), "rank".to_owned()]);
+    let results = client
+        .query_strong(&collection_name, "id == 3", &options)
+        .await?;
+    assert_eq!(results.get_dynamic(0, "color"), Some("color_3".into()));
+    assert_eq!(results.get_dynamic(0, "rank"), Some(30.into()));
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_describe_indexes() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", true))
+        .add_field(FieldSchema::new_varchar("title", "", 64))
+        .add_field(FieldSchema::new_float_vector(DEFAULT_VEC_FIELD, "", 4))
+        .build()?;
+    client.create_collection(schema, None).await?;
+
+    assert!(client.describe_indexes(&collection_name).await?.is_empty());
+    assert!(
+        client
+            .list_indexes(&collection_name, None)
+            .await?
+            .is_empty()
+    );
+
+    client
+        .create_index(
+            &collection_name,
+            DEFAULT_VEC_FIELD,
+            IndexParams::new(
+                "vector_index".to_owned(),
+                IndexType::Flat,
+                MetricType::L2,
+                HashMap::new(),
+            ),
+        )
+        .await?;
+    client
+        .create_index(
+            &collection_name,
+            "title",
+            IndexParams::new(
+                "title_index".to_owned(),
+                IndexType::Trie,
+                MetricType::L2,
+                HashMap::new(),
+            ),
+        )
+        .await?;
+
+    let mut indexes: Vec<(String, String)> = client
+        .describe_indexes(&collection_name)
+        .await?
+        .into_iter()
+        .map(|index| (index.index_name().to_owned(), index.field_name().to_owned()))
+        .collect();
+    indexes.sort();
+    assert_eq!(
+        indexes,
+        vec![
+            ("title_index".to_owned(), "title".to_owned()),
+            ("vector_index".to_owned(), DEFAULT_VEC_FIELD.to_owned()),
+        ]
+    );
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_upsert_counts() -> Result<()> {
+    // an empty collection, so the count is only the upserted ids
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", false))
+        .add_field(FieldSchema::new_float_vector(
+            DEFAULT_VEC_FIELD,
+            "",
+            DEFAULT_DIM,
+        ))
+        .build()?;
+    client.create_collection(schema.clone(), None).await?;
+    let columns = |ids: Vec<i64>| {
+        let vectors = gen_random_f32_vector(ids.len() as i64);
+        vec![
+            FieldColumn::new(schema.get_field("id").unwrap(), ids),
+            FieldColumn::new(schema.get_field(DEFAULT_VEC_FIELD).unwrap(), vectors),
+        ]
+    };
+    client
+        .insert(schema.name(), columns((0..10).collect()), None)
+        .await?;
+
+    // ids 5..10 exist and are updated, 10..15 are new
+    let result = client
+        .upsert(schema.name(), columns((5..15).collect()), None)
+        .await?;
+    assert_eq!(result.upsert_cnt, 10);
+    client.flush(schema.name()).await?;
+
+    client
+        .create_index(
+            schema.name(),
+            DEFAULT_VEC_FIELD,
+            IndexParams::new(
+                DEFAULT_INDEX_NAME.to_owned(),
+                IndexType::Flat,
+                MetricType::L2,
+                HashMap::new(),
+            ),
+        )
+        .await?;
+    client
+        .load_collection(schema.name(), Some(LoadOptions::default()))
+        .await?;
+    let options = QueryOptions::default().output_fields(vec![String::from("count(*)")]);
+    let result = client.query_strong(schema.name(), "", &options).await?;
+    assert!(matches!(&result[0].value, ValueVec::Long(count) if count[0] == 15));
+
+    client.drop_collection(schema.name()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_precheck_loaded() -> Result<()> {
+    let (_, schema) = create_test_collection(true).await?;
+    let client = Client::with_config(ClientConfig::new(URL).precheck_loaded(true)).await?;
+    client.release_collection(schema.name()).await?;
+
+    let options = || SearchOptions::with_limit(1).anns_field(vec![DEFAULT_VEC_FIELD.to_owned()]);
+    let err = client
+        .search(
+            schema.name(),
+            vec![Value::from(gen_random_f32_vector(1))],
+            Some(options()),
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, Error::Collection(milvus::collection::Error::NotLoaded(name)) if name == schema.name()),
+        "{err:?}"
+    );
+
+    let err = client
+        .query("missing_collection", "id > 0", &QueryOptions::new())
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            &err,
+            Error::Collection(milvus::collection::Error::CollectionNotFound(_))
+        ),
+        "{err:?}"
+    );
+
+    client.load_collection(schema.name(), None).await?;
+    client
+        .search(
+            schema.name(),
+            vec![Value::from(gen_random_f32_vector(1))],
+            Some(options()),
+        )
+        .await?;
+
+    client.drop_collection(schema.name()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_search_ignore_growing() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", false))
+        .add_field(FieldSchema::new_float_vector(DEFAULT_VEC_FIELD, "", 4))
+        .build()?;
+    client.create_collection(schema.clone(), None).await?;
+    let columns = |ids: Vec<i64>| {
+        let vectors = gen_random_f32_vector_custom(ids.len() as i64, 4);
+        vec![
+            FieldColumn::new(schema.get_field("id").unwrap(), ids),
+            FieldColumn::new(schema.get_field(DEFAULT_VEC_FIELD).unwrap(), vectors),
+        ]
+    };
+
+    client
+        .insert(&collection_name, columns((0..10).collect()), None)
+        .await?;
+    client.flush(&collection_name).await?;
+    client
+        .create_index(
+            &collection_name,
+            DEFAULT_VEC_FIELD,
+            IndexParams::new(
+                DEFAULT_INDEX_NAME.to_owned(),
+                IndexType::Flat,
+                MetricType::L2,
+                HashMap::new(),
+            ),
+        )
+        .await?;
+    client
+        .load_collection(&collection_name, Some(LoadOptions::default()))
+        .await?;
+
+    // not flushed, so the row stays in a growing segment
+    let inserted = client
+        .insert(&collection_name, columns(vec![100]), None)
+        .await?;
+
+    for (ignore_growing, expected) in [(true, 0), (false, 1)] {
+        let options = SearchOptions::with_limit(5)
+            .filter("id == 100".to_owned())
+            .add_param("metric_type", "L2")
+            .guarantee_timestamp(inserted.timestamp)
+            .ignore_growing(ignore_growing);
+        let results = client
+            .search(
+                &collection_name,
+                vec![Value::from(gen_random_f32_vector_custom(1, 4))],
+                Some(options),
+            )
+            .await?;
+        assert_eq!(
+            results[0].size, expected,
+            "ignore_growing: {ignore_growing}"
+        );
+    }
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_create_with_bm25_function() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", true))
+        .add_field(FieldSchema::new_varchar("text", "", 1024).with_analyzer())
+        .add_field(FieldSchema::new_sparse_float_vector("sparse", ""))
+        .add_function(FunctionSchema::bm25("text", "sparse"))
+        .build()?;
+    client.create_collection(schema, None).await?;
+
+    let described = client.describe_collection(&collection_name).await?;
+    let functions = &described.schema.functions;
+    assert_eq!(functions.len(), 1);
+    assert_eq!(functions[0].name, "text_bm25");
+    assert_eq!(functions[0].r#type, FunctionType::Bm25 as i32);
+    assert_eq!(functions[0].input_field_names, vec!["text".to_string()]);
+    assert_eq!(functions[0].output_field_names, vec!["sparse".to_string()]);
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_search_text_bm25() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", false))
+        .add_field(
+            FieldSchema::new_varchar("text", "", 1024)
+                .with_analyzer()
+                .with_match(),
+        )
+        .add_field(FieldSchema::new_sparse_float_vector("sparse", "").with_bm25_stats())
+        .add_bm25_function("text_bm25", "text", "sparse")
+        .build()?;
+    client.create_collection(schema.clone(), None).await?;
+
+    let docs = [
+        "vector databases store embeddings",
+        "the quick brown fox jumps over the lazy dog",
+        "milvus is a vector database built for similarity search",
+    ];
+    let columns = vec![
+        FieldColumn::new(schema.get_field("id").unwrap(), vec![0i64, 1, 2]),
+        FieldColumn::new(
+            schema.get_field("text").unwrap(),
+            docs.iter().map(|d| d.to_string()).collect::<Vec<_>>(),
+        ),
+    ];
+    client.insert(&collection_name, columns, None).await?;
+    client.flush(&collection_name).await?;
+    client
+        .create_index(
+            &collection_name,
+            "sparse",
+            IndexParams::new(
+                "sparse_index".to_owned(),
+                IndexType::SparseInvertedIndex,
+                MetricType::BM25,
+                HashMap::new(),
+            ),
+        )
+        .await?;
+    client
+        .load_collection(&collection_name, Some(LoadOptions::default()))
+        .await?;
+
+    let hits = client
+        .search_text(&collection_name, "text", "vector database", 3)
+        .await?;
+    let ids: Vec<i64> = hits
+        .iter()
+        .map(|hit| match hit.id {
+            Value::Long(id) => id,
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(ids.len(), 2, "{ids:?}");
+    assert!(!ids.contains(&1), "{ids:?}");
+
+    let options = QueryOptions::default().output_fields(vec!["id".to_owned()]);
+    let matched = client
+        .query_strong(
+            &collection_name,
+            Expr::text_match("text", "fox").as_str(),
+            &options,
+        )
+        .await?;
+    assert!(matches!(&matched[0].value, ValueVec::Long(ids) if ids == &[1]));
+
+    let err = client
+        .search_text(&collection_name, "id", "vector", 3)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidParameter(..)), "{err}");
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn collection_search_text_custom_analyzer() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let collection_name = format!("test_collection_{}", gen_random_name());
+    let analyzer = serde_json::json!({
+        "tokenizer": "standard",
+        "filter": ["lowercase", {"type": "stop", "stop_words": ["the", "over"]}],
+    });
+    let schema = CollectionSchemaBuilder::new(&collection_name, "")
+        .add_field(FieldSchema::new_primary_int64("id", "", false))
+        .add_field(FieldSchema::varchar_with_analyzer(
+            "text",
+            1024,
+            analyzer.clone(),
+        )?)
+        .add_field(FieldSchema::new_sparse_float_vector("sparse", "").with_bm25_stats())
+        .add_bm25_function("text_bm25", "text", "sparse")
+        .build()?;
+    client.create_collection(schema.clone(), None).await?;
+
+    let described: CollectionSchema = client
+        .describe_collection(&collection_name)
+        .await?
+        .schema
+        .into();
+    let text = described.get_field("text").unwrap();
+    assert_eq!(text.analyzer_params.as_ref(), Some(&analyzer));
+
+    let docs = [
+        "Vector databases store embeddings",
+        "the quick brown fox jumps over the lazy dog",
+        "Milvus is a VECTOR database built for similarity search",
+    ];
+    let columns = vec![
+        FieldColumn::new(schema.get_field("id").unwrap(), vec![0i64, 1, 2]),
+        FieldColumn::new(
+            schema.get_field("text").unwrap(),
+            docs.iter().map(|d| d.to_string()).collect::<Vec<_>>(),
+        ),
+    ];
+    client.insert(&collection_name, columns, None).await?;
+    client.flush(&collection_name).await?;
+    client
+        .create_index(
+            &collection_name,
+            "sparse",
+            IndexParams::new(
+                "sparse_index".to_owned(),
+                IndexType::SparseInvertedIndex,
+                MetricType::BM25,
+                HashMap::new(),
+            ),
+        )
+        .await?;
+    client
+        .load_collection(&collection_name, Some(LoadOptions::default()))
+        .await?;
+
+    // the lowercase filter makes the search case insensitive
+    let hits = client
+        .search_text(&collection_name, "text", "vector", 3)
+        .await?;
+    let mut ids: Vec<i64> = hits
+        .iter()
+        .map(|hit| match hit.id {
+            Value::Long(id) => id,
+            _ => unreachable!(),
+        })
+        .collect();
+    ids.sort();
+    assert_eq!(ids, vec![0, 2]);
+
+    client.drop_collection(&collection_name).await?;
+    Ok(())
+}
@@ -15,10 +15,15 @@
 // limitations under the License.
 
 use milvus::client::{self, *};
-use milvus::error::Result;
-use milvus::index::IndexType;
-use milvus::options::CreateCollectionOptions;
+use milvus::collection::Hit;
+use milvus::data::FieldColumn;
+use milvus::error::{Error, Result};
+use milvus::index::{IndexParams, IndexType, MetricType};
+use milvus::mutate::{DeleteOptions, InsertOptions};
+use milvus::options::{CreateCollectionOptions, GetStatsOptions};
 use milvus::proto::schema;
+use milvus::query::{FindDuplicatesOptions, QueryOptions};
+use milvus::value::{Value, ValueVec};
 use milvus::{collection, schema::*};
 use std::collections::HashMap;
 
@@ -61,3 +66,271 @@ async fn load_release_partitions() -> Result<()> {
     client.drop_collection(schema.name()).await?;
     Ok(())
 }
+
+#[tokio::test]
+#[ignore]
+async fn loading_progress_scoped_to_partition() -> Result<()> {
+    let (client, schema) = create_test_collection(true).await?;
+    for partition in ["partition_A", "partition_B"] {
+        client
+            .create_partition(schema.name().to_string(), partition.to_string())
+            .await?;
+    }
+    client.release_collection(schema.name()).await?;
+
+    client
+        .load_partitions(schema.name(), vec!["partition_A"], 0, None)
+        .await?;
+
+    let progress = |partition: &str| {
+        client.get_loading_progress(schema.name(), Some(vec![partition.to_string()]))
+    };
+    assert_eq!(progress("partition_A").await?, 100);
+    assert_eq!(progress("partition_B").await?, 0);
+
+    client.drop_collection(schema.name()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn count_and_stats_scoped_to_partition() -> Result<()> {
+    // the fixture's ENTITYNUM rows are in the default partition
+    let (client, schema) = create_test_collection(false).await?;
+    for partition in ["partition_A", "partition_B"] {
+        client
+            .create_partition(schema.name().to_string(), partition.to_string())
+            .await?;
+    }
+
+    let rows = [("partition_A", 3i64), ("partition_B", 5)];
+    let mut next_id = 0;
+    for (partition, num_rows) in rows {
+        let columns = vec![
+            FieldColumn::new(
+                schema.get_field("id").unwrap(),
+                (next_id..next_id + num_rows).collect::<Vec<_>>(),
+            ),
+            FieldColumn::new(
+                schema.get_field(DEFAULT_VEC_FIELD).unwrap(),
+                gen_random_f32_vector_custom(num_rows, DEFAULT_DIM),
+            ),
+        ];
+        client
+            .insert(
+                schema.name(),
+                columns,
+                Some(InsertOptions::with_partition_name(partition.to_string())),
+            )
+            .await?;
+        next_id += num_rows;
+    }
+    client.flush(schema.name()).await?;
+
+    // the fixture has indexed the collection, reload it with the new partitions
+    client.release_collection(schema.name()).await?;
+    client.load_collection(schema.name(), None).await?;
+
+    for (partition, num_rows) in rows {
+        let options = QueryOptions::new()
+            .partition_names(vec![partition.to_string()])
+            .consistency_level(ConsistencyLevel::Strong);
+        assert_eq!(client.count(schema.name(), "", &options).await?, num_rows);
+
+        let stats = client
+            .get_collection_stats(
+                schema.name(),
+                Some(GetStatsOptions::with_partition_names(vec![
+                    partition.to_string(),
+                ])),
+            )
+            .await?;
+        assert_eq!(stats["row_count"], num_rows.to_string());
+    }
+
+    let options = QueryOptions::new().consistency_level(ConsistencyLevel::Strong);
+    assert_eq!(
+        client.count(schema.name(), "", &options).await?,
+        ENTITYNUM + 8
+    );
+    let stats = client.get_collection_stats(schema.name(), None).await?;
+    assert_eq!(stats["row_count"], (ENTITYNUM + 8).to_string());
+
+    client.drop_collection(schema.name()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn delete_scoped_to_partition() -> Result<()> {
+    let (client, schema) = create_test_collection(false).await?;
+    for partition in ["partition_A", "partition_B"] {
+        client
+            .create_partition(schema.name().to_string(), partition.to_string())
+            .await?;
+    }
+
+    for (partition, ids) in [("partition_A", [1i64, 2]), ("partition_B", [3, 4])] {
+        let columns = vec![
+            FieldColumn::new(schema.get_field("id").unwrap(), ids.to_vec()),
+            FieldColumn::new(
+                schema.get_field(DEFAULT_VEC_FIELD).unwrap(),
+                gen_random_f32_vector(2),
+            ),
+        ];
+        client
+            .insert(
+                schema.name(),
+                columns,
+                Some(InsertOptions::with_partition_name(partition.to_string())),
+            )
+            .await?;
+    }
+
+    // the fixture has indexed the collection, reload it with the new partitions
+    client.release_collection(schema.name()).await?;
+    client.load_collection(schema.name(), None).await?;
+
+    let err = client
+        .delete(
+            schema.name(),
+            &DeleteOptions::with_filter("id > 0".to_string())
+                .partition_name("partition_C".to_string()),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Collection(collection::Error::PartitionNotFound(_, partition)) if partition == "partition_C"
+    ));
+
+    client
+        .delete(
+            schema.name(),
+            &DeleteOptions::with_filter("id > 0".to_string())
+                .partition_name("partition_A".to_string()),
+        )
+        .await?;
+
+    for (partition, expected) in [("partition_A", 0i64), ("partition_B", 2)] {
+        let options = QueryOptions::new()
+            .output_fields(vec!["count(*)".to_string()])
+            .partition_names(vec![partition.to_string()]);
+        let result = client.query_strong(schema.name(), "", &options).await?;
+        assert!(
+            matches!(&result[0].value, ValueVec::Long(count) if count[0] == expected),
+            "{partition}: {:?}",
+            result[0].value
+        );
+    }
+
+    client.drop_collection(schema.name()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn create_partition_if_not_exists() -> Result<()> {
+    let (client, schema) = create_test_collection(true).await?;
+
+    assert!(
+        client
+            .create_partition_if_not_exists(schema.name(), "partition_A")
+            .await?
+    );
+    assert!(
+        !client
+            .create_partition_if_not_exists(schema.name(), "partition_A")
+            .await?
+    );
+    assert!(
+        client
+            .has_partition(schema.name().to_string(), "partition_A".to_string())
+            .await?
+    );
+
+    client.drop_collection(schema.name()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn find_duplicates_scoped_to_partition() -> Result<()> {
+    let (client, schema) = create_test_collection(false).await?;
+    for partition in ["partition_A", "partition_B"] {
+        client
+            .create_partition(schema.name().to_string(), partition.to_string())
+            .await?;
+    }
+
+    // Each partition holds a near-duplicate of the same vector
+    let base = gen_random_f32_vector(1);
+    let near: Vec<f32> = base.iter().map(|x| x + 0.0001).collect();
+    for (partition, id, vector) in [("partition_A", 1i64, &base), ("partition_B", 2, &near)] {
+        let columns = vec![
+            FieldColumn::new(schema.get_field("id").unwrap(), vec![id]),
+            FieldColumn::new(schema.get_field(DEFAULT_VEC_FIELD).unwrap(), vector.clone()),
+        ];
+        client
+            .insert(
+                schema.name(),
+                columns,
+                Some(InsertOptions::with_partition_name(partition.to_string())),
+            )
+            .await?;
+    }
+    client.flush(schema.name()).await?;
+
+    // replace the fixture's L2 index with a COSINE one
+    client.release_collection(schema.name()).await?;
+    client
+        .drop_index(schema.name(), DEFAULT_VEC_FIELD, Some(DEFAULT_INDEX_NAME))
+        .await?;
+    let index_params = IndexParams::new(
+        DEFAULT_INDEX_NAME.to_owned(),
+        IndexType::Flat,
+        MetricType::COSINE,
+        HashMap::new(),
+    );
+    client
+        .create_index(schema.name(), DEFAULT_VEC_FIELD, index_params)
+        .await?;
+    client.load_collection(schema.name(), None).await?;
+
+    let find = |partitions: Vec<&str>| {
+        let options = FindDuplicatesOptions::new()
+            .partition_names(partitions.into_iter().map(String::from).collect());
+        client.find_duplicates(
+            schema.name(),
+            DEFAULT_VEC_FIELD,
+            vec![base.clone()],
+            0.99,
+            MetricType::COSINE,
+            Some(options),
+        )
+    };
+
+    let ids = |groups: Vec<Vec<Hit>>| -> Vec<i64> {
+        let mut ids: Vec<i64> = groups[0]
+            .iter()
+            .map(|hit| match hit.id {
+                Value::Long(id) => id,
+                _ => panic!("unexpected id type"),
+            })
+            .collect();
+        ids.sort();
+        ids
+    };
+    assert_eq!(ids(find(vec!["partition_A"]).await?), vec![1]);
+    assert_eq!(ids(find(vec!["partition_B"]).await?), vec![2]);
+    assert_eq!(ids(find(vec![]).await?), vec![1, 2]);
+
+    let err = find(vec!["partition_C"]).await.unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Collection(collection::Error::PartitionNotFound(_, partition)) if partition == "partition_C"
+    ));
+
+    client.drop_collection(schema.name()).await?;
+    Ok(())
+}
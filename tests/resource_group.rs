@@ -0,0 +1,42 @@
+// Licensed to the LF AI & Data foundation under one
+// or more contributor license agreements. See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership. The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Resource groups need a cluster with spare query nodes, run these with
+// `cargo test --features resource-group-tests --test resource_group`
+#![cfg(feature = "resource-group-tests")]
+
+use milvus::client::Client;
+use milvus::error::Result;
+use milvus::resource_group::CreateRgOptions;
+
+const URL: &str = "http://localhost:19530";
+
+#[tokio::test]
+async fn create_and_list_resource_group() -> Result<()> {
+    let client = Client::new(URL).await?;
+    let name = format!("test_rg_{}", rand::random::<u32>());
+
+    let options = CreateRgOptions::new().requests(0).limits(1);
+    client.create_resource_group(&name, Some(options)).await?;
+    assert!(client.list_resource_groups().await?.contains(&name));
+
+    let group = client.describe_resource_group(&name).await?.unwrap();
+    assert_eq!(group.name, name);
+
+    client.drop_resource_group(&name).await?;
+    assert!(!client.list_resource_groups().await?.contains(&name));
+    Ok(())
+}
@@ -7,7 +7,7 @@ use milvus::client::*;
 #[tokio::test]
 async fn test_get_collection_stats() {
     let (client, collection) = create_test_collection(true).await.unwrap();
-    let result = client.get_collection_stats(collection.name()).await;
+    let result = client.get_collection_stats(collection.name(), None).await;
 
     assert!(result.is_ok());
 